@@ -2,9 +2,9 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE
 
-#[cfg(features = "deflate")]
+#[cfg(all(feature = "deflate", feature = "tokio"))]
 mod inner {
-    use async_zip::write::ZipFileWriter;
+    use async_zip::tokio::write::ZipFileWriter;
     use async_zip::{Compression, ZipEntryBuilder};
 
     use std::path::Path;
@@ -15,6 +15,7 @@ mod inner {
     use futures::StreamExt;
     use futures_lite::io::AsyncWriteExt;
     use tokio::fs::File;
+    use tokio::io::AsyncWriteExt as _;
     use uuid::Uuid;
 
     const TMP_DIR: &str = "./tmp/";
@@ -25,7 +26,7 @@ mod inner {
 
     impl ResponseError for CreationError {}
 
-    async fn do_main() -> std::io::Result<()> {
+    pub async fn do_main() -> std::io::Result<()> {
         let tmp_path = Path::new(TMP_DIR);
 
         if !tmp_path.exists() {
@@ -49,21 +50,21 @@ mod inner {
     async fn create_archive(mut body: Multipart) -> Result<String, anyhow::Error> {
         let archive_name = format!("tmp/{}", Uuid::new_v4());
         let mut archive = File::create(archive_name.clone()).await?;
-        let mut writer = ZipFileWriter::new(&mut archive);
+        let mut writer = ZipFileWriter::with_tokio(&mut archive);
 
         while let Some(item) = body.next().await {
-            let mut field = item?;
+            let mut field = item.map_err(|err| anyhow::anyhow!("{err}"))?;
 
-            let filename = match field.content_disposition().get_filename() {
+            let filename = match field.content_disposition().and_then(|cd| cd.get_filename()) {
                 Some(filename) => sanitize_filename::sanitize(filename),
                 None => Uuid::new_v4().to_string(),
             };
 
-            let builder = ZipEntryBuilder::new(filename, Compression::Deflate);
+            let builder = ZipEntryBuilder::new(filename.into(), Compression::Deflate);
             let mut entry_writer = writer.write_entry_stream(builder).await.unwrap();
 
             while let Some(chunk) = field.next().await {
-                entry_writer.write_all_buf(&mut chunk?).await?;
+                entry_writer.write_all(&chunk.map_err(|err| anyhow::anyhow!("{err}"))?).await?;
             }
 
             entry_writer.close().await.unwrap();
@@ -78,7 +79,7 @@ mod inner {
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    #[cfg(features = "deflate")]
+    #[cfg(all(feature = "deflate", feature = "tokio"))]
     {
         inner::do_main().await?;
     }
@@ -3,7 +3,7 @@
 
 #[tokio::main]
 async fn main() {
-    #[cfg(features = "deflate")]
+    #[cfg(all(feature = "deflate", feature = "tokio"))]
     if let Err(err) = inner::run().await {
         eprintln!("Error: {}", err);
         eprintln!("Usage: cli_compress <input file or directory> <output ZIP file name>");
@@ -11,19 +11,19 @@ async fn main() {
     }
 }
 
-#[cfg(features = "deflate")]
+#[cfg(all(feature = "deflate", feature = "tokio"))]
 mod inner {
 
-    use async_zip::base::write::ZipFileWriter;
+    use async_zip::tokio::write::ZipFileWriter;
     use async_zip::{Compression, ZipEntryBuilder};
 
     use std::path::{Path, PathBuf};
 
     use anyhow::{anyhow, bail, Result};
-    use futures_lite::io::AsyncReadExt;
     use tokio::fs::File;
+    use tokio::io::AsyncReadExt;
 
-    async fn run() -> Result<()> {
+    pub async fn run() -> Result<()> {
         let mut args = std::env::args().skip(1);
 
         let input_str = args.next().ok_or(anyhow!("No input file or directory specified."))?;
@@ -42,7 +42,7 @@ mod inner {
             bail!("The input file or directory specified doesn't exist.");
         }
 
-        let mut output_writer = ZipFileWriter::new(File::create(output_path).await?);
+        let mut output_writer = ZipFileWriter::with_tokio(File::create(output_path).await?);
 
         if input_path.is_dir() {
             handle_directory(input_path, &mut output_writer).await?;
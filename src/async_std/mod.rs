@@ -0,0 +1,14 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A set of [`async_std`]-specific features.
+//!
+//! # Usage
+//! As [`async_std`]'s IO types implement the same async IO traits as the [`base`] implementation, they can be used with
+//! its types directly and without any compatibility wrapper. This module instead provides features which are
+//! specific to [`async_std`], such as reading over a file system path via [`async_std::fs`].
+
+#[cfg(doc)]
+use crate::base;
+
+pub mod read;
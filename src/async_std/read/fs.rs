@@ -0,0 +1,106 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A concurrent ZIP reader which acts over a file system path, using [`async_std::fs`].
+//!
+//! This mirrors the `tokio::read::fs` module. The provided path is wrapped within an [`Arc`] to allow shared ownership,
+//! and a new [`File`] is opened from the path for each entry reader, allowing concurrent [`ZipEntryReader`]s.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::async_std::read::fs::ZipFileReader;
+//! # use async_zip::error::Result;
+//! #
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new("./foo.zip").await?;
+//!     let (first, second) = futures_lite::future::zip(read(&reader, 0), read(&reader, 1)).await;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//!
+//! async fn read(reader: &ZipFileReader, index: usize) -> Result<Vec<u8>> {
+//!     reader.reader_with_entry(index).await?.read_to_vec().await
+//! }
+//! ```
+
+use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ::async_std::fs::File;
+use futures_lite::io::BufReader;
+
+/// The capacity of the buffer between an entry's file and its decompressor.
+const BUFFER_CAPACITY: usize = 64 * 1024;
+
+struct Inner {
+    path: PathBuf,
+    file: ZipFile,
+}
+
+/// A concurrent ZIP reader which acts over a file system path.
+#[derive(Clone)]
+pub struct ZipFileReader {
+    inner: Arc<Inner>,
+}
+
+impl ZipFileReader {
+    /// Constructs a new ZIP reader from a file system path.
+    pub async fn new<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let file = crate::base::read::file(BufReader::new(File::open(path.as_ref()).await?)).await?;
+        Ok(ZipFileReader::from_raw_parts(path, file))
+    }
+
+    /// Constructs a ZIP reader from a file system path and ZIP file information derived from that path.
+    ///
+    /// Providing a [`ZipFile`] that wasn't derived from that path may lead to inaccurate parsing.
+    pub fn from_raw_parts<P>(path: P, file: ZipFile) -> ZipFileReader
+    where
+        P: AsRef<Path>,
+    {
+        ZipFileReader { inner: Arc::new(Inner { path: path.as_ref().to_owned(), file }) }
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.inner.file
+    }
+
+    /// Returns the file system path provided to the reader during construction.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_without_entry(
+        &self,
+        index: usize,
+    ) -> Result<ZipEntryReader<'static, BufReader<File>, WithoutEntry>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut fs_file = BufReader::with_capacity(BUFFER_CAPACITY, File::open(&self.inner.path).await?);
+
+        stored_entry.seek_to_data_offset(&mut fs_file).await?;
+
+        ZipEntryReader::new_with_owned(
+            fs_file,
+            stored_entry.entry.readable_compression()?,
+            stored_entry.entry.compressed_size(),
+        )
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, BufReader<File>, WithEntry<'_>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let reader = self.reader_without_entry(index).await?;
+
+        Ok(reader.into_with_entry(stored_entry))
+    }
+}
@@ -15,6 +15,7 @@ pub struct CombinedCentralDirectoryRecord {
     pub num_entries_in_directory: u64,
     pub directory_size: u64,
     pub offset_of_start_of_directory: u64,
+    #[allow(dead_code)]
     pub file_comment_length: u16,
 }
 
@@ -1,6 +1,9 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "lzma")]
+use crate::base::read::io::lzma::LzmaZipReader;
+use crate::error::{Result, ZipError};
 use crate::spec::Compression;
 
 use std::pin::Pin;
@@ -29,11 +32,13 @@ pub(crate) enum CompressedReader<R> {
     #[cfg(feature = "bzip2")]
     Bz(#[pin] bufread::BzDecoder<R>),
     #[cfg(feature = "lzma")]
-    Lzma(#[pin] bufread::LzmaDecoder<R>),
+    Lzma(#[pin] bufread::LzmaDecoder<LzmaZipReader<R>>),
     #[cfg(feature = "zstd")]
     Zstd(#[pin] bufread::ZstdDecoder<R>),
     #[cfg(feature = "xz")]
     Xz(#[pin] bufread::XzDecoder<R>),
+    /// Data compressed with an unsupported method, which can't be read but may still be skipped over.
+    Unsupported(#[pin] R, u16),
 }
 
 impl<R> CompressedReader<R>
@@ -41,7 +46,18 @@ where
     R: AsyncBufRead + Unpin,
 {
     /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer.
-    pub(crate) fn new(reader: R, compression: Compression) -> Self {
+    ///
+    /// Fails with [`ZipError::CompressionNotSupported`] if the compression method isn't supported.
+    pub(crate) fn new(reader: R, compression: Compression) -> Result<Self> {
+        Ok(CompressedReader::new_or_unsupported(reader, compression.supported()?))
+    }
+
+    /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer, whose reads fail with
+    /// [`ZipError::CompressionNotSupported`] if the compression method isn't supported.
+    ///
+    /// The inner reader remains accessible via [`CompressedReader::unsupported_mut()`], so that the data can still be
+    /// skipped over.
+    pub(crate) fn new_or_unsupported(reader: R, compression: Compression) -> Self {
         match compression {
             Compression::Stored => CompressedReader::Stored(reader),
             #[cfg(feature = "deflate")]
@@ -51,11 +67,50 @@ where
             #[cfg(feature = "bzip2")]
             Compression::Bz => CompressedReader::Bz(bufread::BzDecoder::new(reader)),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedReader::Lzma(bufread::LzmaDecoder::new(reader)),
+            Compression::Lzma => CompressedReader::Lzma(bufread::LzmaDecoder::new(LzmaZipReader::new(reader))),
             #[cfg(feature = "zstd")]
             Compression::Zstd => CompressedReader::Zstd(bufread::ZstdDecoder::new(reader)),
             #[cfg(feature = "xz")]
             Compression::Xz => CompressedReader::Xz(bufread::XzDecoder::new(reader)),
+            Compression::Unsupported(method) => CompressedReader::Unsupported(reader, method),
+        }
+    }
+
+    /// Returns a mutable reference to the inner value if the compression method is unsupported, alongside that method.
+    pub(crate) fn unsupported_mut(&mut self) -> Option<(&mut R, u16)> {
+        match self {
+            CompressedReader::Unsupported(inner, method) => Some((inner, *method)),
+            _ => None,
+        }
+    }
+
+    /// Sets the known uncompressed size of the data, which must be done before any data is read.
+    ///
+    /// This is only used by LZMA, whose data needn't end with an end-of-stream marker if the size is known.
+    pub(crate) fn set_uncompressed_size(&mut self, _size: u64) {
+        #[cfg(feature = "lzma")]
+        if let CompressedReader::Lzma(inner) = self {
+            inner.get_mut().set_uncompressed_size(_size);
+        }
+    }
+
+    /// Returns a reference to the inner value.
+    pub(crate) fn get_ref(&self) -> &R {
+        match self {
+            CompressedReader::Stored(inner) => inner,
+            #[cfg(feature = "deflate")]
+            CompressedReader::Deflate(inner) => inner.get_ref(),
+            #[cfg(feature = "deflate64")]
+            CompressedReader::Deflate64(inner) => inner.get_ref(),
+            #[cfg(feature = "bzip2")]
+            CompressedReader::Bz(inner) => inner.get_ref(),
+            #[cfg(feature = "lzma")]
+            CompressedReader::Lzma(inner) => inner.get_ref().get_ref(),
+            #[cfg(feature = "zstd")]
+            CompressedReader::Zstd(inner) => inner.get_ref(),
+            #[cfg(feature = "xz")]
+            CompressedReader::Xz(inner) => inner.get_ref(),
+            CompressedReader::Unsupported(inner, _) => inner,
         }
     }
 
@@ -70,11 +125,12 @@ where
             #[cfg(feature = "bzip2")]
             CompressedReader::Bz(inner) => inner.into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedReader::Lzma(inner) => inner.into_inner(),
+            CompressedReader::Lzma(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "zstd")]
             CompressedReader::Zstd(inner) => inner.into_inner(),
             #[cfg(feature = "xz")]
             CompressedReader::Xz(inner) => inner.into_inner(),
+            CompressedReader::Unsupported(inner, _) => inner,
         }
     }
 }
@@ -98,6 +154,9 @@ where
             CompressedReaderProj::Zstd(inner) => inner.poll_read(c, b),
             #[cfg(feature = "xz")]
             CompressedReaderProj::Xz(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Unsupported(_, method) => {
+                Poll::Ready(Err(std::io::Error::other(ZipError::CompressionNotSupported(*method))))
+            }
         }
     }
 }
@@ -1,15 +1,19 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(doc)]
+use crate::base::read::seek;
+
 use crate::base::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader};
+use crate::crc::Crc32;
 use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
 use crate::spec::Compression;
 
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::task::{ready, Context, Poll};
 
-use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, Take};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, SeekFrom, Take};
 use pin_project::pin_project;
 
 /// A type which encodes that [`ZipEntryReader`] has associated entry data.
@@ -24,6 +28,27 @@ pub struct ZipEntryReader<'a, R, E> {
     #[pin]
     reader: HashedReader<CompressedReader<Take<OwnedReader<'a, R>>>>,
     entry: E,
+    compressed_size: u64,
+    size_limit: u64,
+    max_ratio: u64,
+    produced: u64,
+    /// The offset of the entry's data within the underlying reader, if known.
+    data_offset: Option<u64>,
+    buffer: ReadBuffer,
+}
+
+/// The capacity of the buffer allocated once an entry reader is first used via [`AsyncBufRead`].
+const BUFFER_CAPACITY: usize = 8 * 1024;
+
+/// The largest number of bytes preallocated by [`ZipEntryReader::read_to_vec()`] from an entry's declared size.
+const MAX_PREALLOCATION: u64 = 64 * 1024;
+
+/// Decompressed data which has been read ahead via [`AsyncBufRead`], but not yet consumed.
+#[derive(Default)]
+struct ReadBuffer {
+    data: Vec<u8>,
+    pos: usize,
+    filled: usize,
 }
 
 impl<'a, R> ZipEntryReader<'a, R, WithoutEntry>
@@ -31,23 +56,65 @@ where
     R: AsyncBufRead + Unpin,
 {
     /// Constructs a new entry reader from its required parameters (incl. an owned R).
-    pub(crate) fn new_with_owned(reader: R, compression: Compression, size: u64) -> Self {
-        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Owned(reader).take(size), compression));
-        Self { reader, entry: WithoutEntry }
+    pub(crate) fn new_with_owned(reader: R, compression: Compression, size: u64) -> Result<Self> {
+        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Owned(reader).take(size), compression)?);
+        Ok(Self::from_parts(reader, size))
+    }
+
+    /// Constructs a new entry reader from its required parameters (incl. an owned R), whose reads fail if the
+    /// compression method is unsupported rather than its construction.
+    pub(crate) fn new_with_owned_or_unsupported(reader: R, compression: Compression, size: u64) -> Self {
+        let reader = CompressedReader::new_or_unsupported(OwnedReader::Owned(reader).take(size), compression);
+        Self::from_parts(HashedReader::new(reader), size)
     }
 
     /// Constructs a new entry reader from its required parameters (incl. a mutable borrow of an R).
-    pub(crate) fn new_with_borrow(reader: &'a mut R, compression: Compression, size: u64) -> Self {
-        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Borrow(reader).take(size), compression));
-        Self { reader, entry: WithoutEntry }
+    pub(crate) fn new_with_borrow(reader: &'a mut R, compression: Compression, size: u64) -> Result<Self> {
+        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Borrow(reader).take(size), compression)?);
+        Ok(Self::from_parts(reader, size))
+    }
+
+    fn from_parts(reader: HashedReader<CompressedReader<Take<OwnedReader<'a, R>>>>, compressed_size: u64) -> Self {
+        let (size_limit, max_ratio) = (u64::MAX, u64::MAX);
+        let (entry, produced, data_offset, buffer) = (WithoutEntry, 0, None, ReadBuffer::default());
+        Self { reader, entry, compressed_size, size_limit, max_ratio, produced, data_offset, buffer }
     }
 
-    pub(crate) fn into_with_entry(self, entry: &'a ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Borrow(entry)) }
+    pub(crate) fn into_with_entry(mut self, entry: &'a ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
+        set_known_uncompressed_size(&mut self.reader.reader, entry);
+        self.with_entry_data(WithEntry(OwnedEntry::Borrow(entry)))
     }
 
-    pub(crate) fn into_with_entry_owned(self, entry: ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Owned(entry)) }
+    pub(crate) fn into_with_entry_owned(mut self, entry: ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
+        set_known_uncompressed_size(&mut self.reader.reader, &entry);
+        self.with_entry_data(WithEntry(OwnedEntry::Owned(Box::new(entry))))
+    }
+
+    fn with_entry_data<E>(self, entry: E) -> ZipEntryReader<'a, R, E> {
+        let Self { reader, compressed_size, size_limit, max_ratio, produced, data_offset, buffer, .. } = self;
+        ZipEntryReader { reader, entry, compressed_size, size_limit, max_ratio, produced, data_offset, buffer }
+    }
+}
+
+impl<'a, R, E> ZipEntryReader<'a, R, E>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Reads decompressed data directly from the inner reader, enforcing any limits set.
+    fn poll_read_inner(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let read = ready!(self.as_mut().project().reader.poll_read(c, b))?;
+        let consumed = self.compressed_bytes_read();
+        let this = self.project();
+        *this.produced += read as u64;
+
+        if *this.produced > *this.size_limit {
+            return Poll::Ready(Err(std::io::Error::other(ZipError::SizeLimitExceeded(*this.size_limit))));
+        }
+        if *this.produced > consumed.saturating_mul(*this.max_ratio) {
+            return Poll::Ready(Err(std::io::Error::other(ZipError::CompressionRatioExceeded)));
+        }
+
+        Poll::Ready(Ok(read))
     }
 }
 
@@ -55,8 +122,41 @@ impl<'a, R, E> AsyncRead for ZipEntryReader<'a, R, E>
 where
     R: AsyncBufRead + Unpin,
 {
-    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
-        self.project().reader.poll_read(c, b)
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        // Any data already read ahead via `AsyncBufRead` must be returned first.
+        let buffer = &mut self.buffer;
+        if buffer.pos < buffer.filled {
+            let len = b.len().min(buffer.filled - buffer.pos);
+            b[..len].copy_from_slice(&buffer.data[buffer.pos..buffer.pos + len]);
+            buffer.pos += len;
+            return Poll::Ready(Ok(len));
+        }
+
+        self.poll_read_inner(c, b)
+    }
+}
+
+impl<'a, R, E> AsyncBufRead for ZipEntryReader<'a, R, E>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(mut self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        if self.buffer.pos >= self.buffer.filled {
+            let mut data = std::mem::take(&mut self.buffer.data);
+            data.resize(BUFFER_CAPACITY, 0);
+
+            let result = self.as_mut().poll_read_inner(c, &mut data);
+            self.buffer.data = data;
+            self.buffer.filled = ready!(result)?;
+            self.buffer.pos = 0;
+        }
+
+        let this = self.project();
+        Poll::Ready(Ok(&this.buffer.data[this.buffer.pos..this.buffer.filled]))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        self.buffer.pos = (self.buffer.pos + amt).min(self.buffer.filled);
     }
 }
 
@@ -64,6 +164,41 @@ impl<'a, R, E> ZipEntryReader<'a, R, E>
 where
     R: AsyncBufRead + Unpin,
 {
+    /// Records the offset of the entry's data within the underlying reader, allowing it to be reset.
+    pub(crate) fn with_data_offset(mut self, offset: u64) -> Self {
+        self.data_offset = Some(offset);
+        self
+    }
+
+    /// Replaces the hasher used to compute the CRC32 value of the data read.
+    ///
+    /// By default, [`crc32fast::Hasher`] is used. This should be called before any data is read, as data already read
+    /// won't be reflected within the new hasher's checksum.
+    pub fn with_crc32_hasher(mut self, hasher: Box<dyn Crc32>) -> Self {
+        self.reader.hasher = hasher;
+        self
+    }
+
+    /// Limits the number of decompressed bytes this reader may produce.
+    ///
+    /// Once more than `max_bytes` have been produced, reads fail with [`ZipError::SizeLimitExceeded`]. This protects
+    /// against entries whose decompressed size far exceeds what their headers claim (ie. zip bombs).
+    pub fn with_limit(mut self, max_bytes: u64) -> Self {
+        self.size_limit = max_bytes;
+        self
+    }
+
+    /// Limits the ratio of decompressed bytes produced to the entry's compressed size.
+    ///
+    /// Once more than `max_ratio` times the number of compressed bytes consumed so far has been produced, reads fail
+    /// with [`ZipError::CompressionRatioExceeded`]. For example, a `max_ratio` of `1000` flags entries which decompress
+    /// at a ratio beyond 1000:1. As the ratio is measured against the data actually consumed, this also applies to
+    /// entries whose compressed size isn't known upfront (eg. those read by the stream reader with a data descriptor).
+    pub fn with_ratio_limit(mut self, max_ratio: u64) -> Self {
+        self.max_ratio = max_ratio;
+        self
+    }
+
     /// Computes and returns the CRC32 hash of bytes read by this reader so far.
     ///
     /// This hash should only be computed once EOF has been reached.
@@ -71,8 +206,42 @@ where
         self.reader.swap_and_compute_hash()
     }
 
-    /// Consumes this reader and returns the inner value.
-    pub(crate) fn into_inner(self) -> R {
+    /// Returns the running CRC32 hash of the bytes read by this reader so far.
+    ///
+    /// Unlike [`ZipEntryReader::compute_hash()`], this may be called at any point whilst streaming and doesn't reset
+    /// the hash. Once EOF has been reached, it can be compared against the expected value to validate the entry.
+    pub fn current_crc(&self) -> u32 {
+        self.reader.current_hash()
+    }
+
+    /// Returns the number of compressed bytes consumed from the underlying reader so far.
+    ///
+    /// This may be compared against the entry's compressed size to track progress. Decompressors may consume input
+    /// ahead of the data they've so far produced, so this can lead the number of decompressed bytes read.
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.compressed_size - self.reader.reader.get_ref().limit()
+    }
+
+    /// Consumes this reader and returns the inner reader, if it was owned rather than mutably borrowed.
+    ///
+    /// Readers returned by [`seek::ZipFileReader`] mutably borrow the underlying reader and so will return `None`. In
+    /// that case, the underlying reader is accessible again from the ZIP reader once this entry reader is dropped.
+    ///
+    /// The inner reader is positioned directly after the last compressed byte consumed by this reader. Decompressors
+    /// may consume input ahead of the data they've so far produced, so this isn't necessarily the position implied by
+    /// the number of decompressed bytes read.
+    pub fn into_inner(self) -> Option<R> {
+        self.reader.into_inner().into_inner().into_inner().into_owned()
+    }
+
+    /// Returns the raw reader over the entry's compressed data if its compression method is unsupported, alongside
+    /// that method.
+    pub(crate) fn unsupported_mut(&mut self) -> Option<(&mut Take<OwnedReader<'a, R>>, u16)> {
+        self.reader.reader.unsupported_mut()
+    }
+
+    /// Consumes this reader and returns the inner reader, which must have been owned.
+    pub(crate) fn owned_into_inner(self) -> R {
         self.reader.into_inner().into_inner().into_inner().owned_into_inner()
     }
 }
@@ -86,6 +255,11 @@ where
         self.entry.0.entry()
     }
 
+    /// Returns the CRC32 hash which the entry's data is expected to have, as stored within its metadata.
+    pub fn expected_crc(&self) -> u32 {
+        self.entry().crc32()
+    }
+
     /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
     ///
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_end()`].
@@ -99,6 +273,71 @@ where
         }
     }
 
+    /// Reads all bytes until EOF has been reached into a new vector, and verifies the CRC32 values.
+    ///
+    /// The vector is preallocated to the entry's uncompressed size (capped by any limit set via
+    /// [`ZipEntryReader::with_limit()`]), which is known up-front unless the entry was read via a data descriptor. As
+    /// this size is declared by the archive, the preallocation is also capped at 64 KiB and the vector grows as further
+    /// data arrives.
+    pub async fn read_to_vec(&mut self) -> Result<Vec<u8>> {
+        let capacity = self.entry().uncompressed_size().min(self.size_limit).min(MAX_PREALLOCATION);
+        let capacity = usize::try_from(capacity).unwrap_or(0);
+        let mut buf = vec![0; capacity];
+        let mut filled = 0;
+
+        // Fill the preallocated space directly, as `read_to_end()` grows the vector before probing for EOF.
+        while filled < capacity {
+            match self.read(&mut buf[filled..]).await? {
+                0 => break,
+                read => filled += read,
+            }
+        }
+        buf.truncate(filled);
+
+        // The entry's data may still exceed its claimed size.
+        let mut probe = [0; 1];
+        if filled == capacity && self.read(&mut probe).await? == 1 {
+            buf.push(probe[0]);
+            self.read_to_end(&mut buf).await?;
+        }
+
+        match self.compute_hash() == self.entry.0.entry().crc32() {
+            true => Ok(buf),
+            false => Err(ZipError::CRC32CheckError),
+        }
+    }
+
+    /// Copies all bytes until EOF has been reached into the provided writer, and verifies the CRC32 values.
+    ///
+    /// Returns the number of bytes copied and the validated CRC32 value. Data is passed through a fixed-size buffer
+    /// and each write is awaited before reading further, so a slow writer (eg. a socket) applies backpressure rather
+    /// than the entry being buffered in memory. Tokio writers can be used via `tokio_util::compat`.
+    pub async fn copy_to<W: AsyncWrite + Unpin>(&mut self, writer: W) -> Result<(u64, u32)> {
+        let copied = futures_lite::io::copy(&mut *self, writer).await?;
+        let crc = self.compute_hash();
+
+        match crc == self.entry().crc32() {
+            true => Ok((copied, crc)),
+            false => Err(ZipError::CRC32CheckError),
+        }
+    }
+
+    /// Reads all bytes until EOF has been reached into a new string, and verifies the CRC32 values.
+    ///
+    /// Like [`ZipEntryReader::read_to_vec()`] but additionally validates that the data is UTF-8. This is named so as
+    /// not to shadow [`AsyncReadExt::read_to_string()`].
+    pub async fn read_to_string_owned(&mut self) -> Result<String> {
+        String::from_utf8(self.read_to_vec().await?).map_err(|_| ZipError::StringNotUtf8)
+    }
+
+    /// Reads all bytes until EOF has been reached, verifies the CRC32 values, and deserialises them from JSON.
+    ///
+    /// Fails with [`ZipError::Json`] if the data isn't valid JSON for the requested type.
+    #[cfg(feature = "json")]
+    pub async fn read_json<T: serde::de::DeserializeOwned>(&mut self) -> Result<T> {
+        serde_json::from_slice(&self.read_to_vec().await?).map_err(ZipError::Json)
+    }
+
     /// Reads all bytes until EOF has been reached, placing them into buf, and verifies the CRC32 values.
     ///
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_string()`].
@@ -113,8 +352,17 @@ where
     }
 }
 
+/// Passes the entry's uncompressed size to the decompressor, if it's known.
+///
+/// Entries with a data descriptor read from their local file header (ie. when streaming) hold a zero size.
+fn set_known_uncompressed_size<R: AsyncBufRead + Unpin>(reader: &mut CompressedReader<R>, entry: &ZipEntry) {
+    if !(entry.data_descriptor && entry.uncompressed_size == 0) {
+        reader.set_uncompressed_size(entry.uncompressed_size);
+    }
+}
+
 enum OwnedEntry<'a> {
-    Owned(ZipEntry),
+    Owned(Box<ZipEntry>),
     Borrow(&'a ZipEntry),
 }
 
@@ -126,3 +374,32 @@ impl<'a> OwnedEntry<'a> {
         }
     }
 }
+
+impl<'a, R> ZipEntryReader<'a, R, WithEntry<'a>>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    /// Seeks back to the start of the entry's data and reinitialises the decompressor and CRC32 state.
+    ///
+    /// This allows a read which failed midway (eg. due to a flaky network-backed source) to be retried from the start
+    /// of the entry, as resuming mid-stream isn't possible for compressed data. Any limits set on this reader are
+    /// retained. Only readers returned by [`seek::ZipFileReader::reader_with_entry()`] know their data offset, so this
+    /// fails with [`ZipError::FeatureNotSupported`] for readers from other sources.
+    pub async fn reset(self) -> Result<Self> {
+        let data_offset =
+            self.data_offset.ok_or(ZipError::FeatureNotSupported("resetting a reader with an unknown data offset"))?;
+        let compression = self.entry().readable_compression()?;
+
+        let Self { reader, entry, compressed_size, size_limit, max_ratio, .. } = self;
+        let HashedReader { reader, mut hasher } = reader;
+        let mut inner = reader.into_inner().into_inner();
+        inner.seek(SeekFrom::Start(data_offset)).await?;
+        hasher.reset();
+
+        let mut reader = CompressedReader::new(inner.take(compressed_size), compression)?;
+        set_known_uncompressed_size(&mut reader, entry.0.entry());
+        let reader = HashedReader { reader, hasher };
+        let (produced, data_offset, buffer) = (0, Some(data_offset), ReadBuffer::default());
+        Ok(ZipEntryReader { reader, entry, compressed_size, size_limit, max_ratio, produced, data_offset, buffer })
+    }
+}
@@ -2,11 +2,11 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::base::read::io::poll_result_ok;
+use crate::crc::Crc32;
 
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
 
-use crc32fast::Hasher;
 use futures_lite::io::AsyncRead;
 use pin_project::pin_project;
 
@@ -15,7 +15,7 @@ use pin_project::pin_project;
 pub(crate) struct HashedReader<R> {
     #[pin]
     pub(crate) reader: R,
-    pub(crate) hasher: Hasher,
+    pub(crate) hasher: Box<dyn Crc32>,
 }
 
 impl<R> HashedReader<R>
@@ -24,16 +24,22 @@ where
 {
     /// Constructs a new wrapping reader from a generic [`AsyncRead`] implementer.
     pub(crate) fn new(reader: R) -> Self {
-        Self { reader, hasher: Hasher::default() }
+        Self { reader, hasher: Box::new(crc32fast::Hasher::new()) }
     }
 
-    /// Swaps the internal hasher and returns the computed CRC32 hash.
+    /// Resets the internal hasher and returns the computed CRC32 hash.
     ///
-    /// The internal hasher is taken and replaced with a newly-constructed one. As a result, this method should only be
-    /// called once EOF has been reached and it's known that no more data will be read, else the computed hash(s) won't
-    /// accurately represent the data read in.
+    /// As the internal hasher is reset, this method should only be called once EOF has been reached and it's known
+    /// that no more data will be read, else the computed hash(s) won't accurately represent the data read in.
     pub(crate) fn swap_and_compute_hash(&mut self) -> u32 {
-        std::mem::take(&mut self.hasher).finalize()
+        let hash = self.hasher.sum();
+        self.hasher.reset();
+        hash
+    }
+
+    /// Returns the CRC32 hash of the data read so far, without resetting the internal hasher.
+    pub(crate) fn current_hash(&self) -> u32 {
+        self.hasher.sum()
     }
 
     /// Consumes this reader and returns the inner value.
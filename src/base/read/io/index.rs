@@ -0,0 +1,312 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Restart points within Deflate streams, allowing decompression to begin part-way through an entry.
+//!
+//! Deflate offers no random access, but an encoder which performs a sync or full flush ends the current block on a
+//! byte boundary with an empty stored block (`00 00 FF FF`). Decompression can restart directly after such a marker
+//! as long as the decoder is first primed with the preceding 32 KiB of output, which we do by prepending a synthetic
+//! stored block holding that window.
+//!
+//! The marker bytes may also appear by chance within compressed data, so each candidate is only recorded once a
+//! decoder restarted from it has been verified to reproduce the output of the full pass.
+
+use crate::error::Result;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_compression::futures::bufread::DeflateDecoder;
+use flate2::{Decompress, FlushDecompress, Status};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, Chain, Take};
+use pin_project::pin_project;
+
+/// The maximum distance a Deflate back-reference may reach.
+const WINDOW_SIZE: usize = 32 * 1024;
+
+/// The number of output bytes a restarted decoder must reproduce before its restart point is trusted.
+const VERIFY_SIZE: usize = 4 * 1024;
+
+/// The empty stored block which terminates a sync or full flush.
+const FLUSH_MARKER: u32 = 0x0000FFFF;
+
+/// A point within a Deflate stream from which decompression can be restarted.
+#[derive(Debug, Clone)]
+pub(crate) struct SeekPoint {
+    pub(crate) compressed_offset: u64,
+    pub(crate) uncompressed_offset: u64,
+    pub(crate) window: Vec<u8>,
+}
+
+impl SeekPoint {
+    /// Returns a stored block which primes a fresh decoder with this point's window.
+    fn primer(&self) -> Vec<u8> {
+        if self.window.is_empty() {
+            return Vec::new();
+        }
+
+        let length = self.window.len() as u16;
+        let mut block = Vec::with_capacity(self.window.len() + 5);
+
+        // BFINAL = 0 and BTYPE = 00 (stored), padded to the byte boundary.
+        block.push(0x00);
+        block.extend_from_slice(&length.to_le_bytes());
+        block.extend_from_slice(&(!length).to_le_bytes());
+        block.extend_from_slice(&self.window);
+        block
+    }
+}
+
+/// An index of points from which a Deflate entry's decompression can be restarted.
+///
+/// Built via [`ZipFileReader::build_seek_index()`] and consumed by [`ZipFileReader::reader_at_offset()`].
+///
+/// [`ZipFileReader::build_seek_index()`]: crate::base::read::seek::ZipFileReader::build_seek_index
+/// [`ZipFileReader::reader_at_offset()`]: crate::base::read::seek::ZipFileReader::reader_at_offset
+#[derive(Debug, Clone)]
+pub struct SeekIndex {
+    pub(crate) entry_index: usize,
+    pub(crate) points: Vec<SeekPoint>,
+}
+
+impl SeekIndex {
+    /// Returns the index of the entry this seek index was built for.
+    pub fn entry_index(&self) -> usize {
+        self.entry_index
+    }
+
+    /// Returns the number of restart points within this index, including the start of the entry.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns whether this index holds no restart points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the closest restart point at or before the provided uncompressed offset.
+    pub(crate) fn point_before(&self, offset: u64) -> Option<&SeekPoint> {
+        self.points.iter().take_while(|point| point.uncompressed_offset <= offset).last()
+    }
+}
+
+/// A restart point which has yet to be verified against the output of the full pass.
+struct Candidate {
+    point: SeekPoint,
+    decoder: Decompress,
+    expected: Vec<u8>,
+    actual: Vec<u8>,
+    failed: bool,
+}
+
+impl Candidate {
+    fn new(point: SeekPoint, buffer: &mut [u8]) -> std::io::Result<Self> {
+        let mut decoder = Decompress::new(false);
+        inflate(&mut decoder, &point.primer(), buffer, |_| ())?;
+
+        Ok(Self { point, decoder, expected: Vec::new(), actual: Vec::new(), failed: false })
+    }
+
+    /// Returns whether the restarted decoder has reproduced enough of the full pass's output to be trusted.
+    fn verified(&self) -> bool {
+        self.expected.len() == VERIFY_SIZE && self.actual.len() == VERIFY_SIZE && self.expected == self.actual
+    }
+
+    /// Returns whether the restarted decoder has diverged from the full pass's output.
+    fn diverged(&self) -> bool {
+        let common = self.expected.len().min(self.actual.len());
+        self.failed || self.expected[..common] != self.actual[..common]
+    }
+}
+
+/// Feeds the entirety of `input` through `decoder`, passing all output to `sink`.
+///
+/// Returns whether the end of the Deflate stream was reached.
+fn inflate(
+    decoder: &mut Decompress,
+    mut input: &[u8],
+    buffer: &mut [u8],
+    mut sink: impl FnMut(&[u8]),
+) -> std::io::Result<bool> {
+    loop {
+        let (total_in, total_out) = (decoder.total_in(), decoder.total_out());
+        let status = decoder.decompress(input, buffer, FlushDecompress::None)?;
+
+        let consumed = (decoder.total_in() - total_in) as usize;
+        let produced = (decoder.total_out() - total_out) as usize;
+
+        input = &input[consumed..];
+        sink(&buffer[..produced]);
+
+        if status == Status::StreamEnd {
+            return Ok(true);
+        }
+        if produced < buffer.len() && (input.is_empty() || consumed == 0) {
+            return Ok(false);
+        }
+    }
+}
+
+/// Appends `data` to `buffer` without growing it past `limit` bytes.
+fn extend_bounded(buffer: &mut Vec<u8>, data: &[u8], limit: usize) {
+    let remaining = limit.saturating_sub(buffer.len());
+    buffer.extend_from_slice(&data[..remaining.min(data.len())]);
+}
+
+/// Decompresses an entry's raw Deflate stream in full, recording restart points at least `spacing` bytes apart.
+pub(crate) async fn build<R>(mut reader: R, spacing: u64) -> Result<Vec<SeekPoint>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut decoder = Decompress::new(false);
+    let mut input = vec![0; 16 * 1024];
+    let mut buffer = vec![0; 64 * 1024];
+    let mut window = Vec::with_capacity(2 * WINDOW_SIZE);
+
+    let mut points = vec![SeekPoint { compressed_offset: 0, uncompressed_offset: 0, window: Vec::new() }];
+    let mut candidates: Vec<Candidate> = Vec::new();
+
+    let mut marker = 0u32;
+    let mut position = 0u64;
+    let mut ended = false;
+
+    while !ended {
+        let read = reader.read(&mut input).await?;
+        if read == 0 {
+            break;
+        }
+
+        let mut start = 0;
+        for index in 0..read {
+            marker = (marker << 8) | u32::from(input[index]);
+            position += 1;
+
+            // The marker must be preceded by at least the byte holding the empty block's header bits.
+            if marker != FLUSH_MARKER || position < 5 {
+                continue;
+            }
+
+            let slice = &input[start..index + 1];
+            start = index + 1;
+            ended = process(&mut decoder, slice, &mut buffer, &mut window, &mut candidates)?;
+            settle(&mut candidates, &mut points);
+
+            if ended {
+                break;
+            }
+
+            let last = candidates.last().map(|candidate| &candidate.point).or(points.last());
+            let last_offset = last.map(|point| point.uncompressed_offset).unwrap_or_default();
+
+            if decoder.total_in() != position || decoder.total_out() < last_offset + spacing {
+                continue;
+            }
+
+            let window = window[window.len().saturating_sub(WINDOW_SIZE)..].to_vec();
+            let point = SeekPoint { compressed_offset: position, uncompressed_offset: decoder.total_out(), window };
+            candidates.push(Candidate::new(point, &mut buffer)?);
+        }
+
+        if !ended && start < read {
+            ended = process(&mut decoder, &input[start..read], &mut buffer, &mut window, &mut candidates)?;
+            settle(&mut candidates, &mut points);
+        }
+    }
+
+    // Any candidates still pending have reproduced all remaining output and so are sound.
+    for candidate in candidates.drain(..) {
+        if !candidate.failed && candidate.expected == candidate.actual {
+            points.push(candidate.point);
+        }
+    }
+
+    points.sort_by_key(|point| point.uncompressed_offset);
+    Ok(points)
+}
+
+/// Feeds a slice of compressed data through the full pass and all pending candidates.
+fn process(
+    decoder: &mut Decompress,
+    slice: &[u8],
+    buffer: &mut [u8],
+    window: &mut Vec<u8>,
+    candidates: &mut [Candidate],
+) -> std::io::Result<bool> {
+    let ended = inflate(decoder, slice, buffer, |output| {
+        window.extend_from_slice(output);
+        if window.len() > 2 * WINDOW_SIZE {
+            window.drain(..window.len() - WINDOW_SIZE);
+        }
+
+        for candidate in candidates.iter_mut() {
+            extend_bounded(&mut candidate.expected, output, VERIFY_SIZE);
+        }
+    })?;
+
+    for candidate in candidates.iter_mut() {
+        let Candidate { decoder, actual, failed, .. } = candidate;
+
+        if inflate(decoder, slice, buffer, |output| extend_bounded(actual, output, VERIFY_SIZE)).is_err() {
+            *failed = true;
+        }
+    }
+
+    Ok(ended)
+}
+
+/// Records verified candidates as restart points and discards those which have diverged.
+fn settle(candidates: &mut Vec<Candidate>, points: &mut Vec<SeekPoint>) {
+    candidates.retain_mut(|candidate| {
+        if candidate.diverged() {
+            return false;
+        }
+        if candidate.verified() {
+            points.push(std::mem::replace(
+                &mut candidate.point,
+                SeekPoint { compressed_offset: 0, uncompressed_offset: 0, window: Vec::new() },
+            ));
+            return false;
+        }
+        true
+    });
+}
+
+/// A reader which decompresses a Deflate entry from an arbitrary uncompressed offset.
+///
+/// As decompression doesn't begin at the start of the entry, no CRC32 check is performed.
+#[pin_project]
+pub struct IndexedEntryReader<'a, R> {
+    #[pin]
+    reader: DeflateDecoder<Chain<futures_lite::io::Cursor<Vec<u8>>, Take<&'a mut R>>>,
+}
+
+impl<'a, R> IndexedEntryReader<'a, R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a reader positioned at `offset`, given a source positioned at the restart point's compressed data.
+    pub(crate) async fn new(
+        reader: &'a mut R,
+        point: &SeekPoint,
+        compressed_remaining: u64,
+        offset: u64,
+    ) -> Result<Self> {
+        let primer = futures_lite::io::Cursor::new(point.primer());
+        let skip = point.window.len() as u64 + (offset - point.uncompressed_offset);
+
+        let mut reader = Self { reader: DeflateDecoder::new(primer.chain(reader.take(compressed_remaining))) };
+        futures_lite::io::copy((&mut reader).take(skip), futures_lite::io::sink()).await?;
+
+        Ok(reader)
+    }
+}
+
+impl<R> AsyncRead for IndexedEntryReader<'_, R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        self.project().reader.poll_read(c, b)
+    }
+}
@@ -28,12 +28,6 @@ use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFro
 /// The buffer size used when locating the EOCDR, equal to 2KiB.
 const BUFFER_SIZE: usize = 2048;
 
-/// The upper bound of where the EOCDR signature cannot be located.
-const EOCDR_UPPER_BOUND: u64 = EOCDR_LENGTH as u64;
-
-/// The lower bound of where the EOCDR signature cannot be located.
-const EOCDR_LOWER_BOUND: u64 = EOCDR_UPPER_BOUND + SIGNATURE_LENGTH as u64 + u16::MAX as u64;
-
 /// Locate the `end of central directory record` offset, if one exists.
 /// The returned offset excludes the signature (4 bytes)
 ///
@@ -43,11 +37,21 @@ const EOCDR_LOWER_BOUND: u64 = EOCDR_UPPER_BOUND + SIGNATURE_LENGTH as u64 + u16
 ///
 /// Whilst I haven't done any in-depth benchmarks, when reading a ZIP file with the maximum length comment, this method
 /// saw a reduction in location time by a factor of 500 when compared with the `zip-rs` method.
-pub async fn eocdr<R>(mut reader: R) -> ZipResult<u64>
+///
+/// Only the final `window` bytes of the data are searched. Fails with [`ZipError::EocdNotFound`] if the record doesn't
+/// start within the window, or with [`ZipError::UnableToLocateEOCDR`] if the window spans the entire data and no record
+/// was found.
+pub async fn eocdr<R>(mut reader: R, window: u64) -> ZipResult<u64>
 where
     R: AsyncRead + AsyncSeek + Unpin,
 {
     let length = reader.seek(SeekFrom::End(0)).await?;
+
+    // Data shorter than an EOCDR (including an entirely empty file) has been cut short, rather than lacking one.
+    if length < (SIGNATURE_LENGTH + EOCDR_LENGTH) as u64 {
+        return Err(ZipError::UnexpectedEof);
+    }
+
     let signature = &EOCDR_SIGNATURE.to_le_bytes();
     let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
 
@@ -67,11 +71,20 @@ where
         }
 
         if let Some(match_index) = reverse_search_buffer(&buffer[..read], signature) {
-            return Ok(position + (match_index + 1) as u64);
+            let offset = position + (match_index + 1) as u64;
+
+            // The reverse search finds the last signature, so any earlier one would also lie outside of the window.
+            if length - (offset - SIGNATURE_LENGTH as u64) > window {
+                return Err(ZipError::EocdNotFound(window));
+            }
+
+            return Ok(offset);
         }
 
-        // If we hit the start of the data or the lower bound, we're unable to locate the EOCDR.
-        if position == 0 || position <= length.saturating_sub(EOCDR_LOWER_BOUND) {
+        // If we hit the lower bound or the start of the data, we're unable to locate the EOCDR.
+        if window < length && position <= length - window {
+            return Err(ZipError::EocdNotFound(window));
+        } else if position == 0 {
             return Err(ZipError::UnableToLocateEOCDR);
         }
 
@@ -0,0 +1,134 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::LZMA_PROPERTIES_LENGTH;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead};
+
+/// The length of the header which precedes LZMA data within ZIP entries (a two-byte version & properties size).
+const ZIP_HEADER_LENGTH: usize = 4 + LZMA_PROPERTIES_LENGTH;
+
+/// The length of the header which precedes LZMA data within `.lzma` files (the properties & uncompressed size).
+const ALONE_HEADER_LENGTH: usize = LZMA_PROPERTIES_LENGTH + 8;
+
+/// The exclusive upper bound of the first properties byte, which encodes the lc, lp, and pb parameters.
+const MAX_PROPERTIES_BYTE: u8 = 9 * 5 * 5;
+
+/// A wrapping reader which converts LZMA data framed as within ZIP entries into that expected by `.lzma` decoders.
+///
+/// ZIP entries hold the LZMA SDK version and the size of the properties before the properties themselves, and store
+/// no uncompressed size. If the uncompressed size is known, it's passed to the decoder so that the data needn't end
+/// with an end-of-stream marker. Otherwise, the marker is required.
+///
+/// Earlier versions of this crate wrote entries using the `.lzma` header instead. As the properties size of
+/// such a header would be an implausible dictionary size, these entries are detected and passed through unchanged.
+pub(crate) struct LzmaZipReader<R> {
+    inner: R,
+    uncompressed_size: Option<u64>,
+    zip_header: [u8; ZIP_HEADER_LENGTH],
+    zip_header_read: usize,
+    alone_header: [u8; ALONE_HEADER_LENGTH],
+    alone_header_len: usize,
+    alone_header_pos: usize,
+}
+
+impl<R> LzmaZipReader<R> {
+    /// Constructs a new wrapping reader whose uncompressed size is unknown.
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            uncompressed_size: None,
+            zip_header: [0; ZIP_HEADER_LENGTH],
+            zip_header_read: 0,
+            alone_header: [0; ALONE_HEADER_LENGTH],
+            alone_header_len: ALONE_HEADER_LENGTH,
+            alone_header_pos: 0,
+        }
+    }
+
+    /// Sets the known uncompressed size of the data, which must be done before any data is read.
+    pub(crate) fn set_uncompressed_size(&mut self, size: u64) {
+        self.uncompressed_size = Some(size);
+    }
+
+    /// Returns a reference to the inner reader.
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes this reader and returns the inner reader.
+    pub(crate) fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> AsyncBufRead for LzmaZipReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        while this.zip_header_read < ZIP_HEADER_LENGTH {
+            let available = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                return Poll::Ready(Err(std::io::ErrorKind::UnexpectedEof.into()));
+            }
+
+            let len = available.len().min(ZIP_HEADER_LENGTH - this.zip_header_read);
+            this.zip_header[this.zip_header_read..this.zip_header_read + len].copy_from_slice(&available[..len]);
+            Pin::new(&mut this.inner).consume(len);
+            this.zip_header_read += len;
+
+            if this.zip_header_read == ZIP_HEADER_LENGTH {
+                let properties_size = u16::from_le_bytes([this.zip_header[2], this.zip_header[3]]);
+                if properties_size as usize != LZMA_PROPERTIES_LENGTH && this.zip_header[0] < MAX_PROPERTIES_BYTE {
+                    // The data was written with a `.lzma` header, so hand back what's been read as-is.
+                    this.alone_header[..ZIP_HEADER_LENGTH].copy_from_slice(&this.zip_header);
+                    this.alone_header_len = ZIP_HEADER_LENGTH;
+                    break;
+                } else if properties_size as usize != LZMA_PROPERTIES_LENGTH {
+                    let message = format!("unexpected LZMA properties size of {properties_size}");
+                    return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::InvalidData, message)));
+                }
+
+                let size = this.uncompressed_size.unwrap_or(u64::MAX);
+                this.alone_header[..LZMA_PROPERTIES_LENGTH].copy_from_slice(&this.zip_header[4..]);
+                this.alone_header[LZMA_PROPERTIES_LENGTH..].copy_from_slice(&size.to_le_bytes());
+            }
+        }
+
+        if this.alone_header_pos < this.alone_header_len {
+            return Poll::Ready(Ok(&this.alone_header[this.alone_header_pos..this.alone_header_len]));
+        }
+
+        Pin::new(&mut this.inner).poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+
+        if this.alone_header_pos < this.alone_header_len {
+            this.alone_header_pos += amt;
+        } else {
+            Pin::new(&mut this.inner).consume(amt);
+        }
+    }
+}
+
+impl<R> AsyncRead for LzmaZipReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+
+        Poll::Ready(Ok(len))
+    }
+}
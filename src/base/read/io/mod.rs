@@ -5,7 +5,11 @@ pub(crate) mod combined_record;
 pub(crate) mod compressed;
 pub(crate) mod entry;
 pub(crate) mod hashed;
+#[cfg(feature = "deflate")]
+pub(crate) mod index;
 pub(crate) mod locator;
+#[cfg(feature = "lzma")]
+pub(crate) mod lzma;
 pub(crate) mod owned;
 
 pub use combined_record::CombinedCentralDirectoryRecord;
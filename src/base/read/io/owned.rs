@@ -4,7 +4,7 @@
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use futures_lite::io::{AsyncBufRead, AsyncRead};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek, SeekFrom};
 use pin_project::pin_project;
 
 /// A wrapping reader which holds an owned R or a mutable borrow to R.
@@ -21,6 +21,14 @@ impl<'a, R> OwnedReader<'a, R>
 where
     R: AsyncBufRead + Unpin,
 {
+    /// Consumes this reader and returns the inner value if it's owned.
+    pub(crate) fn into_owned(self) -> Option<R> {
+        match self {
+            OwnedReader::Owned(inner) => Some(inner),
+            OwnedReader::Borrow(_) => None,
+        }
+    }
+
     /// Consumes an owned reader and returns the inner value.
     pub(crate) fn owned_into_inner(self) -> R {
         match self {
@@ -60,3 +68,15 @@ where
         }
     }
 }
+
+impl<'a, R> AsyncSeek for OwnedReader<'a, R>
+where
+    R: AsyncSeek + Unpin,
+{
+    fn poll_seek(self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        match self.project() {
+            OwnedReaderProj::Owned(inner) => inner.poll_seek(c, pos),
+            OwnedReaderProj::Borrow(inner) => inner.poll_seek(c, pos),
+        }
+    }
+}
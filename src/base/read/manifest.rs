@@ -0,0 +1,52 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::Result;
+use crate::file::ZipFile;
+use crate::spec::Compression;
+use crate::string::ZipString;
+
+use futures_lite::io::{AsyncRead, AsyncSeek};
+
+/// A summary of an entry's metadata and where its data is stored within the ZIP file.
+///
+/// Returned by [`ZipFileReader::manifest()`](crate::base::read::seek::ZipFileReader::manifest).
+#[derive(Debug, Clone)]
+pub struct EntryManifest {
+    /// The entry's filename.
+    pub name: ZipString,
+    /// The entry's compression method.
+    pub compression: Compression,
+    /// The entry's CRC32 value.
+    pub crc: u32,
+    /// The entry's compressed size, which is the length of the byte range starting at `data_offset`.
+    pub compressed_size: u64,
+    /// The entry's uncompressed size.
+    pub uncompressed_size: u64,
+    /// The offset of the entry's local file header.
+    pub header_offset: u64,
+    /// The offset of the entry's compressed data.
+    pub data_offset: u64,
+}
+
+/// Builds a manifest of all entries, reading each local file header once to locate its data.
+pub(crate) async fn build<R>(file: &ZipFile, reader: &mut R) -> Result<Vec<EntryManifest>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let mut manifest = Vec::with_capacity(file.entries().len());
+
+    for entry in file.entries() {
+        manifest.push(EntryManifest {
+            name: entry.filename().clone(),
+            compression: entry.compression(),
+            crc: entry.crc32(),
+            compressed_size: entry.compressed_size(),
+            uncompressed_size: entry.uncompressed_size(),
+            header_offset: entry.header_offset(),
+            data_offset: entry.data_offset(reader).await?,
+        });
+    }
+
+    Ok(manifest)
+}
@@ -3,6 +3,9 @@
 
 //! A concurrent ZIP reader which acts over an owned vector of bytes.
 //!
+//! Archives already held within a borrowed slice (eg. a memory-mapped file) can instead be read without copying via
+//! [`ZipSliceReader`].
+//!
 //! Concurrency is achieved as a result of:
 //! - Wrapping the provided vector of bytes within an [`Arc`] to allow shared ownership.
 //! - Wrapping this [`Arc`] around a [`Cursor`] when reading (as the [`Arc`] can deref and coerce into a `&[u8]`).
@@ -71,14 +74,22 @@
 use crate::base::read::seek;
 
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::manifest::EntryManifest;
+use crate::entry::StoredZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
+use crate::spec::consts::{CDH_LENGTH, CDH_SIGNATURE, SIGNATURE_LENGTH};
+use crate::spec::header::CentralDirectoryRecord;
+use crate::spec::parse::parse_extra_fields;
+use crate::string::ZipString;
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
-use futures_lite::io::Cursor;
+use futures_lite::io::{AsyncReadExt, Cursor, Take};
 
 use super::io::entry::{WithEntry, WithoutEntry};
+use super::{detect_filename, Directory, ZipFileReaderOptions};
 
 struct Inner {
     data: Vec<u8>,
@@ -98,6 +109,57 @@ impl ZipFileReader {
         Ok(ZipFileReader::from_raw_parts(data, file))
     }
 
+    /// Constructs a new ZIP reader from an owned vector of bytes, permitting at most `max_entries` entries.
+    ///
+    /// Fails with [`ZipError::TooManyEntries`] if the central directory declares more entries than this, or more than
+    /// could fit within its declared size. [`ZipFileReader::new()`] uses [`DEFAULT_MAX_ENTRIES`].
+    ///
+    /// [`DEFAULT_MAX_ENTRIES`]: crate::base::read::DEFAULT_MAX_ENTRIES
+    pub async fn with_max_entries(data: Vec<u8>, max_entries: u64) -> Result<ZipFileReader> {
+        ZipFileReader::with_options(data, ZipFileReaderOptions::new().max_entries(max_entries)).await
+    }
+
+    /// Constructs a new ZIP reader from an owned vector of bytes, locating and parsing its central directory as
+    /// configured by the provided options.
+    pub async fn with_options(data: Vec<u8>, options: ZipFileReaderOptions) -> Result<ZipFileReader> {
+        let file = crate::base::read::file_with_options(Cursor::new(&data), &options).await?;
+        Ok(ZipFileReader::from_raw_parts(data, file))
+    }
+
+    /// Constructs a new lazily-parsed ZIP reader from an owned vector of bytes.
+    ///
+    /// See [`LazyZipFileReader`] for more information.
+    pub async fn new_lazy(data: Vec<u8>) -> Result<LazyZipFileReader> {
+        ZipFileReader::new_lazy_with_options(data, ZipFileReaderOptions::new()).await
+    }
+
+    /// Constructs a new lazily-parsed ZIP reader from an owned vector of bytes, locating its central directory as
+    /// configured by the provided options.
+    ///
+    /// See [`LazyZipFileReader`] for more information.
+    pub async fn new_lazy_with_options(data: Vec<u8>, options: ZipFileReaderOptions) -> Result<LazyZipFileReader> {
+        let directory = crate::base::read::directory(Cursor::new(&data), options.eocd_search_window).await?;
+        directory.check_num_entries(options.max_entries)?;
+        let num_entries = usize::try_from(directory.num_entries).map_err(|_| ZipError::TargetZip64NotSupported)?;
+        let mut offset = usize::try_from(directory.offset).map_err(|_| ZipError::UnexpectedEof)?;
+
+        // Bound the preallocation by the number of records the data could actually hold.
+        let mut records = Vec::with_capacity(num_entries.min(data.len() / (SIGNATURE_LENGTH + CDH_LENGTH)));
+
+        for _ in 0..num_entries {
+            let header = record_header(&data, offset)?;
+            records.push(offset);
+
+            let trailing_size = header.file_name_length as usize
+                + header.extra_field_length as usize
+                + header.file_comment_length as usize;
+            offset += SIGNATURE_LENGTH + CDH_LENGTH + trailing_size;
+        }
+
+        let Directory { stub_length, zip64, comment, .. } = directory;
+        Ok(LazyZipFileReader { data, records, stub_length, zip64, comment })
+    }
+
     /// Constructs a ZIP reader from an owned vector of bytes and ZIP file information derived from those bytes.
     ///
     /// Providing a [`ZipFile`] that wasn't derived from those bytes may lead to inaccurate parsing.
@@ -110,27 +172,48 @@ impl ZipFileReader {
         &self.inner.file
     }
 
+    /// Returns this ZIP file's trailing comment as raw bytes, or None if no comment is present.
+    ///
+    /// See [`ZipFile::comment_bytes()`] for more information.
+    pub fn comment_bytes(&self) -> Option<&[u8]> {
+        self.file().comment_bytes()
+    }
+
+    /// Returns this ZIP file's trailing comment decoded as UTF-8, or None if no comment is present.
+    ///
+    /// Invalid UTF-8 sequences are replaced with [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    pub fn comment(&self) -> Option<Cow<'_, str>> {
+        self.comment_bytes().map(String::from_utf8_lossy)
+    }
+
+    /// Returns whether this ZIP file was read via a ZIP64 end of central directory record.
+    ///
+    /// See [`ZipFile::zip64()`] for more information.
+    pub fn is_zip64(&self) -> bool {
+        self.file().zip64()
+    }
+
     /// Returns the raw bytes provided to the reader during construction.
     pub fn data(&self) -> &[u8] {
         &self.inner.data
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>, WithoutEntry>> {
+    pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithoutEntry>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut cursor = Cursor::new(&self.inner.data[..]);
 
         stored_entry.seek_to_data_offset(&mut cursor).await?;
 
-        Ok(ZipEntryReader::new_with_owned(
+        ZipEntryReader::new_with_owned(
             cursor,
-            stored_entry.entry.compression(),
+            stored_entry.entry.readable_compression()?,
             stored_entry.entry.compressed_size(),
-        ))
+        )
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>, WithEntry<'_>>> {
+    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut cursor = Cursor::new(&self.inner.data[..]);
 
@@ -138,10 +221,178 @@ impl ZipFileReader {
 
         let reader = ZipEntryReader::new_with_owned(
             cursor,
-            stored_entry.entry.compression(),
+            stored_entry.entry.readable_compression()?,
             stored_entry.entry.compressed_size(),
-        );
+        )?;
+
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a manifest of every entry's metadata and byte range within this ZIP file.
+    pub async fn manifest(&self) -> Result<Vec<EntryManifest>> {
+        crate::base::read::manifest::build(&self.inner.file, &mut Cursor::new(&self.inner.data[..])).await
+    }
+
+    /// Returns a reader over an entry's raw (still compressed) data if the provided index is valid.
+    ///
+    /// The reader is bounded to the entry's compressed size, and performs no decompression or CRC32 checking.
+    pub async fn entry_raw_reader(&self, index: usize) -> Result<Take<Cursor<&[u8]>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut cursor = Cursor::new(&self.inner.data[..]);
+
+        stored_entry.seek_to_data_offset(&mut cursor).await?;
+
+        Ok(cursor.take(stored_entry.entry.compressed_size()))
+    }
+}
+
+/// A ZIP reader which acts over a borrowed slice of bytes.
+///
+/// Unlike [`ZipFileReader`], the bytes aren't copied into an owned vector, so this suits archives which are already
+/// held in memory (eg. a memory-mapped file). Entry readers decompress directly from sub-slices of the borrowed bytes,
+/// and never pend as no IO is performed.
+#[derive(Clone)]
+pub struct ZipSliceReader<'a> {
+    data: &'a [u8],
+    file: ZipFile,
+}
+
+impl<'a> ZipSliceReader<'a> {
+    /// Constructs a new ZIP reader from a borrowed slice of bytes.
+    pub async fn new(data: &'a [u8]) -> Result<ZipSliceReader<'a>> {
+        let file = crate::base::read::file(Cursor::new(data)).await?;
+        Ok(ZipSliceReader { data, file })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Returns the bytes provided to the reader during construction.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns a sub-slice of an entry's raw (still compressed) data if the provided index is valid.
+    pub async fn entry_data(&self, index: usize) -> Result<&'a [u8]> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let start = stored_entry.data_offset(&mut Cursor::new(self.data)).await?;
+
+        let end = start.checked_add(stored_entry.entry.compressed_size()).ok_or(ZipError::UnexpectedEof)?;
+        let range = usize::try_from(start).ok().zip(usize::try_from(end).ok());
+
+        range.and_then(|(start, end)| self.data.get(start..end)).ok_or(ZipError::UnexpectedEof)
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<'a, &'a [u8], WithoutEntry>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let data = self.entry_data(index).await?;
+
+        ZipEntryReader::new_with_owned(data, stored_entry.entry.readable_compression()?, data.len() as u64)
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, &'a [u8], WithEntry<'_>>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let data = self.entry_data(index).await?;
+
+        let reader =
+            ZipEntryReader::new_with_owned(data, stored_entry.entry.readable_compression()?, data.len() as u64)?;
 
         Ok(reader.into_with_entry(stored_entry))
     }
 }
+
+/// A ZIP reader which acts over an owned vector of bytes, parsing central directory records only when needed.
+///
+/// Constructed via [`ZipFileReader::new_lazy()`]. Only the offset of each central directory record is stored, so the
+/// number of allocations made during construction doesn't grow with the number of entries. This suits archives with
+/// huge numbers of entries where only a few are of interest.
+pub struct LazyZipFileReader {
+    data: Vec<u8>,
+    records: Vec<usize>,
+    stub_length: u64,
+    zip64: bool,
+    comment: ZipString,
+}
+
+impl LazyZipFileReader {
+    /// Returns the number of entries within this ZIP file.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns whether this ZIP file holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns this ZIP file's trailing comment.
+    pub fn comment(&self) -> &ZipString {
+        &self.comment
+    }
+
+    /// Returns an entry's filename bytes exactly as they're stored within its central directory record, without
+    /// allocating.
+    ///
+    /// Returns `None` if the provided index is out of bounds.
+    pub fn entry_name_bytes(&self, index: usize) -> Option<&[u8]> {
+        let offset = *self.records.get(index)?;
+        let header = record_header(&self.data, offset).ok()?;
+
+        let start = offset + SIGNATURE_LENGTH + CDH_LENGTH;
+        self.data.get(start..start + header.file_name_length as usize)
+    }
+
+    /// Decodes an entry's filename if the provided index is valid.
+    ///
+    /// Filenames are decoded as they would be by [`ZipFileReader::new()`], including the use of any Info-ZIP Unicode
+    /// path extra field.
+    pub fn entry_name(&self, index: usize) -> Result<ZipString> {
+        let offset = *self.records.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let header = record_header(&self.data, offset)?;
+
+        let name_start = offset + SIGNATURE_LENGTH + CDH_LENGTH;
+        let extra_start = name_start + header.file_name_length as usize;
+        let extra_end = extra_start + header.extra_field_length as usize;
+
+        let basic = self.data.get(name_start..extra_start).ok_or(ZipError::UnexpectedEof)?;
+        let extra_field = self.data.get(extra_start..extra_end).ok_or(ZipError::UnexpectedEof)?;
+        let extra_fields = parse_extra_fields(extra_field.to_vec(), header.uncompressed_size, header.compressed_size)?;
+
+        Ok(detect_filename(basic.to_vec(), header.flags.filename_unicode, &extra_fields).0)
+    }
+
+    /// Parses an entry's full central directory record if the provided index is valid.
+    pub async fn entry(&self, index: usize) -> Result<StoredZipEntry> {
+        let offset = *self.records.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut entry = crate::base::read::cd_record(Cursor::new(&self.data[offset..]), self.zip64).await?;
+
+        entry.file_offset = entry.file_offset.saturating_add(self.stub_length);
+        Ok(entry)
+    }
+
+    /// Parses every remaining central directory record, returning an eagerly-parsed [`ZipFileReader`].
+    pub async fn into_eager(self) -> Result<ZipFileReader> {
+        let mut entries = Vec::with_capacity(self.records.len());
+        for index in 0..self.records.len() {
+            entries.push(self.entry(index).await?);
+        }
+
+        let file = ZipFile { entries, comment: self.comment, zip64: self.zip64, stub_length: self.stub_length };
+        Ok(ZipFileReader::from_raw_parts(self.data, file))
+    }
+}
+
+/// Parses the fixed-length portion of the central directory record starting at the provided offset.
+fn record_header(data: &[u8], offset: usize) -> Result<CentralDirectoryRecord> {
+    let bytes = data.get(offset..offset + SIGNATURE_LENGTH + CDH_LENGTH).ok_or(ZipError::UnexpectedEof)?;
+    let (signature, header) = bytes.split_at(SIGNATURE_LENGTH);
+
+    match u32::from_le_bytes(signature.try_into().unwrap()) {
+        CDH_SIGNATURE => Ok(CentralDirectoryRecord::from(<[u8; CDH_LENGTH]>::try_from(header).unwrap())),
+        actual => Err(ZipError::UnexpectedHeaderError(actual, CDH_SIGNATURE)),
+    }
+}
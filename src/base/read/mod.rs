@@ -8,19 +8,26 @@ pub mod seek;
 pub mod stream;
 
 pub(crate) mod io;
+pub(crate) mod manifest;
+pub(crate) mod validate;
 
+use crate::string::{decode_cp437, NameEncoding};
 use crate::ZipString;
 // Re-exported as part of the public API.
 pub use crate::base::read::io::entry::WithEntry;
 pub use crate::base::read::io::entry::WithoutEntry;
 pub use crate::base::read::io::entry::ZipEntryReader;
+#[cfg(feature = "deflate")]
+pub use crate::base::read::io::index::{IndexedEntryReader, SeekIndex};
+pub use crate::base::read::manifest::EntryManifest;
+pub use crate::base::read::validate::{Discrepancy, DiscrepancyKind};
 
 use crate::date::ZipDateTime;
 use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 use crate::spec::attribute::AttributeCompatibility;
-use crate::spec::consts::LFH_LENGTH;
+use crate::spec::consts::{AES_COMPRESSION_METHOD, CDH_LENGTH, EOCDR_LENGTH, LFH_LENGTH};
 use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH};
 use crate::spec::header::InfoZipUnicodeCommentExtraField;
 use crate::spec::header::InfoZipUnicodePathExtraField;
@@ -34,20 +41,143 @@ use crate::string::StringEncoding;
 use crate::base::read::io::CombinedCentralDirectoryRecord;
 use crate::spec::parse::parse_extra_fields;
 
-use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+use futures_lite::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
+
+/// The source of truth for an entry's compressed size, which bounds how much of its data is read.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SizeSource {
+    /// Use the size held within the entry's central directory record.
+    #[default]
+    Central,
+    /// Re-read the size from the entry's local file header.
+    ///
+    /// Entries with a data descriptor don't store their sizes locally, so fall back to the central directory.
+    Local,
+}
+
+/// The maximum number of entries a central directory may declare, unless configured otherwise.
+///
+/// This guards against archives declaring a bogus number of entries, which would otherwise cause a huge allocation
+/// before any records are parsed.
+pub const DEFAULT_MAX_ENTRIES: u64 = 4_000_000;
+
+/// The number of bytes from the end of the data searched for the end of central directory record, unless configured
+/// otherwise.
+///
+/// This covers the record itself along with a maximum-length archive comment.
+pub const DEFAULT_EOCD_SEARCH_WINDOW: u64 = (EOCDR_LENGTH + SIGNATURE_LENGTH) as u64 + u16::MAX as u64;
+
+/// Options which configure how a ZIP file's central directory is located and parsed.
+#[derive(Debug, Clone, Copy)]
+pub struct ZipFileReaderOptions {
+    pub(crate) max_entries: u64,
+    pub(crate) eocd_search_window: u64,
+}
+
+impl Default for ZipFileReaderOptions {
+    fn default() -> Self {
+        Self { max_entries: DEFAULT_MAX_ENTRIES, eocd_search_window: DEFAULT_EOCD_SEARCH_WINDOW }
+    }
+}
+
+impl ZipFileReaderOptions {
+    /// Constructs a new set of options with their default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of entries the central directory may declare.
+    ///
+    /// Defaults to [`DEFAULT_MAX_ENTRIES`].
+    pub fn max_entries(mut self, max_entries: u64) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Sets the number of bytes from the end of the data which are searched for the end of central directory record.
+    ///
+    /// Reading fails with [`ZipError::EocdNotFound`] if the record doesn't start within this window. Defaults to
+    /// [`DEFAULT_EOCD_SEARCH_WINDOW`].
+    pub fn eocd_search_window(mut self, window: u64) -> Self {
+        self.eocd_search_window = window;
+        self
+    }
+}
+
+pub(crate) async fn file<R>(reader: R) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    file_with_options(reader, &ZipFileReaderOptions::default()).await
+}
+
+/// Parses the ZIP file as configured by the provided options.
+pub(crate) async fn file_with_options<R>(reader: R, options: &ZipFileReaderOptions) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    Ok(file_with_directory_offset(reader, options).await?.0)
+}
+
+/// Parses the ZIP file, additionally returning the offset at which its central directory starts.
+pub(crate) async fn file_with_directory_offset<R>(
+    mut reader: R,
+    options: &ZipFileReaderOptions,
+) -> Result<(ZipFile, u64)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let directory = directory(&mut reader, options.eocd_search_window).await?;
+    directory.check_num_entries(options.max_entries)?;
+
+    // Find and parse the central directory.
+    reader.seek(SeekFrom::Start(directory.offset)).await?;
+    let mut entries = crate::base::read::cd(reader, directory.num_entries, directory.zip64).await?;
+
+    for entry in entries.iter_mut() {
+        entry.file_offset = entry.file_offset.saturating_add(directory.stub_length);
+    }
+
+    let file =
+        ZipFile { entries, comment: directory.comment, zip64: directory.zip64, stub_length: directory.stub_length };
+    Ok((file, directory.offset))
+}
 
-pub(crate) async fn file<R>(mut reader: R) -> Result<ZipFile>
+/// The location of a ZIP file's central directory, as described by its end of central directory record(s).
+pub(crate) struct Directory {
+    /// The offset at which the central directory starts, accounting for any prepended stub.
+    pub(crate) offset: u64,
+    pub(crate) num_entries: u64,
+    /// The size of the central directory, as declared by the end of central directory record(s).
+    pub(crate) size: u64,
+    /// The length of any stub (eg. a self-extracting executable) prepended to the archive.
+    pub(crate) stub_length: u64,
+    pub(crate) zip64: bool,
+    pub(crate) comment: ZipString,
+}
+
+/// Locates and parses the ZIP file's end of central directory record(s).
+pub(crate) async fn directory<R>(mut reader: R, eocd_search_window: u64) -> Result<Directory>
 where
     R: AsyncRead + AsyncSeek + Unpin,
 {
     // First find and parse the EOCDR.
-    let eocdr_offset = crate::base::read::io::locator::eocdr(&mut reader).await?;
+    let eocdr_offset = crate::base::read::io::locator::eocdr(&mut reader, eocd_search_window).await?;
 
     reader.seek(SeekFrom::Start(eocdr_offset)).await?;
     let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
 
     let comment = io::read_string(&mut reader, eocdr.file_comm_length.into(), crate::StringEncoding::Utf8).await?;
 
+    // Some tools declare a comment longer than the bytes which follow, so keep what's present rather than failing.
+    if comment.as_bytes().len() < usize::from(eocdr.file_comm_length) {
+        crate::utils::log_warn!(
+            "the archive comment was truncated from its declared {} bytes to the {} bytes present",
+            eocdr.file_comm_length,
+            comment.as_bytes().len()
+        );
+    }
+
     // Check the 20 bytes before the EOCDR for the Zip64 EOCDL, plus an extra 4 bytes because the offset
     // does not include the signature. If the ECODL exists we are dealing with a Zip64 file.
     let (eocdr, zip64) = match eocdr_offset.checked_sub(ZIP64_EOCDL_LENGTH + SIGNATURE_LENGTH as u64) {
@@ -67,18 +197,39 @@ where
         }
     };
 
-    // Outdated feature so unlikely to ever make it into this crate.
+    // Outdated feature so unlikely to ever make it into this crate. Some tools write non-zero disk numbers within
+    // single-file archives, so these are tolerated as long as the whole central directory is on the same disk.
     if eocdr.disk_number != eocdr.disk_number_start_of_cd
         || eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk
     {
-        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+        return Err(ZipError::MultiDiskUnsupported);
     }
 
-    // Find and parse the central directory.
-    reader.seek(SeekFrom::Start(eocdr.offset_of_start_of_directory)).await?;
-    let entries = crate::base::read::cd(reader, eocdr.num_entries_in_directory, zip64).await?;
+    // Self-extracting archives prepend an executable stub, so stored offsets are relative to the start of the archive
+    // rather than the file. As the central directory immediately precedes the EOCDR, the stub's length is the distance
+    // between where the central directory actually ends and where it claims to.
+    let directory_end = eocdr.offset_of_start_of_directory.saturating_add(eocdr.directory_size);
+    let stub_length = match zip64 {
+        true => 0,
+        false => (eocdr_offset - SIGNATURE_LENGTH as u64).saturating_sub(directory_end),
+    };
+
+    let offset = eocdr.offset_of_start_of_directory + stub_length;
+    let num_entries = eocdr.num_entries_in_directory;
+    Ok(Directory { offset, num_entries, size: eocdr.directory_size, stub_length, zip64, comment })
+}
+
+impl Directory {
+    /// Checks that the declared number of entries is within `max_entries` and could fit within the declared size.
+    pub(crate) fn check_num_entries(&self, max_entries: u64) -> Result<()> {
+        let min_size = self.num_entries.saturating_mul((SIGNATURE_LENGTH + CDH_LENGTH) as u64);
+
+        if self.num_entries > max_entries || min_size > self.size {
+            return Err(ZipError::TooManyEntries(self.num_entries));
+        }
 
-    Ok(ZipFile { entries, comment, zip64 })
+        Ok(())
+    }
 }
 
 pub(crate) async fn cd<R>(mut reader: R, num_of_entries: u64, zip64: bool) -> Result<Vec<StoredZipEntry>>
@@ -96,6 +247,21 @@ where
     Ok(entries)
 }
 
+/// Returns the entry's actual compression method, which AES-encrypted entries store within their WinZip AES field.
+pub(crate) fn actual_compression_method(method: u16, extra_fields: &[ExtraField]) -> u16 {
+    if method != AES_COMPRESSION_METHOD {
+        return method;
+    }
+
+    extra_fields
+        .iter()
+        .find_map(|field| match field {
+            ExtraField::WinZipAes(field) => Some(field.compression),
+            _ => None,
+        })
+        .unwrap_or(method)
+}
+
 pub(crate) fn get_zip64_extra_field(extra_fields: &[ExtraField]) -> Option<&Zip64ExtendedInformationExtraField> {
     for field in extra_fields {
         if let ExtraField::Zip64ExtendedInformation(zip64field) = field {
@@ -116,7 +282,7 @@ pub(crate) fn get_zip64_extra_field_mut(
     None
 }
 
-fn get_combined_sizes(
+pub(crate) fn get_combined_sizes(
     uncompressed_size: u32,
     compressed_size: u32,
     extra_field: &Option<&Zip64ExtendedInformationExtraField>,
@@ -146,9 +312,11 @@ where
     let header_size = (SIGNATURE_LENGTH + LFH_LENGTH) as u64;
     let trailing_size = header.file_name_length as u64 + header.extra_field_length as u64;
     let filename_basic = io::read_bytes(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
     let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
     let extra_fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+    // Entries using an unsupported method are still listed, but can't be opened for reading.
+    let method = actual_compression_method(header.compression, &extra_fields);
+    let compression = Compression::try_from(method).unwrap_or(Compression::Unsupported(method));
     let comment_basic = io::read_bytes(reader, header.file_comment_length.into()).await?;
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
@@ -156,19 +324,27 @@ where
         get_combined_sizes(header.uncompressed_size, header.compressed_size, &zip64_extra_field)?;
 
     let mut file_offset = header.lh_offset as u64;
+    let mut disk_start_number = header.disk_start as u32;
     if let Some(zip64_extra_field) = zip64_extra_field {
         if file_offset == NON_ZIP64_MAX_SIZE as u64 {
             if let Some(offset) = zip64_extra_field.relative_header_offset {
                 file_offset = offset;
             }
         }
+        if header.disk_start == u16::MAX {
+            if let Some(disk) = zip64_extra_field.disk_start_number {
+                disk_start_number = disk;
+            }
+        }
     }
 
-    let filename = detect_filename(filename_basic, header.flags.filename_unicode, extra_fields.as_ref());
+    let (filename, name_encoding) =
+        detect_filename(filename_basic, header.flags.filename_unicode, extra_fields.as_ref());
     let comment = detect_comment(comment_basic, header.flags.filename_unicode, extra_fields.as_ref());
 
     let entry = ZipEntry {
         filename,
+        name_encoding,
         compression,
         #[cfg(any(
             feature = "deflate",
@@ -179,8 +355,11 @@ where
             feature = "deflate64"
         ))]
         compression_level: async_compression::Level::Default,
-        attribute_compatibility: AttributeCompatibility::Unix,
-        // FIXME: Default to Unix for the moment
+        #[cfg(feature = "deflate")]
+        deflate_strategy: crate::spec::compression::DeflateStrategy::Default,
+        // Hosts without a supported mapping are treated as MS-DOS, whose attributes most hosts also populate.
+        attribute_compatibility: AttributeCompatibility::try_from(header.v_made_by >> 8)
+            .unwrap_or(AttributeCompatibility::Dos),
         crc32: header.crc,
         uncompressed_size,
         compressed_size,
@@ -188,22 +367,25 @@ where
         internal_file_attribute: header.inter_attr,
         external_file_attribute: header.exter_attr,
         extra_fields,
+        local_extra_fields: None,
         comment,
         data_descriptor: header.flags.data_descriptor,
+        encrypted: header.flags.encrypted,
+        min_version: None,
+        trusted_crc32: None,
+        inherit_compression: false,
+        force_data_descriptor: false,
+        alignment: 0,
     };
 
-    Ok(StoredZipEntry { entry, file_offset, header_size: header_size + trailing_size })
+    Ok(StoredZipEntry { entry, file_offset, header_size: header_size + trailing_size, disk_start_number })
 }
 
 pub(crate) async fn lfh<R>(mut reader: R) -> Result<Option<ZipEntry>>
 where
     R: AsyncRead + Unpin,
 {
-    let signature = {
-        let mut buffer = [0; 4];
-        reader.read_exact(&mut buffer).await?;
-        u32::from_le_bytes(buffer)
-    };
+    let signature = u32::from_le_bytes(crate::utils::read_header(&mut reader).await?);
     match signature {
         actual if actual == LFH_SIGNATURE => (),
         actual if actual == CDH_SIGNATURE => return Ok(None),
@@ -211,16 +393,18 @@ where
     };
 
     let header = LocalFileHeader::from_reader(&mut reader).await?;
-    let filename_basic = io::read_bytes(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
-    let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
+    let filename_basic = read_trailing(&mut reader, header.file_name_length.into()).await?;
+    let extra_field = read_trailing(&mut reader, header.extra_field_length.into()).await?;
     let extra_fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+    let method = actual_compression_method(header.compression, &extra_fields);
+    let compression = Compression::try_from(method).unwrap_or(Compression::Unsupported(method));
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
     let (uncompressed_size, compressed_size) =
         get_combined_sizes(header.uncompressed_size, header.compressed_size, &zip64_extra_field)?;
 
-    if header.flags.data_descriptor && compression == Compression::Stored {
+    // Stored data can't delimit itself, so is only supported if the header redundantly holds its size anyway.
+    if header.flags.data_descriptor && compression == Compression::Stored && compressed_size == 0 {
         return Err(ZipError::FeatureNotSupported(
             "stream reading entries with data descriptors & Stored compression mode",
         ));
@@ -229,10 +413,12 @@ where
         return Err(ZipError::FeatureNotSupported("encryption"));
     }
 
-    let filename = detect_filename(filename_basic, header.flags.filename_unicode, extra_fields.as_ref());
+    let (filename, name_encoding) =
+        detect_filename(filename_basic, header.flags.filename_unicode, extra_fields.as_ref());
 
     let entry = ZipEntry {
         filename,
+        name_encoding,
         compression,
         #[cfg(any(
             feature = "deflate",
@@ -243,6 +429,8 @@ where
             feature = "deflate64"
         ))]
         compression_level: async_compression::Level::Default,
+        #[cfg(feature = "deflate")]
+        deflate_strategy: crate::spec::compression::DeflateStrategy::Default,
         attribute_compatibility: AttributeCompatibility::Unix,
         // FIXME: Default to Unix for the moment
         crc32: header.crc,
@@ -252,13 +440,30 @@ where
         internal_file_attribute: 0,
         external_file_attribute: 0,
         extra_fields,
+        local_extra_fields: None,
         comment: String::new().into(),
         data_descriptor: header.flags.data_descriptor,
+        encrypted: header.flags.encrypted,
+        min_version: None,
+        trusted_crc32: None,
+        inherit_compression: false,
+        force_data_descriptor: false,
+        alignment: 0,
     };
 
     Ok(Some(entry))
 }
 
+/// Reads a header's variable-length trailing data, treating a short read as truncation.
+async fn read_trailing<R: AsyncRead + Unpin>(reader: R, length: usize) -> Result<Vec<u8>> {
+    let buffer = io::read_bytes(reader, length).await?;
+
+    match buffer.len() == length {
+        true => Ok(buffer),
+        false => Err(ZipError::UnexpectedEof),
+    }
+}
+
 fn detect_comment(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraField]) -> ZipString {
     if basic_is_utf8 {
         ZipString::new(basic, StringEncoding::Utf8)
@@ -289,32 +494,36 @@ fn detect_comment(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraFiel
     }
 }
 
-fn detect_filename(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraField]) -> ZipString {
+fn detect_filename(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraField]) -> (ZipString, NameEncoding) {
     if basic_is_utf8 {
-        ZipString::new(basic, StringEncoding::Utf8)
-    } else {
-        let unicode_extra = extra_fields.iter().find_map(|field| match field {
-            ExtraField::InfoZipUnicodePath(InfoZipUnicodePathExtraField::V1 { crc32, unicode }) => {
-                if *crc32 == crc32fast::hash(&basic) {
-                    Some(std::string::String::from_utf8(unicode.clone()))
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        });
-        if let Some(Ok(s)) = unicode_extra {
-            ZipString::new_with_alternative(s, basic)
-        } else {
-            // Do not treat as UTF-8 if UTF-8 flags are not set,
-            // some string in MBCS may be valid UTF-8 in form, but they are not in truth.
-            if basic.is_ascii() {
-                // SAFETY:
-                // a valid ASCII string is always a valid UTF-8 string
-                unsafe { std::string::String::from_utf8_unchecked(basic).into() }
+        let filename = ZipString::new(basic, StringEncoding::Utf8);
+        let encoding = NameEncoding::of(&filename);
+        return (filename, encoding);
+    }
+
+    let unicode_extra = extra_fields.iter().find_map(|field| match field {
+        ExtraField::InfoZipUnicodePath(InfoZipUnicodePathExtraField::V1 { crc32, unicode }) => {
+            if *crc32 == crc32fast::hash(&basic) {
+                Some(std::string::String::from_utf8(unicode.clone()))
             } else {
-                ZipString::new(basic, StringEncoding::Raw)
+                None
             }
         }
+        _ => None,
+    });
+
+    if let Some(Ok(s)) = unicode_extra {
+        return (ZipString::new_with_alternative(s, basic), NameEncoding::Utf8);
+    }
+
+    // Without the UTF-8 flag the filename should be CP437, yet many tools write UTF-8 without setting the flag. As
+    // valid UTF-8 is vanishingly unlikely to be intended as CP437 beyond pure ASCII, we prefer it where possible.
+    match std::string::String::from_utf8(basic) {
+        Ok(s) if s.is_ascii() => (s.into(), NameEncoding::Ascii),
+        Ok(s) => (s.into(), NameEncoding::Utf8),
+        Err(err) => {
+            let basic = err.into_bytes();
+            (ZipString::new_with_alternative(decode_cp437(&basic), basic), NameEncoding::Cp437)
+        }
     }
 }
@@ -33,7 +33,29 @@ use crate::file::ZipFile;
 #[cfg(feature = "tokio")]
 use crate::tokio::read::seek::ZipFileReader as TokioZipFileReader;
 
-use futures_lite::io::{AsyncBufRead, AsyncSeek};
+use crate::base::read::io::read_bytes;
+use crate::base::read::manifest::EntryManifest;
+use crate::base::read::stream::read_data_descriptor;
+use crate::base::read::validate::{Discrepancy, DiscrepancyKind};
+use crate::base::read::{get_combined_sizes, get_zip64_extra_field, SizeSource, ZipFileReaderOptions};
+use crate::base::write::{CentralDirectoryEntry, ZipFileWriter};
+use crate::spec::consts::{
+    CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, EOCDR_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_NUM_FILES,
+    NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDR_SIGNATURE,
+};
+use crate::spec::extra_field::{ExtraFieldAsBytes, Zip64ExtendedInformationExtraFieldBuilder};
+use crate::spec::header::{CentralDirectoryRecord, ExtraField, LocalFileHeader};
+use crate::spec::parse::parse_extra_fields;
+
+use futures_lite::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, SeekFrom, Take,
+};
+use std::borrow::Cow;
+#[cfg(feature = "deflate")]
+use {
+    crate::base::read::{IndexedEntryReader, SeekIndex},
+    crate::spec::Compression,
+};
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
@@ -45,6 +67,7 @@ use super::io::entry::{WithEntry, WithoutEntry};
 pub struct ZipFileReader<R> {
     reader: R,
     file: ZipFile,
+    size_source: SizeSource,
 }
 
 impl<R> ZipFileReader<R>
@@ -57,11 +80,37 @@ where
         Ok(ZipFileReader::from_raw_parts(reader, file))
     }
 
+    /// Constructs a new ZIP reader from a seekable source, permitting at most `max_entries` entries.
+    ///
+    /// Fails with [`ZipError::TooManyEntries`] if the central directory declares more entries than this, or more than
+    /// could fit within its declared size. [`ZipFileReader::new()`] uses [`DEFAULT_MAX_ENTRIES`].
+    ///
+    /// [`DEFAULT_MAX_ENTRIES`]: crate::base::read::DEFAULT_MAX_ENTRIES
+    pub async fn with_max_entries(reader: R, max_entries: u64) -> Result<ZipFileReader<R>> {
+        ZipFileReader::with_options(reader, ZipFileReaderOptions::new().max_entries(max_entries)).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source, locating and parsing its central directory as configured by
+    /// the provided options.
+    pub async fn with_options(mut reader: R, options: ZipFileReaderOptions) -> Result<ZipFileReader<R>> {
+        let file = crate::base::read::file_with_options(&mut reader, &options).await?;
+        Ok(ZipFileReader::from_raw_parts(reader, file))
+    }
+
     /// Constructs a ZIP reader from a seekable source and ZIP file information derived from that source.
     ///
     /// Providing a [`ZipFile`] that wasn't derived from that source may lead to inaccurate parsing.
     pub fn from_raw_parts(reader: R, file: ZipFile) -> ZipFileReader<R> {
-        ZipFileReader { reader, file }
+        ZipFileReader { reader, file, size_source: SizeSource::default() }
+    }
+
+    /// Sets which record an entry's compressed size is taken from when bounding its data.
+    ///
+    /// Defaults to [`SizeSource::Central`]. Archives whose central directory has been corrupted or left stale by an
+    /// in-place edit may still be readable via [`SizeSource::Local`].
+    pub fn size_source(mut self, source: SizeSource) -> Self {
+        self.size_source = source;
+        self
     }
 
     /// Returns this ZIP file's information.
@@ -69,6 +118,27 @@ where
         &self.file
     }
 
+    /// Returns this ZIP file's trailing comment as raw bytes, or None if no comment is present.
+    ///
+    /// See [`ZipFile::comment_bytes()`] for more information.
+    pub fn comment_bytes(&self) -> Option<&[u8]> {
+        self.file().comment_bytes()
+    }
+
+    /// Returns this ZIP file's trailing comment decoded as UTF-8, or None if no comment is present.
+    ///
+    /// Invalid UTF-8 sequences are replaced with [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    pub fn comment(&self) -> Option<Cow<'_, str>> {
+        self.comment_bytes().map(String::from_utf8_lossy)
+    }
+
+    /// Returns whether this ZIP file was read via a ZIP64 end of central directory record.
+    ///
+    /// See [`ZipFile::zip64()`] for more information.
+    pub fn is_zip64(&self) -> bool {
+        self.file().zip64()
+    }
+
     /// Returns a mutable reference to the inner seekable source.
     ///
     /// Swapping the source (eg. via std::mem operations) may lead to inaccurate parsing.
@@ -84,28 +154,35 @@ where
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_without_entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R, WithoutEntry>> {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        let (_, compressed_size) = stored_entry.seek_to_data(&mut self.reader, self.size_source).await?;
 
-        Ok(ZipEntryReader::new_with_borrow(
-            &mut self.reader,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+        ZipEntryReader::new_with_borrow(&mut self.reader, stored_entry.entry.readable_compression()?, compressed_size)
     }
 
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_with_entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R, WithEntry<'_>>> {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
 
-        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        let (data_offset, compressed_size) = stored_entry.seek_to_data(&mut self.reader, self.size_source).await?;
 
         let reader = ZipEntryReader::new_with_borrow(
             &mut self.reader,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        );
+            stored_entry.entry.readable_compression()?,
+            compressed_size,
+        )?;
 
-        Ok(reader.into_with_entry(stored_entry))
+        Ok(reader.into_with_entry(stored_entry).with_data_offset(data_offset))
+    }
+
+    /// Returns a reader over an entry's raw (still compressed) data if the provided index is valid.
+    ///
+    /// The reader is bounded to the entry's compressed size, and performs no decompression or CRC32 checking. This is
+    /// suitable for inspecting or re-archiving entries verbatim.
+    pub async fn entry_raw_reader(&mut self, index: usize) -> Result<Take<&mut R>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let (_, compressed_size) = stored_entry.seek_to_data(&mut self.reader, self.size_source).await?;
+
+        Ok((&mut self.reader).take(compressed_size))
     }
 
     /// Returns a new entry reader if the provided index is valid.
@@ -116,13 +193,259 @@ where
     {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
 
+        let (_, compressed_size) = stored_entry.seek_to_data(&mut self.reader, self.size_source).await?;
+
+        ZipEntryReader::new_with_owned(self.reader, stored_entry.entry.readable_compression()?, compressed_size)
+    }
+
+    /// Writes a copy of this archive to `dest` with its central directory re-derived from the local file headers.
+    ///
+    /// This is intended for repairing archives whose entry data has been edited in place, leaving the offsets and
+    /// sizes held within the central directory stale. Local entries are scanned sequentially from the start of the
+    /// archive (after any prepended stub, which is copied as-is) until another header is reached, and are copied
+    /// verbatim. Each is matched by name against the existing central directory, from which metadata absent from local
+    /// headers (eg. comments and attributes) is taken.
+    ///
+    /// Offsets, sizes, and CRC32 values are taken from the local headers, or from the data descriptors of entries
+    /// written with one. Such an entry's data descriptor is located by scanning for the next header (or the end of the
+    /// source) preceded by a descriptor whose compressed size matches the data before it, so the sizes held within the
+    /// central directory are never relied upon. Scanning stops cleanly if the existing central directory is missing.
+    pub async fn recompute_central_directory<W>(&mut self, dest: W) -> Result<W>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut writer = ZipFileWriter::new(dest);
+        let mut matched = vec![false; self.file.entries.len()];
+        let mut offset = self.file.stub_length;
+
+        self.reader.seek(SeekFrom::Start(offset)).await?;
+
+        loop {
+            let mut signature = [0; SIGNATURE_LENGTH];
+            match self.reader.read_exact(&mut signature).await {
+                Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                result => result?,
+            }
+
+            if u32::from_le_bytes(signature) != LFH_SIGNATURE {
+                break;
+            }
+
+            let header = LocalFileHeader::from_reader(&mut self.reader).await?;
+            let filename = read_bytes(&mut self.reader, header.file_name_length.into()).await?;
+            let extra_field = read_bytes(&mut self.reader, header.extra_field_length.into()).await?;
+
+            let index =
+                self.file.entries.iter().enumerate().position(|(index, stored)| {
+                    !matched[index] && stored.entry.raw_name_bytes() == filename.as_slice()
+                });
+            let mut entry = match index {
+                Some(index) => {
+                    matched[index] = true;
+                    Some(self.file.entries[index].entry.clone())
+                }
+                None => None,
+            };
+
+            let fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+            let zip64 = get_zip64_extra_field(&fields);
+
+            let compressed_size = if header.flags.data_descriptor {
+                let data_offset = self.reader.seek(SeekFrom::Current(0)).await?;
+                let compressed_size = locate_data_descriptor(&mut self.reader).await?;
+                self.reader.seek(SeekFrom::Start(data_offset)).await?;
+                compressed_size
+            } else {
+                let (uncompressed_size, compressed_size) =
+                    get_combined_sizes(header.uncompressed_size, header.compressed_size, &zip64)?;
+
+                if let Some(entry) = entry.as_mut() {
+                    entry.crc32 = header.crc;
+                    entry.uncompressed_size = uncompressed_size;
+                    entry.compressed_size = compressed_size;
+                }
+                compressed_size
+            };
+
+            self.reader.seek(SeekFrom::Current(compressed_size as i64)).await?;
+            let descriptor = match header.flags.data_descriptor {
+                true => Some(read_data_descriptor(&mut self.reader, zip64.is_some()).await?),
+                false => None,
+            };
+            if let Some(descriptor) = &descriptor {
+                self.reader.seek(SeekFrom::Current(-(descriptor.consumed.len() as i64))).await?;
+            }
+
+            let lh_offset = offset;
+            offset = self.reader.seek(SeekFrom::Current(0)).await?;
+
+            // Local entries absent from the central directory are copied but remain unlisted.
+            let Some(mut entry) = entry else {
+                continue;
+            };
+            if let Some(descriptor) = descriptor {
+                entry.crc32 = descriptor.crc;
+
+                if let Some((compressed_size, uncompressed_size)) = descriptor.sizes {
+                    entry.compressed_size = compressed_size;
+                    entry.uncompressed_size = uncompressed_size;
+                }
+            }
+
+            entry.extra_fields.retain(|field| !matches!(field, ExtraField::Zip64ExtendedInformation(_)));
+            let mut builder = Zip64ExtendedInformationExtraFieldBuilder::new();
+            let mut zip64 = false;
+
+            let (compressed_size, uncompressed_size) = if entry.compressed_size() > NON_ZIP64_MAX_SIZE.into()
+                || entry.uncompressed_size() > NON_ZIP64_MAX_SIZE.into()
+            {
+                builder = builder.sizes(entry.compressed_size(), entry.uncompressed_size());
+                zip64 = true;
+                (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE)
+            } else {
+                (entry.compressed_size() as u32, entry.uncompressed_size() as u32)
+            };
+
+            let lh_offset = if lh_offset > NON_ZIP64_MAX_SIZE.into() {
+                builder = builder.relative_header_offset(lh_offset);
+                zip64 = true;
+                NON_ZIP64_MAX_SIZE
+            } else {
+                lh_offset as u32
+            };
+
+            if zip64 {
+                entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
+                writer.is_zip64 = true;
+            }
+
+            let comment_basic = entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes());
+            let header = CentralDirectoryRecord {
+                v_made_by: crate::spec::version::as_made_by(&entry),
+                v_needed: header.version,
+                compressed_size,
+                uncompressed_size,
+                compression: header.compression,
+                crc: entry.crc32(),
+                extra_field_length: entry
+                    .extra_fields()
+                    .count_bytes()
+                    .try_into()
+                    .map_err(|_| ZipError::ExtraFieldTooLarge)?,
+                file_name_length: header.file_name_length,
+                file_comment_length: comment_basic.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
+                mod_time: header.mod_time,
+                mod_date: header.mod_date,
+                flags: header.flags,
+                disk_start: 0,
+                inter_attr: entry.internal_file_attribute(),
+                exter_attr: entry.external_file_attribute(),
+                lh_offset,
+            };
+
+            writer.cd_entries.push(CentralDirectoryEntry { header, entry, raw_extra: None });
+        }
+
+        if writer.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
+            writer.is_zip64 = true;
+        }
+        if let Some(comment) = self.file.comment_bytes() {
+            writer.comment_bytes(comment.to_vec())?;
+        }
+
+        self.reader.seek(SeekFrom::Start(0)).await?;
+        futures_lite::io::copy((&mut self.reader).take(offset), &mut writer.writer).await?;
+
+        writer.close().await
+    }
+
+    /// Returns a manifest of every entry's metadata and byte range within this ZIP file.
+    ///
+    /// Locating each entry's data requires reading its local file header, so this performs a single pass over them.
+    pub async fn manifest(&mut self) -> Result<Vec<EntryManifest>> {
+        crate::base::read::manifest::build(&self.file, &mut self.reader).await
+    }
+
+    /// Checks each entry's local file header against its central directory record, returning any discrepancies.
+    ///
+    /// The CRC32 value and sizes are compared for every entry which doesn't use a data descriptor (as such entries
+    /// don't store these values locally). This can be used to detect tampering or corruption before extraction.
+    pub async fn validate(&mut self) -> Result<Vec<Discrepancy>> {
+        let mut discrepancies = Vec::new();
+
+        for (index, stored_entry) in self.file.entries.iter().enumerate() {
+            self.reader.seek(SeekFrom::Start(stored_entry.file_offset)).await?;
+            crate::utils::assert_signature(&mut self.reader, LFH_SIGNATURE).await?;
+
+            let header = LocalFileHeader::from_reader(&mut self.reader).await?;
+            if header.flags.data_descriptor {
+                continue;
+            }
+
+            self.reader.seek(SeekFrom::Current(header.file_name_length.into())).await?;
+            let extra_field = read_bytes(&mut self.reader, header.extra_field_length.into()).await?;
+            let fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+            let (uncompressed_size, compressed_size) =
+                get_combined_sizes(header.uncompressed_size, header.compressed_size, &get_zip64_extra_field(&fields))?;
+
+            let entry = &stored_entry.entry;
+            let kinds = [
+                (entry.crc32 != header.crc)
+                    .then_some(DiscrepancyKind::Crc32 { central: entry.crc32, local: header.crc }),
+                (entry.compressed_size != compressed_size).then_some(DiscrepancyKind::CompressedSize {
+                    central: entry.compressed_size,
+                    local: compressed_size,
+                }),
+                (entry.uncompressed_size != uncompressed_size).then_some(DiscrepancyKind::UncompressedSize {
+                    central: entry.uncompressed_size,
+                    local: uncompressed_size,
+                }),
+            ];
+
+            discrepancies.extend(kinds.into_iter().flatten().map(|kind| Discrepancy { index, kind }));
+        }
+
+        Ok(discrepancies)
+    }
+
+    /// Builds an index of restart points for a Deflate entry, at least `spacing` uncompressed bytes apart.
+    ///
+    /// This decompresses the entry in full. Restart points can only be placed where the encoder performed a sync or
+    /// full flush, so an entry compressed without any flushes yields an index holding only its start.
+    #[cfg(feature = "deflate")]
+    pub async fn build_seek_index(&mut self, index: usize, spacing: u64) -> Result<SeekIndex> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if stored_entry.entry.readable_compression()? != Compression::Deflate {
+            return Err(ZipError::FeatureNotSupported("seek indexes for non-Deflate entries"));
+        }
+
+        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        let reader = (&mut self.reader).take(stored_entry.entry.compressed_size());
+        let points = crate::base::read::io::index::build(reader, spacing).await?;
+
+        Ok(SeekIndex { entry_index: index, points })
+    }
+
+    /// Returns a reader over the entry's uncompressed data starting at `offset`, using a previously built index.
+    ///
+    /// Decompression begins at the closest restart point at or before `offset`. As the entry isn't read from its
+    /// start, no CRC32 check is performed. If the index was built for a different entry (eg. from another archive) and
+    /// the restart point lies beyond this entry's compressed data, this fails with [`ZipError::SeekIndexMismatch`].
+    #[cfg(feature = "deflate")]
+    pub async fn reader_at_offset(&mut self, index: &SeekIndex, offset: u64) -> Result<IndexedEntryReader<'_, R>> {
+        let stored_entry = self.file.entries.get(index.entry_index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let point = index.point_before(offset).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let remaining = stored_entry
+            .entry
+            .compressed_size()
+            .checked_sub(point.compressed_offset)
+            .ok_or(ZipError::SeekIndexMismatch)?;
+
         stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        self.reader.seek(SeekFrom::Current(point.compressed_offset as i64)).await?;
 
-        Ok(ZipEntryReader::new_with_owned(
-            self.reader,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+        IndexedEntryReader::new(&mut self.reader, point, remaining, offset).await
     }
 }
 
@@ -138,3 +461,78 @@ where
         Ok(ZipFileReader::from_raw_parts(reader, file))
     }
 }
+
+/// The layouts a data descriptor may take, as its length, whether it's signed, and whether its sizes are 64-bit.
+const DATA_DESCRIPTOR_LAYOUTS: [(usize, bool, bool); 4] =
+    [(24, true, true), (20, false, true), (16, true, false), (12, false, false)];
+
+/// Scans forward from the start of an entry's data for its data descriptor, returning the entry's compressed size.
+///
+/// A descriptor is only accepted if it's immediately followed by another header (or the end of the source) and its
+/// compressed size matches the number of bytes preceding it, so signatures occurring within the data are skipped.
+async fn locate_data_descriptor<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<u64> {
+    const HEADERS: [u32; 4] = [LFH_SIGNATURE, CDH_SIGNATURE, ZIP64_EOCDR_SIGNATURE, EOCDR_SIGNATURE];
+    // Enough bytes to hold the largest descriptor layout before any position yet to be checked.
+    const RETAINED: usize = 24 + SIGNATURE_LENGTH - 1;
+
+    let mut window = Vec::new();
+    let mut window_offset = 0u64;
+    let mut checked = 0;
+
+    loop {
+        let buffer = reader.fill_buf().await?;
+        let eof = buffer.is_empty();
+        window.extend_from_slice(buffer);
+        let length = buffer.len();
+        reader.consume(length);
+
+        let scan_end = match eof {
+            true => window.len() + 1,
+            false => window.len().saturating_sub(SIGNATURE_LENGTH - 1),
+        };
+        for index in checked..scan_end {
+            let at_header = match window.get(index..index + SIGNATURE_LENGTH) {
+                Some(signature) => HEADERS.contains(&u32::from_le_bytes(signature.try_into().unwrap())),
+                None => index == window.len(),
+            };
+            if !at_header {
+                continue;
+            }
+            if let Some(compressed_size) = match_data_descriptor(&window[..index], window_offset + index as u64) {
+                return Ok(compressed_size);
+            }
+        }
+
+        if eof {
+            return Err(ZipError::DataDescriptorNotFound);
+        }
+
+        let drained = scan_end.saturating_sub(RETAINED);
+        window.drain(..drained);
+        window_offset += drained as u64;
+        checked = scan_end - drained;
+    }
+}
+
+/// Returns the compressed size held by a data descriptor ending `end` bytes after the start of an entry's data, if
+/// the trailing bytes of `window` form one which matches.
+fn match_data_descriptor(window: &[u8], end: u64) -> Option<u64> {
+    DATA_DESCRIPTOR_LAYOUTS.into_iter().find_map(|(length, signed, zip64)| {
+        let compressed_size = end.checked_sub(length as u64)?;
+        let descriptor = window.get(window.len().checked_sub(length)?..)?;
+
+        let descriptor = match signed {
+            true if descriptor[..SIGNATURE_LENGTH] == DATA_DESCRIPTOR_SIGNATURE.to_le_bytes() => {
+                &descriptor[SIGNATURE_LENGTH..]
+            }
+            true => return None,
+            false => descriptor,
+        };
+        let stored = match zip64 {
+            true => u64::from_le_bytes(descriptor[4..12].try_into().unwrap()),
+            false => u32::from_le_bytes(descriptor[4..8].try_into().unwrap()).into(),
+        };
+
+        (stored == compressed_size).then_some(compressed_size)
+    })
+}
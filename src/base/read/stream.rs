@@ -46,18 +46,23 @@
 //! # }
 //! ```
 
+use crate::base::read::get_zip64_extra_field;
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::entry::ZipEntry;
 use crate::error::Result;
 use crate::error::ZipError;
 
 use crate::spec::consts::DATA_DESCRIPTOR_LENGTH;
 use crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE;
 use crate::spec::consts::SIGNATURE_LENGTH;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
 #[cfg(feature = "tokio")]
 use crate::tokio::read::stream::Ready as TokioReady;
 
 use futures_lite::io::AsyncBufRead;
+use futures_lite::io::AsyncBufReadExt;
 use futures_lite::io::AsyncReadExt;
+use futures_lite::io::Cursor;
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::TokioAsyncReadCompatExt;
@@ -66,10 +71,14 @@ use super::io::entry::WithEntry;
 use super::io::entry::WithoutEntry;
 
 /// A type which encodes that [`ZipFileReader`] is ready to open a new entry.
-pub struct Ready<R>(R);
+///
+/// Any bytes of the next header's signature which had to be consumed whilst reading a data descriptor are also held.
+pub struct Ready<R>(R, Vec<u8>);
 
 /// A type which encodes that [`ZipFileReader`] is currently reading an entry.
-pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>, bool);
+///
+/// If the entry has a data descriptor, whether its sizes are 64-bit is also held.
+pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>, Option<bool>);
 
 /// A ZIP reader which acts over a non-seekable source.
 ///
@@ -83,37 +92,43 @@ where
 {
     /// Constructs a new ZIP reader from a non-seekable source.
     pub fn new(reader: R) -> Self {
-        Self(Ready(reader))
+        Self(Ready(reader, Vec::new()))
     }
 
     /// Opens the next entry for reading if the central directory hasn’t yet been reached.
     pub async fn next_without_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, R, WithoutEntry>>>> {
-        let entry = match crate::base::read::lfh(&mut self.0 .0).await? {
+        let consumed = Cursor::new(std::mem::take(&mut self.0 .1));
+        let entry = match crate::base::read::lfh(consumed.chain(&mut self.0 .0)).await? {
             Some(entry) => entry,
             None => return Ok(None),
         };
 
-        let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
-        let reader = ZipEntryReader::new_with_owned(self.0 .0, entry.compression, length);
+        let length = data_length(&entry);
+        let reader = ZipEntryReader::new_with_owned_or_unsupported(self.0 .0, entry.compression, length);
+        let data_descriptor = data_descriptor(&entry);
 
-        Ok(Some(ZipFileReader(Reading(reader, entry.data_descriptor))))
+        Ok(Some(ZipFileReader(Reading(reader, data_descriptor))))
     }
 
     /// Opens the next entry for reading if the central directory hasn’t yet been reached.
     pub async fn next_with_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, R, WithEntry<'a>>>>> {
-        let entry = match crate::base::read::lfh(&mut self.0 .0).await? {
+        let consumed = Cursor::new(std::mem::take(&mut self.0 .1));
+        let entry = match crate::base::read::lfh(consumed.chain(&mut self.0 .0)).await? {
             Some(entry) => entry,
             None => return Ok(None),
         };
 
-        let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
-        let reader = ZipEntryReader::new_with_owned(self.0 .0, entry.compression, length);
-        let data_descriptor = entry.data_descriptor;
+        let length = data_length(&entry);
+        let reader = ZipEntryReader::new_with_owned_or_unsupported(self.0 .0, entry.compression, length);
+        let data_descriptor = data_descriptor(&entry);
 
         Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(entry), data_descriptor))))
     }
 
     /// Consumes the `ZipFileReader` returning the original `reader`
+    ///
+    /// If the previous entry's data descriptor ended at the end of the reader's buffer, part of the next header's
+    /// signature may already have been consumed from it.
     pub async fn into_inner(self) -> R {
         self.0 .0
     }
@@ -126,7 +141,7 @@ where
 {
     /// Constructs a new tokio-specific ZIP reader from a non-seekable source.
     pub fn with_tokio(reader: R) -> ZipFileReader<TokioReady<R>> {
-        Self(Ready(reader.compat()))
+        Self(Ready(reader.compat(), Vec::new()))
     }
 }
 
@@ -144,44 +159,162 @@ where
         &mut self.0 .0
     }
 
+    /// Limits the number of decompressed bytes the inner entry reader may produce.
+    ///
+    /// See [`ZipEntryReader::with_limit()`] for more information.
+    pub fn with_limit(self, max_bytes: u64) -> Self {
+        let Reading(reader, data_descriptor) = self.0;
+        Self(Reading(reader.with_limit(max_bytes), data_descriptor))
+    }
+
+    /// Limits the ratio of decompressed bytes the inner entry reader may produce to the compressed bytes it consumes.
+    ///
+    /// See [`ZipEntryReader::with_ratio_limit()`] for more information.
+    pub fn with_ratio_limit(self, max_ratio: u64) -> Self {
+        let Reading(reader, data_descriptor) = self.0;
+        Self(Reading(reader.with_ratio_limit(max_ratio), data_descriptor))
+    }
+
     /// Converts the reader back into the Ready state if EOF has been reached.
     pub async fn done(mut self) -> Result<ZipFileReader<Ready<R>>> {
         if self.0 .0.read(&mut [0; 1]).await? != 0 {
             return Err(ZipError::EOFNotReached);
         }
 
-        let mut inner = self.0 .0.into_inner();
+        let mut inner = self.0 .0.owned_into_inner();
 
         // Has data descriptor.
-        if self.0 .1 {
-            consume_data_descriptor(&mut inner).await?;
-        }
+        let consumed = match self.0 .1 {
+            Some(zip64) => read_data_descriptor(&mut inner, zip64).await?.consumed,
+            None => Vec::new(),
+        };
 
-        Ok(ZipFileReader(Ready(inner)))
+        Ok(ZipFileReader(Ready(inner, consumed)))
     }
 
     /// Reads until EOF and converts the reader back into the Ready state.
+    ///
+    /// Entries using an unsupported compression method can't be read, so their compressed data is skipped over instead.
+    /// This fails with [`ZipError::CompressionNotSupported`] if the entry has a data descriptor and its compressed size
+    /// is unknown, as the end of its data can't then be found.
     pub async fn skip(mut self) -> Result<ZipFileReader<Ready<R>>> {
-        while self.0 .0.read(&mut [0; 2048]).await? != 0 {}
-        let mut inner = self.0 .0.into_inner();
+        match self.0 .0.unsupported_mut() {
+            Some((raw, method)) if raw.limit() == u64::MAX => return Err(ZipError::CompressionNotSupported(method)),
+            Some((raw, _)) => {
+                futures_lite::io::copy(raw, futures_lite::io::sink()).await?;
+            }
+            None => while self.0 .0.read(&mut [0; 2048]).await? != 0 {},
+        }
+        let mut inner = self.0 .0.owned_into_inner();
 
         // Has data descriptor.
-        if self.0 .1 {
-            consume_data_descriptor(&mut inner).await?;
-        }
+        let consumed = match self.0 .1 {
+            Some(zip64) => read_data_descriptor(&mut inner, zip64).await?.consumed,
+            None => Vec::new(),
+        };
 
-        Ok(ZipFileReader(Ready(inner)))
+        Ok(ZipFileReader(Ready(inner, consumed)))
+    }
+}
+
+/// Returns the length of an entry's data as read from its local file header.
+///
+/// Entries with a data descriptor are read until the decompressor reaches the end of its stream, unless the local file
+/// header redundantly holds their sizes anyway (in which case the read is bounded by them).
+fn data_length(entry: &ZipEntry) -> u64 {
+    match entry.data_descriptor && entry.compressed_size == 0 {
+        true => u64::MAX,
+        false => entry.compressed_size,
     }
 }
 
-async fn consume_data_descriptor<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<()> {
-    let mut descriptor: [u8; DATA_DESCRIPTOR_LENGTH] = [0; DATA_DESCRIPTOR_LENGTH];
-    reader.read_exact(&mut descriptor).await?;
+/// Returns whether an entry read from its local file header has a data descriptor, and if so, whether its sizes are
+/// 64-bit (as is the case when the header holds a zip64 extended information field).
+fn data_descriptor(entry: &ZipEntry) -> Option<bool> {
+    entry.data_descriptor.then(|| get_zip64_extra_field(entry.extra_fields()).is_some())
+}
+
+/// The values held within an entry's data descriptor.
+pub(crate) struct DataDescriptor {
+    pub(crate) crc: u32,
+    /// The compressed and uncompressed sizes, unless the descriptor held only the CRC32 value.
+    pub(crate) sizes: Option<(u64, u64)>,
+    /// Any bytes of the following header's signature which had to be consumed in order to detect it.
+    pub(crate) consumed: Vec<u8>,
+}
 
-    if descriptor[0..SIGNATURE_LENGTH] == DATA_DESCRIPTOR_SIGNATURE.to_le_bytes() {
-        let mut tail: [u8; SIGNATURE_LENGTH] = [0; SIGNATURE_LENGTH];
-        reader.read_exact(&mut tail).await?;
+/// Reads an entry's data descriptor and returns the values it holds.
+///
+/// If `zip64` is set, the descriptor's sizes are expected to be 64-bit, though the 32-bit form emitted by some writers
+/// alongside a zip64 extended information field is also tolerated.
+///
+/// Some minimal writers emit a nonstandard descriptor holding only the CRC32 value (optionally preceded by the
+/// signature). We tolerate this by checking whether a header signature immediately follows the CRC32 value, in which
+/// case the sizes are assumed to be absent. If that signature straddled the end of the reader's buffer, the bytes of it
+/// which had to be consumed are also returned, so that the caller can account for them.
+///
+/// A source which ends before the descriptor is complete (eg. a truncated download) fails with
+/// [`ZipError::UnexpectedEof`].
+pub(crate) async fn read_data_descriptor<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    zip64: bool,
+) -> Result<DataDescriptor> {
+    let mut buffer: [u8; SIGNATURE_LENGTH] = crate::utils::read_header(reader).await?;
+
+    if buffer == DATA_DESCRIPTOR_SIGNATURE.to_le_bytes() {
+        buffer = crate::utils::read_header(reader).await?;
     }
 
-    Ok(())
+    let crc = u32::from_le_bytes(buffer);
+    let mut sizes: Vec<[u8; DATA_DESCRIPTOR_LENGTH - SIGNATURE_LENGTH]> = Vec::with_capacity(2);
+
+    let consumed = loop {
+        if sizes.len() == if zip64 { 2 } else { 1 } {
+            break Vec::new();
+        }
+
+        let (header, consumed) = at_header(reader).await?;
+        if header {
+            break consumed;
+        }
+
+        sizes.push(crate::utils::read_header(&mut Cursor::new(consumed).chain(&mut *reader)).await?);
+    };
+
+    // Sizes are 32-bit unless both 64-bit sizes are present.
+    let sizes = match sizes[..] {
+        [] => None,
+        [[c0, c1, c2, c3, u0, u1, u2, u3]] => {
+            Some((u32::from_le_bytes([c0, c1, c2, c3]).into(), u32::from_le_bytes([u0, u1, u2, u3]).into()))
+        }
+        [compressed, uncompressed, ..] => Some((u64::from_le_bytes(compressed), u64::from_le_bytes(uncompressed))),
+    };
+
+    Ok(DataDescriptor { crc, sizes, consumed })
+}
+
+/// Returns whether the reader is positioned at a local file header, a central directory record, or its end.
+///
+/// A reader's buffer can't be extended without consuming it, so if fewer bytes than a signature are buffered, they're
+/// consumed in order to read further and are returned alongside the result.
+async fn at_header<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<(bool, Vec<u8>)> {
+    let mut consumed = Vec::new();
+
+    loop {
+        let next = reader.fill_buf().await?;
+        if next.is_empty() {
+            return Ok((consumed.is_empty(), consumed));
+        }
+
+        let needed = SIGNATURE_LENGTH - consumed.len();
+        if next.len() >= needed {
+            let signature = [&consumed[..], &next[..needed]].concat();
+            let header = [LFH_SIGNATURE, CDH_SIGNATURE].iter().any(|expected| signature == expected.to_le_bytes());
+            return Ok((header, consumed));
+        }
+
+        let length = next.len();
+        consumed.extend_from_slice(next);
+        reader.consume(length);
+    }
 }
@@ -0,0 +1,21 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+/// A disagreement between an entry's central directory record and its local file header.
+///
+/// Returned by [`ZipFileReader::validate()`](crate::base::read::seek::ZipFileReader::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discrepancy {
+    /// The index of the entry within the central directory.
+    pub index: usize,
+    /// The field which disagrees, alongside both of its values.
+    pub kind: DiscrepancyKind,
+}
+
+/// The field which disagrees between an entry's central directory record and its local file header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscrepancyKind {
+    Crc32 { central: u32, local: u32 },
+    CompressedSize { central: u64, local: u64 },
+    UncompressedSize { central: u64, local: u64 },
+}
@@ -0,0 +1,190 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{SortKey, ZipFileWriter};
+use crate::crc::Crc32;
+use crate::date::ZipDateTime;
+use crate::spec::Compression;
+
+use std::collections::HashSet;
+
+#[cfg(feature = "tokio")]
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+use futures_lite::io::AsyncWrite;
+
+/// A builder for [`ZipFileWriter`].
+///
+/// Each option must be set before the writer is built, and so before any entries are written:
+/// ```
+/// # use async_zip::{base::write::ZipFileWriterBuilder, Compression};
+/// let writer = ZipFileWriterBuilder::new(Vec::<u8>::new())
+///     .buffered(64 * 1024)
+///     .reject_duplicates(true)
+///     .default_compression(Compression::Stored)
+///     .build();
+/// ```
+pub struct ZipFileWriterBuilder<W>(pub(crate) ZipFileWriter<W>);
+
+impl<W: AsyncWrite + Unpin> ZipFileWriterBuilder<W> {
+    /// Constructs a new builder with the default configuration from a writer.
+    pub fn new(writer: W) -> Self {
+        Self(ZipFileWriter::new(writer))
+    }
+
+    /// Force the ZIP writer to operate in non-ZIP64 mode.
+    /// If any files would need ZIP64, an error will be raised.
+    pub fn force_no_zip64(mut self) -> Self {
+        self.0 = self.0.force_no_zip64();
+        self
+    }
+
+    /// Force the ZIP writer to emit Zip64 structs at the end of the archive.
+    /// Zip64 extended fields will only be written if needed.
+    pub fn force_zip64(mut self) -> Self {
+        self.0 = self.0.force_zip64();
+        self
+    }
+
+    /// Buffer writes to the inner writer using an internal buffer of the provided capacity (in bytes).
+    ///
+    /// ZIP headers are written as a number of small, separate writes. When the inner writer performs a system call per
+    /// write (eg. an unbuffered file), this can be slow when writing many small entries. Any buffered data is flushed
+    /// to the inner writer within [`ZipFileWriter::close()`].
+    pub fn buffered(mut self, capacity: usize) -> Self {
+        self.0.writer.set_buffer_capacity(capacity);
+        self
+    }
+
+    /// Set whether to reject entries whose filenames match that of an entry which has already been written.
+    ///
+    /// By default, multiple entries with the same filename may be written. When enabled, any attempt to write such an
+    /// entry will return [`crate::error::ZipError::DuplicateEntryName`] before any of its data is written. An entry's
+    /// filename is only taken once it has been written successfully, so a failed entry may be retried.
+    pub fn reject_duplicates(mut self, reject: bool) -> Self {
+        self.0.names = reject.then(HashSet::new);
+        self
+    }
+
+    /// Panic, rather than log a warning, if this writer or any of its entry stream writers are dropped without being
+    /// closed.
+    ///
+    /// This is useful within tests and debug builds to catch a missing call to `close()`, which would otherwise result
+    /// in a corrupted ZIP file.
+    pub fn panic_if_unclosed(mut self) -> Self {
+        self.0.guard.panic = true;
+        self
+    }
+
+    /// Stamp every entry which doesn't set its own last modification date with the time returned by the provided clock.
+    ///
+    /// By default, such entries are written with a zeroed MS-DOS date & time. A fixed clock makes all entries share
+    /// the same timestamp (eg. for reproducible builds), whilst the current time can be used via chrono:
+    /// ```
+    /// # #[cfg(feature = "chrono")]
+    /// # {
+    /// # use async_zip::{base::write::ZipFileWriterBuilder, ZipDateTime};
+    /// let builder = ZipFileWriterBuilder::new(Vec::<u8>::new()).clock(|| ZipDateTime::from_chrono(&chrono::Utc::now()));
+    /// # }
+    /// ```
+    pub fn clock<C>(mut self, clock: C) -> Self
+    where
+        C: Fn() -> ZipDateTime + Send + Sync + 'static,
+    {
+        self.0.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Sets the compression method of entries constructed via [`ZipEntryBuilder::new_named()`].
+    ///
+    /// By default, such entries are stored without compression.
+    ///
+    /// [`ZipEntryBuilder::new_named()`]: crate::ZipEntryBuilder::new_named
+    pub fn default_compression(mut self, compression: Compression) -> Self {
+        self.0.default_compression = compression;
+        self
+    }
+
+    /// Sets the constructor of the hasher used to compute the CRC32 values of streamed entries.
+    ///
+    /// By default, [`crc32fast::Hasher`] is used. Whole entries are hashed via `crc32fast` regardless.
+    pub fn crc32_hasher<H>(mut self, hasher: H) -> Self
+    where
+        H: Fn() -> Box<dyn Crc32> + Send + Sync + 'static,
+    {
+        self.0.hasher = Some(Box::new(hasher));
+        self
+    }
+
+    /// Sets the specification version written as the lower byte of each entry's "version made by" field.
+    ///
+    /// This is stored as the major version multiplied by ten plus the minor version (eg. `20` for 2.0 or `45` for 4.5),
+    /// and defaults to `63`. The upper byte (the host system) is still taken from each entry's attribute host
+    /// compatibility. This is useful when reproducing an archive written by another tool byte-for-byte.
+    pub fn spec_version_made_by(mut self, version: u8) -> Self {
+        self.0.spec_version = version;
+        self
+    }
+
+    /// Sorts the archive's central directory records by the provided key when the writer is closed.
+    ///
+    /// By default, records are written in the order their entries were written. Sorting only affects the central
+    /// directory; entries' local file headers and data remain in the order they were written, and each record still
+    /// points to its own entry. Records which compare equal keep their relative order.
+    pub fn sort_central_directory(mut self, by: SortKey) -> Self {
+        self.0.cd_order = Some(by);
+        self
+    }
+
+    /// Flush the inner writer after each entry written via [`ZipFileWriter::write_entry_whole()`] or
+    /// [`ZipFileWriter::write_entry_whole_precompressed()`].
+    ///
+    /// This bounds the amount of data held back from a slow sink (eg. a chunked HTTP response) when writing many whole
+    /// entries, including any buffered via [`ZipFileWriterBuilder::buffered()`]. Disabled by default.
+    pub fn flush_after_each_entry(mut self, enabled: bool) -> Self {
+        self.0.flush_after_each_entry = enabled;
+        self
+    }
+
+    /// Reuse the provided buffer for the compressed output of every entry written via
+    /// [`ZipFileWriter::write_entry_whole()`].
+    ///
+    /// By default, a new buffer is allocated for each entry's compressed output. When writing many small entries, a
+    /// single reused buffer avoids these per-entry allocations. The buffer retains the capacity needed by the largest
+    /// entry written until this writer is closed.
+    pub fn scratch_buffer(mut self, buffer: Vec<u8>) -> Self {
+        self.0.scratch = Some(buffer);
+        self
+    }
+
+    /// Consumes this builder and returns a final [`ZipFileWriter`].
+    ///
+    /// This is equivalent to:
+    /// ```
+    /// # use async_zip::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+    /// #
+    /// # let builder = ZipFileWriterBuilder::new(Vec::<u8>::new());
+    /// let writer: ZipFileWriter<_> = builder.into();
+    /// ```
+    #[must_use]
+    pub fn build(self) -> ZipFileWriter<W> {
+        self.into()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<W> ZipFileWriterBuilder<Compat<W>>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    /// Constructs a new builder with the default configuration from a [`tokio`] writer.
+    pub fn with_tokio(writer: W) -> Self {
+        Self(ZipFileWriter::new(writer.compat_write()))
+    }
+}
+
+impl<W> From<ZipFileWriterBuilder<W>> for ZipFileWriter<W> {
+    fn from(builder: ZipFileWriterBuilder<W>) -> Self {
+        builder.0
+    }
+}
@@ -1,7 +1,13 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "deflate")]
+use crate::base::write::io::deflate::DeflateStrategyWriter;
+#[cfg(feature = "lzma")]
+use crate::base::write::io::lzma::LzmaZipWriter;
 use crate::base::write::io::offset::AsyncOffsetWriter;
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
 use crate::spec::Compression;
 
 use std::io::Error;
@@ -16,10 +22,12 @@ pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
     Stored(ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>),
     #[cfg(feature = "deflate")]
     Deflate(write::DeflateEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    #[cfg(feature = "deflate")]
+    DeflateStrategy(DeflateStrategyWriter<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "bzip2")]
     Bz(write::BzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "lzma")]
-    Lzma(write::LzmaEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Lzma(write::LzmaEncoder<LzmaZipWriter<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>>),
     #[cfg(feature = "zstd")]
     Zstd(write::ZstdEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "xz")]
@@ -27,28 +35,41 @@ pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
 }
 
 impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
-    pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, compression: Compression, precompressed: bool) -> Self {
+    pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, entry: &ZipEntry, precompressed: bool) -> Result<Self> {
         if precompressed {
-            return CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer));
+            return Ok(CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer)));
         }
 
-        match compression {
+        let writer = match entry.compression() {
             Compression::Stored => CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer)),
             #[cfg(feature = "deflate")]
+            Compression::Deflate if entry.deflate_strategy != crate::DeflateStrategy::Default => {
+                CompressedAsyncWriter::DeflateStrategy(DeflateStrategyWriter::new(
+                    ShutdownIgnoredWriter(writer),
+                    entry.compression_level,
+                    entry.deflate_strategy,
+                ))
+            }
+            #[cfg(feature = "deflate")]
             Compression::Deflate => {
                 CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(ShutdownIgnoredWriter(writer)))
             }
             #[cfg(feature = "deflate64")]
-            Compression::Deflate64 => panic!("writing deflate64 is not supported"),
+            Compression::Deflate64 => return Err(ZipError::CompressionNotSupported(Compression::Deflate64.into())),
             #[cfg(feature = "bzip2")]
             Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(ShutdownIgnoredWriter(writer))),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Lzma => {
+                CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(LzmaZipWriter::new(ShutdownIgnoredWriter(writer))))
+            }
             #[cfg(feature = "zstd")]
             Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(ShutdownIgnoredWriter(writer))),
             #[cfg(feature = "xz")]
             Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(ShutdownIgnoredWriter(writer))),
-        }
+            Compression::Unsupported(method) => return Err(ZipError::CompressionNotSupported(method)),
+        };
+
+        Ok(writer)
     }
 
     pub fn into_inner(self) -> &'b mut AsyncOffsetWriter<W> {
@@ -56,10 +77,12 @@ impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
             CompressedAsyncWriter::Stored(inner) => inner.into_inner(),
             #[cfg(feature = "deflate")]
             CompressedAsyncWriter::Deflate(inner) => inner.into_inner().into_inner(),
+            #[cfg(feature = "deflate")]
+            CompressedAsyncWriter::DeflateStrategy(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "bzip2")]
             CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "zstd")]
             CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "xz")]
@@ -74,6 +97,8 @@ impl<'b, W: AsyncWrite + Unpin> AsyncWrite for CompressedAsyncWriter<'b, W> {
             CompressedAsyncWriter::Stored(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
             #[cfg(feature = "deflate")]
             CompressedAsyncWriter::Deflate(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
+            #[cfg(feature = "deflate")]
+            CompressedAsyncWriter::DeflateStrategy(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
             #[cfg(feature = "bzip2")]
             CompressedAsyncWriter::Bz(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
             #[cfg(feature = "lzma")]
@@ -90,6 +115,8 @@ impl<'b, W: AsyncWrite + Unpin> AsyncWrite for CompressedAsyncWriter<'b, W> {
             CompressedAsyncWriter::Stored(ref mut inner) => Pin::new(inner).poll_flush(cx),
             #[cfg(feature = "deflate")]
             CompressedAsyncWriter::Deflate(ref mut inner) => Pin::new(inner).poll_flush(cx),
+            #[cfg(feature = "deflate")]
+            CompressedAsyncWriter::DeflateStrategy(ref mut inner) => Pin::new(inner).poll_flush(cx),
             #[cfg(feature = "bzip2")]
             CompressedAsyncWriter::Bz(ref mut inner) => Pin::new(inner).poll_flush(cx),
             #[cfg(feature = "lzma")]
@@ -106,6 +133,8 @@ impl<'b, W: AsyncWrite + Unpin> AsyncWrite for CompressedAsyncWriter<'b, W> {
             CompressedAsyncWriter::Stored(ref mut inner) => Pin::new(inner).poll_close(cx),
             #[cfg(feature = "deflate")]
             CompressedAsyncWriter::Deflate(ref mut inner) => Pin::new(inner).poll_close(cx),
+            #[cfg(feature = "deflate")]
+            CompressedAsyncWriter::DeflateStrategy(ref mut inner) => Pin::new(inner).poll_close(cx),
             #[cfg(feature = "bzip2")]
             CompressedAsyncWriter::Bz(ref mut inner) => Pin::new(inner).poll_close(cx),
             #[cfg(feature = "lzma")]
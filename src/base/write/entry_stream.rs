@@ -2,29 +2,27 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::base::write::compressed_writer::CompressedAsyncWriter;
-use crate::base::write::get_or_put_info_zip_unicode_comment_extra_field_mut;
-use crate::base::write::get_or_put_info_zip_unicode_path_extra_field_mut;
+use crate::base::write::guard::CloseGuard;
 use crate::base::write::io::offset::AsyncOffsetWriter;
-use crate::base::write::CentralDirectoryEntry;
 use crate::base::write::ZipFileWriter;
+use crate::base::write::{local_extra_fields, register_name, unicode_flag};
+use crate::base::write::{CentralDirectoryEntry, WrittenEntry};
 use crate::entry::ZipEntry;
 use crate::error::{Result, Zip64ErrorCase, ZipError};
 use crate::spec::extra_field::ExtraFieldAsBytes;
-use crate::spec::header::InfoZipUnicodeCommentExtraField;
-use crate::spec::header::InfoZipUnicodePathExtraField;
 use crate::spec::header::{
     CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, HeaderId, LocalFileHeader,
     Zip64ExtendedInformationExtraField,
 };
-use crate::string::StringEncoding;
 
+use std::collections::HashSet;
 use std::io::Error;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::base::read::get_zip64_extra_field_mut;
+use crate::crc::Crc32;
 use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
-use crc32fast::Hasher;
 use futures_lite::io::{AsyncWrite, AsyncWriteExt};
 
 /// An entry writer which supports the streaming of data (ie. the writing of unknown size or data at runtime).
@@ -33,18 +31,24 @@ use futures_lite::io::{AsyncWrite, AsyncWriteExt};
 /// - This writer cannot be manually constructed; instead, use [`ZipFileWriter::write_entry_stream()`].
 /// - [`EntryStreamWriter::close()`] must be called before a stream writer goes out of scope.
 /// - Utilities for working with [`AsyncWrite`] values are provided by [`AsyncWriteExt`].
+/// - Flushing this writer performs a sync flush of the entry's compressor (eg. emitting an empty stored block for
+///   Deflate) before flushing the underlying writer, so all data written so far can be decompressed by the receiver.
+///   Frequent flushes reduce the compression ratio.
 pub struct EntryStreamWriter<'b, W: AsyncWrite + Unpin> {
     writer: AsyncOffsetWriter<CompressedAsyncWriter<'b, W>>,
     cd_entries: &'b mut Vec<CentralDirectoryEntry>,
+    names: &'b mut Option<HashSet<Vec<u8>>>,
     entry: ZipEntry,
-    hasher: Hasher,
+    hasher: Box<dyn Crc32>,
     lfh: LocalFileHeader,
     lfh_offset: u64,
     data_offset: u64,
     force_no_zip64: bool,
+    spec_version: u8,
     /// To write back to the original writer if zip64 is required.
     is_zip64: &'b mut bool,
     precompressed: bool,
+    pub(crate) guard: CloseGuard,
 }
 
 impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
@@ -52,27 +56,36 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         writer: &'b mut ZipFileWriter<W>,
         mut entry: ZipEntry,
     ) -> Result<EntryStreamWriter<'b, W>> {
+        writer.inherit_compression(&mut entry);
+        entry.compression().writable()?;
+
         let lfh_offset = writer.writer.offset();
         let lfh = EntryStreamWriter::write_lfh(writer, &mut entry).await?;
         let data_offset = writer.writer.offset();
         let force_no_zip64 = writer.force_no_zip64;
+        let spec_version = writer.spec_version;
+        let guard = CloseGuard::new("EntryStreamWriter", writer.guard.panic);
+        let hasher = writer.new_hasher();
 
         let cd_entries = &mut writer.cd_entries;
+        let names = &mut writer.names;
         let is_zip64 = &mut writer.is_zip64;
-        let writer =
-            AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression(), false));
+        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, &entry, false)?);
 
         Ok(EntryStreamWriter {
             writer,
             cd_entries,
+            names,
             entry,
             lfh,
             lfh_offset,
             data_offset,
-            hasher: Hasher::new(),
+            hasher,
             force_no_zip64,
+            spec_version,
             is_zip64,
             precompressed: false,
+            guard,
         })
     }
 
@@ -84,27 +97,36 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         let lfh = EntryStreamWriter::write_lfh(writer, &mut entry).await?;
         let data_offset = writer.writer.offset();
         let force_no_zip64 = writer.force_no_zip64;
+        let spec_version = writer.spec_version;
+        let guard = CloseGuard::new("EntryStreamWriter", writer.guard.panic);
+        let hasher = writer.new_hasher();
 
         let cd_entries = &mut writer.cd_entries;
+        let names = &mut writer.names;
         let is_zip64 = &mut writer.is_zip64;
-        let writer =
-            AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression(), true));
+        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, &entry, true)?);
 
         Ok(EntryStreamWriter {
             writer,
             cd_entries,
+            names,
             entry,
             lfh,
             lfh_offset,
             data_offset,
-            hasher: Hasher::new(),
+            hasher,
             force_no_zip64,
+            spec_version,
             is_zip64,
             precompressed: true,
+            guard,
         })
     }
 
     async fn write_lfh(writer: &'b mut ZipFileWriter<W>, entry: &mut ZipEntry) -> Result<LocalFileHeader> {
+        writer.check_name(entry)?;
+        writer.stamp(entry);
+
         // Always emit a zip64 extended field, even if we don't need it, because we *might* need it.
         // If we are forcing no zip, we will have to error later if the file is too large.
         let (lfh_compressed, lfh_uncompressed) = if !writer.force_no_zip64 {
@@ -129,51 +151,25 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             (entry.compressed_size as u32, entry.uncompressed_size as u32)
         };
 
-        let utf8_without_alternative =
-            entry.filename().is_utf8_without_alternative() && entry.comment().is_utf8_without_alternative();
-        if !utf8_without_alternative {
-            if matches!(entry.filename().encoding(), StringEncoding::Utf8) {
-                let u_file_name = entry.filename().as_bytes().to_vec();
-                if !u_file_name.is_empty() {
-                    let basic_crc32 =
-                        crc32fast::hash(entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes()));
-                    let upath_field = get_or_put_info_zip_unicode_path_extra_field_mut(entry.extra_fields.as_mut());
-                    if let InfoZipUnicodePathExtraField::V1 { crc32, unicode } = upath_field {
-                        *crc32 = basic_crc32;
-                        *unicode = u_file_name;
-                    }
-                }
-            }
-            if matches!(entry.comment().encoding(), StringEncoding::Utf8) {
-                let u_comment = entry.comment().as_bytes().to_vec();
-                if !u_comment.is_empty() {
-                    let basic_crc32 =
-                        crc32fast::hash(entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes()));
-                    let ucom_field = get_or_put_info_zip_unicode_comment_extra_field_mut(entry.extra_fields.as_mut());
-                    if let InfoZipUnicodeCommentExtraField::V1 { crc32, unicode } = ucom_field {
-                        *crc32 = basic_crc32;
-                        *unicode = u_comment;
-                    }
-                }
-            }
-        }
+        let utf8_without_alternative = unicode_flag(entry);
 
         let filename_basic = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes());
 
+        let local_extra_fields: &[ExtraField] = &local_extra_fields(entry, writer.writer.offset());
+
         let lfh = LocalFileHeader {
             compressed_size: lfh_compressed,
             uncompressed_size: lfh_uncompressed,
             compression: entry.compression().into(),
             crc: entry.crc32,
-            extra_field_length: entry
-                .extra_fields()
+            extra_field_length: local_extra_fields
                 .count_bytes()
                 .try_into()
                 .map_err(|_| ZipError::ExtraFieldTooLarge)?,
             file_name_length: filename_basic.len().try_into().map_err(|_| ZipError::FileNameTooLarge)?,
             mod_time: entry.last_modification_date().time,
             mod_date: entry.last_modification_date().date,
-            version: crate::spec::version::as_needed_to_extract(entry),
+            version: crate::spec::version::needed_to_extract(entry)?,
             flags: GeneralPurposeFlag {
                 data_descriptor: true,
                 encrypted: false,
@@ -184,11 +180,18 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         writer.writer.write_all(&lfh.as_slice()).await?;
         writer.writer.write_all(filename_basic).await?;
-        writer.writer.write_all(&entry.extra_fields().as_bytes()).await?;
+        writer.writer.write_all(&local_extra_fields.as_bytes()).await?;
 
         Ok(lfh)
     }
 
+    /// Returns the number of uncompressed bytes written to this entry so far.
+    ///
+    /// This is cheap to call and so is suitable for polling whilst driving progress reporting.
+    pub fn bytes_written(&self) -> u64 {
+        self.writer.offset()
+    }
+
     /// Consumes this entry writer and completes all closing tasks.
     ///
     /// This includes:
@@ -197,12 +200,16 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
     /// - Constructing a central directory header.
     /// - Pushing that central directory header to the [`ZipFileWriter`]'s store.
     ///
-    /// Failure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<()> {
+    /// Failure to call this function before going out of scope would result in a corrupted ZIP file. This is flagged on
+    /// drop by logging a warning, or by panicking if enabled via [`ZipFileWriterBuilder::panic_if_unclosed()`].
+    ///
+    /// [`ZipFileWriterBuilder::panic_if_unclosed()`]: crate::base::write::ZipFileWriterBuilder::panic_if_unclosed
+    pub async fn close(mut self) -> Result<WrittenEntry> {
+        self.guard.disarm();
         self.writer.close().await?;
 
         if !self.precompressed {
-            self.entry.crc32 = self.hasher.finalize();
+            self.entry.crc32 = self.hasher.sum();
             self.entry.uncompressed_size = self.writer.offset();
         }
 
@@ -216,7 +223,7 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             {
                 return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
             }
-            (self.entry.uncompressed_size as u32, compressed_size as u32, self.lfh_offset as u32)
+            (compressed_size as u32, self.entry.uncompressed_size as u32, self.lfh_offset as u32)
         } else {
             // When streaming an entry, we are always using a zip64 field.
             match get_zip64_extra_field_mut(&mut self.entry.extra_fields) {
@@ -238,16 +245,23 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
                     zip64.relative_header_offset = Some(self.lfh_offset);
                 }
             }
-            self.lfh.extra_field_length =
-                self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
 
             (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE)
         };
 
         inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
         inner_writer.write_all(&self.entry.crc32.to_le_bytes()).await?;
-        inner_writer.write_all(&cdr_compressed_size.to_le_bytes()).await?;
-        inner_writer.write_all(&cdr_uncompressed_size.to_le_bytes()).await?;
+
+        // As the local file header holds a zip64 extended information field, the data descriptor holds 64-bit sizes.
+        if self.force_no_zip64 {
+            inner_writer.write_all(&cdr_compressed_size.to_le_bytes()).await?;
+            inner_writer.write_all(&cdr_uncompressed_size.to_le_bytes()).await?;
+        } else {
+            inner_writer.write_all(&compressed_size.to_le_bytes()).await?;
+            inner_writer.write_all(&self.entry.uncompressed_size.to_le_bytes()).await?;
+        }
+
+        let written = WrittenEntry { compressed_size, bytes_written: inner_writer.offset() - self.lfh_offset };
 
         let comment_basic = self.entry.comment().alternative().unwrap_or_else(|| self.entry.comment().as_bytes());
 
@@ -255,10 +269,15 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             compressed_size: cdr_compressed_size,
             uncompressed_size: cdr_uncompressed_size,
             crc: self.entry.crc32,
-            v_made_by: crate::spec::version::as_made_by(),
+            v_made_by: crate::spec::version::made_by(&self.entry, self.spec_version),
             v_needed: self.lfh.version,
             compression: self.lfh.compression,
-            extra_field_length: self.lfh.extra_field_length,
+            extra_field_length: self
+                .entry
+                .extra_fields()
+                .count_bytes()
+                .try_into()
+                .map_err(|_| ZipError::ExtraFieldTooLarge)?,
             file_name_length: self.lfh.file_name_length,
             file_comment_length: comment_basic.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
             mod_time: self.lfh.mod_time,
@@ -270,7 +289,8 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             lh_offset,
         };
 
-        self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry });
+        register_name(self.names, &self.entry);
+        self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry, raw_extra: None });
         // Ensure that we can fit this many files in this archive if forcing no zip64
         if self.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
             if self.force_no_zip64 {
@@ -281,7 +301,7 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             }
         }
 
-        Ok(())
+        Ok(written)
     }
 }
 
@@ -3,23 +3,26 @@
 
 use std::borrow::Cow;
 
-use crate::base::write::get_or_put_info_zip_unicode_comment_extra_field_mut;
-use crate::base::write::get_or_put_info_zip_unicode_path_extra_field_mut;
-use crate::base::write::{CentralDirectoryEntry, ZipFileWriter};
+use crate::base::read::get_zip64_extra_field;
+#[cfg(feature = "deflate")]
+use crate::base::write::io::deflate::DeflateStrategyWriter;
+#[cfg(feature = "lzma")]
+use crate::base::write::io::lzma::LzmaZipWriter;
+use crate::base::write::{
+    local_extra_fields, register_name, unicode_flag, CentralDirectoryEntry, WrittenEntry, ZipFileWriter,
+};
 use crate::entry::ZipEntry;
 use crate::error::{Result, Zip64ErrorCase, ZipError};
 use crate::spec::extra_field::Zip64ExtendedInformationExtraFieldBuilder;
-use crate::spec::header::{InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField};
 use crate::spec::{
     extra_field::ExtraFieldAsBytes,
     header::{CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, LocalFileHeader},
     Compression,
 };
-use crate::StringEncoding;
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use futures_lite::io::Cursor;
 
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::consts::{DATA_DESCRIPTOR_SIGNATURE, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use async_compression::futures::write;
 use futures_lite::io::{AsyncWrite, AsyncWriteExt};
@@ -42,25 +45,6 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
         Self { writer, entry, data: Cow::Borrowed(data), builder: None, lh_offset: 0, precompressed: true }
     }
 
-    async fn compress(&mut self) {
-        if self.entry.compression() == Compression::Stored {
-            return;
-        }
-
-        #[cfg(any(
-            feature = "deflate",
-            feature = "bzip2",
-            feature = "zstd",
-            feature = "lzma",
-            feature = "xz",
-            feature = "deflate64"
-        ))]
-        {
-            let new_data = compress(&self.entry, &self.data).await;
-            self.data = Cow::Owned(new_data);
-        }
-    }
-
     fn enforce_zip64_sizes(&mut self) -> Result<()> {
         let uncompressed_larger = self.entry.uncompressed_size > NON_ZIP64_MAX_SIZE.into();
         let compressed_larger = self.entry.compressed_size > NON_ZIP64_MAX_SIZE.into();
@@ -108,56 +92,32 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
         Ok(())
     }
 
-    fn utf8_without_alternative(&mut self) -> bool {
-        let utf8_without_alternative =
-            self.entry.filename().is_utf8_without_alternative() && self.entry.comment().is_utf8_without_alternative();
-
-        if !utf8_without_alternative {
-            if matches!(self.entry.filename().encoding(), StringEncoding::Utf8) {
-                let u_file_name = self.entry.filename().as_bytes().to_vec();
-                if !u_file_name.is_empty() {
-                    let basic_crc32 = crc32fast::hash(
-                        self.entry.filename().alternative().unwrap_or_else(|| self.entry.filename().as_bytes()),
-                    );
-                    let upath_field =
-                        get_or_put_info_zip_unicode_path_extra_field_mut(self.entry.extra_fields.as_mut());
-                    if let InfoZipUnicodePathExtraField::V1 { crc32, unicode } = upath_field {
-                        *crc32 = basic_crc32;
-                        *unicode = u_file_name;
-                    }
-                }
-            }
-            if matches!(self.entry.comment().encoding(), StringEncoding::Utf8) {
-                let u_comment = self.entry.comment().as_bytes().to_vec();
-                if !u_comment.is_empty() {
-                    let basic_crc32 = crc32fast::hash(
-                        self.entry.comment().alternative().unwrap_or_else(|| self.entry.comment().as_bytes()),
-                    );
-                    let ucom_field =
-                        get_or_put_info_zip_unicode_comment_extra_field_mut(self.entry.extra_fields.as_mut());
-                    if let InfoZipUnicodeCommentExtraField::V1 { crc32, unicode } = ucom_field {
-                        *crc32 = basic_crc32;
-                        *unicode = u_comment;
-                    }
-                }
-            }
+    pub async fn write(mut self) -> Result<WrittenEntry> {
+        if !self.precompressed {
+            self.writer.inherit_compression(&mut self.entry);
+            self.entry.compression().writable()?;
         }
+        self.writer.check_name(&self.entry)?;
+        self.writer.stamp(&mut self.entry);
 
-        utf8_without_alternative
-    }
+        let mut scratch = self.writer.scratch.take();
 
-    pub async fn write(mut self) -> Result<()> {
         if !self.precompressed {
-            self.entry.uncompressed_size = self.data.len() as u64;
-            self.entry.crc32 = crc32fast::hash(&self.data);
-
-            self.compress().await;
+            match prepare(&mut self.entry, std::mem::take(&mut self.data), scratch.as_mut()).await {
+                Ok(data) => self.data = data,
+                Err(err) => {
+                    self.writer.scratch = scratch;
+                    return Err(err);
+                }
+            }
         }
 
         self.entry.compressed_size = self.data.len() as u64;
+        let uncompressed_size = self.entry.uncompressed_size;
         self.enforce_zip64_sizes()?;
 
-        self.lh_offset = self.writer.writer.offset();
+        let start = self.writer.writer.offset();
+        self.lh_offset = start;
         self.enforce_zip64_offset()?;
 
         if let Some(builder) = self.builder {
@@ -169,40 +129,53 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
             }
         }
 
-        let utf8_without_alternative = self.utf8_without_alternative();
+        let utf8_without_alternative = unicode_flag(&mut self.entry);
         let filename_basic = self.entry.filename().alternative().unwrap_or_else(|| self.entry.filename().as_bytes());
         let comment_basic = self.entry.comment().alternative().unwrap_or_else(|| self.entry.comment().as_bytes());
 
+        let local_extra_fields: &[ExtraField] = &local_extra_fields(&self.entry, start);
+
+        // With a data descriptor, the CRC32 and sizes are deferred until after the data (as when streaming). Sizes held
+        // within a zip64 extended information field are left in place, as its layout depends on the header's sizes.
+        let data_descriptor = self.entry.force_data_descriptor;
+        let zip64_descriptor = get_zip64_extra_field(local_extra_fields).is_some();
+        let (lfh_crc, lfh_compressed, lfh_uncompressed) = match (data_descriptor, zip64_descriptor) {
+            (true, false) => (0, 0, 0),
+            (true, true) => (0, self.entry.compressed_size() as u32, self.entry.uncompressed_size() as u32),
+            (false, _) => {
+                (self.entry.crc32(), self.entry.compressed_size() as u32, self.entry.uncompressed_size() as u32)
+            }
+        };
+
         let lf_header = LocalFileHeader {
-            compressed_size: self.entry.compressed_size() as u32,
-            uncompressed_size: self.entry.uncompressed_size() as u32,
+            compressed_size: lfh_compressed,
+            uncompressed_size: lfh_uncompressed,
             compression: self.entry.compression().into(),
-            crc: self.entry.crc32(),
-            extra_field_length: self
-                .entry
-                .extra_fields()
+            crc: lfh_crc,
+            extra_field_length: local_extra_fields
                 .count_bytes()
                 .try_into()
                 .map_err(|_| ZipError::ExtraFieldTooLarge)?,
             file_name_length: filename_basic.len().try_into().map_err(|_| ZipError::FileNameTooLarge)?,
             mod_time: self.entry.last_modification_date().time,
             mod_date: self.entry.last_modification_date().date,
-            version: crate::spec::version::as_needed_to_extract(&self.entry),
-            flags: GeneralPurposeFlag {
-                data_descriptor: false,
-                encrypted: false,
-                filename_unicode: utf8_without_alternative,
-            },
+            version: crate::spec::version::needed_to_extract(&self.entry)?,
+            flags: GeneralPurposeFlag { data_descriptor, encrypted: false, filename_unicode: utf8_without_alternative },
         };
 
         let mut header = CentralDirectoryRecord {
-            v_made_by: crate::spec::version::as_made_by(),
+            v_made_by: crate::spec::version::made_by(&self.entry, self.writer.spec_version),
             v_needed: lf_header.version,
-            compressed_size: lf_header.compressed_size,
-            uncompressed_size: lf_header.uncompressed_size,
+            compressed_size: self.entry.compressed_size() as u32,
+            uncompressed_size: self.entry.uncompressed_size() as u32,
             compression: lf_header.compression,
-            crc: lf_header.crc,
-            extra_field_length: lf_header.extra_field_length,
+            crc: self.entry.crc32(),
+            extra_field_length: self
+                .entry
+                .extra_fields()
+                .count_bytes()
+                .try_into()
+                .map_err(|_| ZipError::ExtraFieldTooLarge)?,
             file_name_length: lf_header.file_name_length,
             file_comment_length: comment_basic.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
             mod_time: lf_header.mod_time,
@@ -217,16 +190,42 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
         self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         self.writer.writer.write_all(&lf_header.as_slice()).await?;
         self.writer.writer.write_all(filename_basic).await?;
-        self.writer.writer.write_all(&self.entry.extra_fields().as_bytes()).await?;
+        self.writer.writer.write_all(&local_extra_fields.as_bytes()).await?;
         self.writer.writer.write_all(&self.data).await?;
 
+        if data_descriptor {
+            self.writer.writer.write_all(&DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+            self.writer.writer.write_all(&self.entry.crc32().to_le_bytes()).await?;
+
+            // Readers expect 64-bit sizes within the descriptor if the local file header holds a zip64 field.
+            if zip64_descriptor {
+                self.writer.writer.write_all(&(self.data.len() as u64).to_le_bytes()).await?;
+                self.writer.writer.write_all(&uncompressed_size.to_le_bytes()).await?;
+            } else {
+                self.writer.writer.write_all(&(self.data.len() as u32).to_le_bytes()).await?;
+                self.writer.writer.write_all(&(uncompressed_size as u32).to_le_bytes()).await?;
+            }
+        }
+
+        let written = WrittenEntry {
+            compressed_size: self.data.len() as u64,
+            bytes_written: self.writer.writer.offset() - start,
+        };
+
+        // Any owned data is the compressed output, which now holds the scratch buffer's allocation.
+        if let (Some(scratch), Cow::Owned(data)) = (scratch.as_mut(), std::mem::take(&mut self.data)) {
+            *scratch = data;
+        }
+        self.writer.scratch = scratch;
+
         if let Some(builder1) = self.builder {
             self.entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder1.build()?));
             header.extra_field_length =
                 self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
         }
 
-        self.writer.cd_entries.push(CentralDirectoryEntry { header, entry: self.entry });
+        register_name(&mut self.writer.names, &self.entry);
+        self.writer.cd_entries.push(CentralDirectoryEntry { header, entry: self.entry, raw_extra: None });
         // Ensure that we can fit this many files in this archive if forcing no zip64
         if self.writer.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
             if self.writer.force_no_zip64 {
@@ -236,7 +235,52 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
                 self.writer.is_zip64 = true;
             }
         }
-        Ok(())
+        Ok(written)
+    }
+}
+
+/// Prepares the data of a ZIP entry for writing, returning the data as it should be emitted.
+///
+/// This includes setting the entry's CRC32 value (unless a trusted value was provided) and uncompressed size, and
+/// compressing the data using the entry's compression method. The returned data can then be emitted via
+/// [`EntryWholeWriter::from_precompressed()`].
+///
+/// If a scratch buffer is provided, its allocation is taken and reused for any compressed output.
+pub(crate) async fn prepare<'c>(
+    entry: &mut ZipEntry,
+    data: Cow<'c, [u8]>,
+    scratch: Option<&mut Vec<u8>>,
+) -> Result<Cow<'c, [u8]>> {
+    entry.uncompressed_size = data.len() as u64;
+    entry.crc32 = entry.trusted_crc32.unwrap_or_else(|| crc32fast::hash(&data));
+
+    if entry.compression() == Compression::Stored {
+        return Ok(data);
+    }
+
+    #[cfg(any(
+        feature = "deflate",
+        feature = "bzip2",
+        feature = "zstd",
+        feature = "lzma",
+        feature = "xz",
+        feature = "deflate64"
+    ))]
+    {
+        Ok(Cow::Owned(compress_into(entry, &data, scratch.map(std::mem::take).unwrap_or_default()).await?))
+    }
+
+    #[cfg(not(any(
+        feature = "deflate",
+        feature = "bzip2",
+        feature = "zstd",
+        feature = "lzma",
+        feature = "xz",
+        feature = "deflate64"
+    )))]
+    {
+        let _ = scratch;
+        Ok(data)
     }
 }
 
@@ -249,50 +293,80 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
     feature = "deflate64"
 ))]
 /// Compresses the data of a ZIP entry using the specified compression method and level.
+///
+/// # Panics
+/// If the entry's compression method can't be written (ie. [`Compression::Unsupported`] or Deflate64). Such entries
+/// are rejected with [`ZipError::CompressionNotSupported`] when written via [`ZipFileWriter`] instead.
+///
+/// [`ZipFileWriter`]: crate::base::write::ZipFileWriter
 pub async fn compress(entry: &ZipEntry, data: &[u8]) -> Vec<u8> {
+    match compress_into(entry, data, Vec::new()).await {
+        Ok(compressed) => compressed,
+        Err(err) => panic!("failed to compress entry data: {err}"),
+    }
+}
+
+#[cfg(any(
+    feature = "deflate",
+    feature = "bzip2",
+    feature = "zstd",
+    feature = "lzma",
+    feature = "xz",
+    feature = "deflate64"
+))]
+/// Compresses the data of a ZIP entry into the provided buffer, after it has been cleared.
+pub(crate) async fn compress_into(entry: &ZipEntry, data: &[u8], mut buffer: Vec<u8>) -> Result<Vec<u8>> {
     // TODO: Reduce reallocations of Vec by making a lower-bound estimate of the length reduction and
     // pre-initialising the Vec to that length. Then truncate() to the actual number of bytes written.
     let level = entry.compression_level;
+    buffer.clear();
 
     match entry.compression() {
+        #[cfg(feature = "deflate")]
+        Compression::Deflate if entry.deflate_strategy != crate::DeflateStrategy::Default => {
+            let mut writer = DeflateStrategyWriter::new(Cursor::new(buffer), level, entry.deflate_strategy);
+            writer.write_all(data).await?;
+            writer.close().await?;
+            Ok(writer.into_inner().into_inner())
+        }
         #[cfg(feature = "deflate")]
         Compression::Deflate => {
-            let mut writer = write::DeflateEncoder::with_quality(Cursor::new(Vec::new()), level);
-            writer.write_all(data).await.unwrap();
-            writer.close().await.unwrap();
-            writer.into_inner().into_inner()
+            let mut writer = write::DeflateEncoder::with_quality(Cursor::new(buffer), level);
+            writer.write_all(data).await?;
+            writer.close().await?;
+            Ok(writer.into_inner().into_inner())
         }
         #[cfg(feature = "deflate64")]
-        Compression::Deflate64 => panic!("compressing deflate64 is not supported"),
+        Compression::Deflate64 => Err(ZipError::CompressionNotSupported(Compression::Deflate64.into())),
         #[cfg(feature = "bzip2")]
         Compression::Bz => {
-            let mut writer = write::BzEncoder::with_quality(Cursor::new(Vec::new()), level);
-            writer.write_all(data).await.unwrap();
-            writer.close().await.unwrap();
-            writer.into_inner().into_inner()
+            let mut writer = write::BzEncoder::with_quality(Cursor::new(buffer), level);
+            writer.write_all(data).await?;
+            writer.close().await?;
+            Ok(writer.into_inner().into_inner())
         }
         #[cfg(feature = "lzma")]
         Compression::Lzma => {
-            let mut writer = write::LzmaEncoder::with_quality(Cursor::new(Vec::new()), level);
-            writer.write_all(data).await.unwrap();
-            writer.close().await.unwrap();
-            writer.into_inner().into_inner()
+            let mut writer = write::LzmaEncoder::with_quality(LzmaZipWriter::new(Cursor::new(buffer)), level);
+            writer.write_all(data).await?;
+            writer.close().await?;
+            Ok(writer.into_inner().into_inner().into_inner())
         }
         #[cfg(feature = "xz")]
         Compression::Xz => {
-            let mut writer = write::XzEncoder::with_quality(Cursor::new(Vec::new()), level);
-            writer.write_all(data).await.unwrap();
-            writer.close().await.unwrap();
-            writer.into_inner().into_inner()
+            let mut writer = write::XzEncoder::with_quality(Cursor::new(buffer), level);
+            writer.write_all(data).await?;
+            writer.close().await?;
+            Ok(writer.into_inner().into_inner())
         }
         #[cfg(feature = "zstd")]
         Compression::Zstd => {
-            let mut writer = write::ZstdEncoder::with_quality(Cursor::new(Vec::new()), level);
-            writer.write_all(data).await.unwrap();
-            writer.close().await.unwrap();
-            writer.into_inner().into_inner()
+            let mut writer = write::ZstdEncoder::with_quality(Cursor::new(buffer), level);
+            writer.write_all(data).await?;
+            writer.close().await?;
+            Ok(writer.into_inner().into_inner())
         }
-        _ => unreachable!(),
+        compression => Err(ZipError::CompressionNotSupported(compression.into())),
     }
 }
 
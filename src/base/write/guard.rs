@@ -0,0 +1,40 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+/// A guard which flags a writer being dropped without its `close()` method having been called.
+///
+/// Async work can't be performed on drop, so the missing data can't be written. Instead, a warning is logged or, if
+/// configured, a panic is raised (unless the thread is already panicking).
+pub(crate) struct CloseGuard {
+    name: &'static str,
+    pub(crate) panic: bool,
+    armed: bool,
+}
+
+impl CloseGuard {
+    pub(crate) fn new(name: &'static str, panic: bool) -> Self {
+        Self { name, panic, armed: true }
+    }
+
+    /// Marks the guarded writer as closed.
+    pub(crate) fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for CloseGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+
+        if self.panic && !std::thread::panicking() {
+            panic!("{} was dropped without close() being called", self.name);
+        }
+
+        crate::utils::log_warn!(
+            "{} was dropped without close() being called, so the written ZIP file is incomplete",
+            self.name
+        );
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A deflate encoder which supports a non-default compression strategy.
+//!
+//! `async-compression` doesn't expose the compressor's strategy, so this drives miniz_oxide's compressor directly. It's
+//! only used when a non-default strategy is set, so the output of all other entries remains unchanged.
+
+use crate::spec::compression::DeflateStrategy;
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use async_compression::Level;
+use futures_lite::io::AsyncWrite;
+use miniz_oxide::deflate::core::{compress, CompressorOxide, TDEFLFlush, TDEFLStatus};
+use miniz_oxide::DataFormat;
+
+const OUTPUT_BUFFER_SIZE: usize = 32 * 1024;
+const WINDOW_BITS: u8 = 15;
+
+pub(crate) struct DeflateStrategyWriter<W> {
+    inner: W,
+    compressor: Box<CompressorOxide>,
+    output: Vec<u8>,
+    pos: usize,
+    /// Whether input has been compressed since the last sync flush.
+    unflushed: bool,
+    finished: bool,
+}
+
+impl<W: AsyncWrite + Unpin> DeflateStrategyWriter<W> {
+    pub(crate) fn new(inner: W, level: Level, strategy: DeflateStrategy) -> Self {
+        let level = match level {
+            Level::Fastest => 1,
+            Level::Best => 9,
+            Level::Precise(level) => level.clamp(0, 10) as u8,
+            _ => 6,
+        };
+        let compressor = CompressorOxide::with_params(DataFormat::Raw, level, strategy.into_miniz(), WINDOW_BITS);
+
+        Self { inner, compressor: Box::new(compressor), output: Vec::new(), pos: 0, unflushed: false, finished: false }
+    }
+
+    pub(crate) fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes any pending compressed output to the inner writer.
+    fn poll_drain(&mut self, cx: &mut Context) -> Poll<std::io::Result<()>> {
+        while self.pos < self.output.len() {
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.output[self.pos..]))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(ErrorKind::WriteZero.into()));
+            }
+
+            self.pos += written;
+        }
+
+        self.output.clear();
+        self.pos = 0;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Runs the compressor over the provided input, buffering its output, and returns the number of bytes consumed.
+    fn compress(&mut self, input: &[u8], flush: TDEFLFlush) -> std::io::Result<(TDEFLStatus, usize)> {
+        self.output.resize(OUTPUT_BUFFER_SIZE, 0);
+        let (status, consumed, produced) = compress(&mut self.compressor, input, &mut self.output, flush);
+        self.output.truncate(produced);
+
+        match status {
+            TDEFLStatus::Okay | TDEFLStatus::Done => Ok((status, consumed)),
+            _ => Err(Error::other(format!("deflate compression failed ({status:?})"))),
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for DeflateStrategyWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            ready!(this.poll_drain(cx))?;
+
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let (_, consumed) = this.compress(buf, TDEFLFlush::None)?;
+
+            if consumed > 0 {
+                this.unflushed = true;
+                return Poll::Ready(Ok(consumed));
+            } else if this.output.is_empty() {
+                return Poll::Ready(Err(Error::other("deflate compressor made no progress")));
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+
+        // Sync flush any input held within the compressor, unless none has arrived since the last flush. A full output
+        // buffer may leave more to be emitted, so flush again once it's drained.
+        loop {
+            ready!(this.poll_drain(cx))?;
+
+            if !this.unflushed {
+                break;
+            }
+
+            this.compress(&[], TDEFLFlush::Sync)?;
+            this.unflushed = this.output.len() == OUTPUT_BUFFER_SIZE;
+        }
+
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            ready!(this.poll_drain(cx))?;
+
+            if this.finished {
+                break;
+            }
+
+            let (status, _) = this.compress(&[], TDEFLFlush::Finish)?;
+            this.finished = status == TDEFLStatus::Done;
+        }
+
+        Pin::new(&mut this.inner).poll_close(cx)
+    }
+}
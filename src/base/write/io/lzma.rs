@@ -0,0 +1,75 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::{LZMA_PROPERTIES_LENGTH, LZMA_SDK_VERSION};
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::AsyncWrite;
+
+/// The header written before the LZMA properties within ZIP entries (the LZMA SDK version & properties size).
+const ZIP_HEADER: [u8; 4] = [LZMA_SDK_VERSION[0], LZMA_SDK_VERSION[1], LZMA_PROPERTIES_LENGTH as u8, 0];
+
+/// The length of the header which precedes LZMA data within `.lzma` files (the properties & uncompressed size).
+const ALONE_HEADER_LENGTH: usize = LZMA_PROPERTIES_LENGTH + 8;
+
+/// A wrapping writer which converts the output of a `.lzma` encoder into LZMA data framed as within ZIP entries.
+///
+/// The `.lzma` header's properties are preceded by the LZMA SDK version and their size, and its uncompressed size is
+/// dropped. As the size is unknown to the encoder, the data always ends with an end-of-stream marker.
+pub(crate) struct LzmaZipWriter<W> {
+    inner: W,
+    zip_header_written: usize,
+    alone_header_consumed: usize,
+}
+
+impl<W> LzmaZipWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self { inner, zip_header_written: 0, alone_header_consumed: 0 }
+    }
+
+    /// Consumes this writer and returns the inner writer.
+    pub(crate) fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> AsyncWrite for LzmaZipWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+
+        while this.zip_header_written < ZIP_HEADER.len() {
+            match ready!(Pin::new(&mut this.inner).poll_write(cx, &ZIP_HEADER[this.zip_header_written..]))? {
+                0 => return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into())),
+                written => this.zip_header_written += written,
+            }
+        }
+
+        // The properties are passed through, whilst the uncompressed size which follows them is dropped.
+        if this.alone_header_consumed < LZMA_PROPERTIES_LENGTH {
+            let len = buf.len().min(LZMA_PROPERTIES_LENGTH - this.alone_header_consumed);
+            let written = ready!(Pin::new(&mut this.inner).poll_write(cx, &buf[..len]))?;
+            this.alone_header_consumed += written;
+            return Poll::Ready(Ok(written));
+        }
+        if this.alone_header_consumed < ALONE_HEADER_LENGTH {
+            let len = buf.len().min(ALONE_HEADER_LENGTH - this.alone_header_consumed);
+            this.alone_header_consumed += len;
+            return Poll::Ready(Ok(len));
+        }
+
+        Pin::new(&mut this.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
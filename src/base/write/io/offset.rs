@@ -1,19 +1,26 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use std::io::{Error, IoSlice};
+use std::io::{Error, ErrorKind, IoSlice};
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::task::{ready, Context, Poll};
 
 use futures_lite::io::AsyncWrite;
 use pin_project::pin_project;
 
 /// A wrapper around an [`AsyncWrite`] implementation which tracks the current byte offset.
+///
+/// This wrapper may optionally buffer writes (see [`AsyncOffsetWriter::set_buffer_capacity()`]) in order to reduce the
+/// number of writes made to the inner writer. The tracked offset always reflects the logical number of bytes written to
+/// this wrapper, regardless of whether or not they've yet reached the inner writer.
 #[pin_project(project = OffsetWriterProj)]
 pub struct AsyncOffsetWriter<W> {
     #[pin]
     inner: W,
     offset: u64,
+    buffer: Vec<u8>,
+    capacity: usize,
+    flushed: usize,
 }
 
 impl<W> AsyncOffsetWriter<W>
@@ -22,7 +29,7 @@ where
 {
     /// Constructs a new wrapper from an inner [`AsyncWrite`] writer.
     pub fn new(inner: W) -> Self {
-        Self { inner, offset: 0 }
+        Self { inner, offset: 0, buffer: Vec::new(), capacity: 0, flushed: 0 }
     }
 
     /// Returns the current byte offset.
@@ -30,21 +37,67 @@ where
         self.offset
     }
 
+    /// Sets the capacity of the internal write buffer, with a capacity of zero disabling buffering.
+    ///
+    /// Any data which is already buffered is retained and will be written out on the next flush.
+    pub fn set_buffer_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.buffer.reserve(capacity.saturating_sub(self.buffer.len()));
+    }
+
     /// Consumes this wrapper and returns the inner [`AsyncWrite`] writer.
+    ///
+    /// Any buffered data which hasn't been flushed will be lost.
     pub fn into_inner(self) -> W {
         self.inner
     }
 
+    /// Returns a mutable reference to the inner [`AsyncWrite`] writer.
+    ///
+    /// Writing directly to the inner writer whilst data is buffered will result in that data being written out of order.
     pub fn inner_mut(&mut self) -> &mut W {
         &mut self.inner
     }
+
+    /// Writes all buffered data to the inner writer, without flushing the inner writer itself.
+    fn poll_flush_buffer(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        let mut this = self.project();
+
+        while *this.flushed < this.buffer.len() {
+            match ready!(this.inner.as_mut().poll_write(cx, &this.buffer[*this.flushed..])) {
+                Ok(0) => return Poll::Ready(Err(Error::new(ErrorKind::WriteZero, "failed to write buffered data"))),
+                Ok(written) => *this.flushed += written,
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+
+        this.buffer.clear();
+        *this.flushed = 0;
+
+        Poll::Ready(Ok(()))
+    }
 }
 
 impl<W> AsyncWrite for AsyncOffsetWriter<W>
 where
     W: AsyncWrite + Unpin,
 {
-    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        if self.capacity != 0 {
+            if self.buffer.len() + buf.len() > self.capacity {
+                ready!(self.as_mut().poll_flush_buffer(cx))?;
+            }
+
+            // Data which wouldn't fit within an empty buffer is written straight through to the inner writer.
+            if buf.len() < self.capacity {
+                let this = self.project();
+                this.buffer.extend_from_slice(buf);
+                *this.offset += buf.len() as u64;
+
+                return Poll::Ready(Ok(buf.len()));
+            }
+        }
+
         let this = self.project();
         let poll = this.inner.poll_write(cx, buf);
 
@@ -55,11 +108,13 @@ where
         poll
     }
 
-    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        ready!(self.as_mut().poll_flush_buffer(cx))?;
         self.project().inner.poll_flush(cx)
     }
 
-    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        ready!(self.as_mut().poll_flush_buffer(cx))?;
         self.project().inner.poll_close(cx)
     }
 
@@ -68,6 +123,18 @@ where
         cx: &mut Context<'_>,
         bufs: &[IoSlice<'_>],
     ) -> Poll<Result<usize, Error>> {
-        self.project().inner.poll_write_vectored(cx, bufs)
+        if self.capacity != 0 {
+            let buf = bufs.iter().find(|buf| !buf.is_empty()).map_or(&[][..], |buf| &**buf);
+            return self.poll_write(cx, buf);
+        }
+
+        let this = self.project();
+        let poll = this.inner.poll_write_vectored(cx, bufs);
+
+        if let Poll::Ready(Ok(inner)) = &poll {
+            *this.offset += *inner as u64;
+        }
+
+        poll
     }
 }
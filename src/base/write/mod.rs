@@ -49,10 +49,13 @@
 //! # }
 //! ```
 
+pub(crate) mod builder;
 pub(crate) mod compressed_writer;
 pub(crate) mod entry_stream;
 pub(crate) mod entry_whole;
+pub(crate) mod guard;
 pub(crate) mod io;
+pub(crate) mod patch;
 
 #[cfg(any(
     feature = "deflate",
@@ -64,50 +67,197 @@ pub(crate) mod io;
 ))]
 pub use entry_whole::compress;
 
+pub use builder::ZipFileWriterBuilder;
 pub use entry_stream::EntryStreamWriter;
 pub use entry_whole::crc32;
+pub use patch::ZipArchivePatcher;
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
+use crate::base::read::seek::ZipFileReader as SeekZipFileReader;
+use crate::crc::Crc32;
+use crate::date::ZipDateTime;
 use crate::entry::ZipEntry;
-use crate::error::Result;
+use crate::error::{Result, ZipError};
 use crate::spec::extra_field::ExtraFieldAsBytes;
 use crate::spec::header::{
-    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, InfoZipUnicodeCommentExtraField,
-    InfoZipUnicodePathExtraField, Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
+    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, HeaderId, InfoZipUnicodeCommentExtraField,
+    InfoZipUnicodePathExtraField, UnknownExtraField, Zip64EndOfCentralDirectoryLocator,
+    Zip64EndOfCentralDirectoryRecord,
 };
+use crate::string::StringEncoding;
 
 #[cfg(feature = "tokio")]
 use crate::tokio::write::ZipFileWriter as TokioZipFileWriter;
 
 use entry_whole::EntryWholeWriter;
+use guard::CloseGuard;
 use io::offset::AsyncOffsetWriter;
 
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
-use futures_lite::io::{AsyncWrite, AsyncWriteExt};
+use crate::spec::consts::{LFH_LENGTH, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH};
+use crate::spec::Compression;
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite, AsyncWriteExt};
+use futures_lite::stream::{Stream, StreamExt};
+use std::borrow::Cow;
+use std::collections::HashSet;
 
 pub(crate) struct CentralDirectoryEntry {
     pub header: CentralDirectoryRecord,
     pub entry: ZipEntry,
+    /// Raw extra field bytes written in place of the entry's extra fields, if any.
+    pub raw_extra: Option<Vec<u8>>,
+}
+
+/// The number of bytes a single entry occupies within an archive, as returned once it has been written.
+///
+/// This excludes the entry's central directory record, which is only written when the archive is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrittenEntry {
+    /// The size of the entry's (compressed) data.
+    pub compressed_size: u64,
+    /// The total number of bytes emitted for the entry, including its local file header, data, and any data
+    /// descriptor.
+    pub bytes_written: u64,
+}
+
+/// The layout of a closed archive, as returned by [`ZipFileWriter::close_with_summary()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CloseSummary {
+    /// The offset at which the central directory starts.
+    pub cd_offset: u64,
+    /// The size of the central directory, excluding any end of central directory records.
+    pub cd_size: u64,
+    /// The total number of bytes emitted for the archive.
+    pub total_size: u64,
+    /// The number of entries within the central directory.
+    pub entry_count: u64,
+}
+
+/// The order in which records are written to the central directory, as set via
+/// [`ZipFileWriterBuilder::sort_central_directory()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Order by the filename bytes stored within each record.
+    Name,
+    /// Order by the filename bytes stored within each record, ignoring the case of ASCII characters.
+    NameIgnoringAsciiCase,
+}
+
+/// The number of entries serialised by each task when building the central directory in parallel.
+#[cfg(feature = "rayon")]
+const CD_CHUNK_SIZE: usize = 1024;
+
+/// Serialises the central directory records of the provided entries, in order.
+pub(crate) fn central_directory(entries: &[CentralDirectoryEntry]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+
+    for entry in entries {
+        let filename_basic = entry.entry.filename().alternative().unwrap_or_else(|| entry.entry.filename().as_bytes());
+        let comment_basic = entry.entry.comment().alternative().unwrap_or_else(|| entry.entry.comment().as_bytes());
+
+        buffer.extend_from_slice(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes());
+        buffer.extend_from_slice(&entry.header.as_slice());
+        buffer.extend_from_slice(filename_basic);
+        match &entry.raw_extra {
+            Some(raw_extra) => buffer.extend_from_slice(raw_extra),
+            None => buffer.extend_from_slice(&entry.entry.extra_fields().as_bytes()),
+        }
+        buffer.extend_from_slice(comment_basic);
+    }
+
+    buffer
+}
+
+/// Serialises the central directory records of the provided entries in parallel chunks, which are then concatenated
+/// in order. The output is identical to that of [`central_directory()`].
+#[cfg(feature = "rayon")]
+pub(crate) fn central_directory_parallel(entries: &[CentralDirectoryEntry]) -> Vec<u8> {
+    use rayon::prelude::*;
+
+    entries.par_chunks(CD_CHUNK_SIZE).map(central_directory).collect::<Vec<_>>().concat()
+}
+
+/// Returns whether the start of precompressed data is consistent with the provided compression method.
+///
+/// Only Stored and Deflate (and Deflate64, which shares its block structure) data can be checked; data compressed with
+/// any other method is assumed to match.
+pub(crate) fn precompressed_matches_method(compression: Compression, data: &[u8], uncompressed_size: u64) -> bool {
+    let deflate = match compression {
+        Compression::Stored => return data.len() as u64 == uncompressed_size,
+        #[cfg(feature = "deflate")]
+        Compression::Deflate => true,
+        #[cfg(feature = "deflate64")]
+        Compression::Deflate64 => true,
+        _ => false,
+    };
+
+    if !deflate {
+        return true;
+    }
+
+    // The first block's header occupies the lowest three bits of the first byte: BFINAL followed by the two-bit BTYPE.
+    match data.first().map(|byte| (byte >> 1) & 0b11) {
+        None | Some(0b11) => false,
+        // A stored block's LEN & NLEN follow from the next byte boundary.
+        Some(0b00) if data.len() >= 5 => {
+            u16::from_le_bytes([data[1], data[2]]) == !u16::from_le_bytes([data[3], data[4]])
+        }
+        Some(0b00) => false,
+        Some(_) => true,
+    }
+}
+
+/// Records a successfully written entry's filename, if duplicate names are being rejected.
+pub(crate) fn register_name(names: &mut Option<HashSet<Vec<u8>>>, entry: &ZipEntry) {
+    if let Some(names) = names.as_mut() {
+        names.insert(entry.filename().as_bytes().to_vec());
+    }
 }
 
 /// A ZIP file writer which acts over AsyncWrite implementers.
 ///
+/// # Configuration
+/// [`ZipFileWriter::new()`] constructs a writer with the default configuration. Any other options (eg.
+/// [`ZipFileWriterBuilder::buffered()`], [`ZipFileWriterBuilder::reject_duplicates()`], or
+/// [`ZipFileWriterBuilder::clock()`]) are set via a [`ZipFileWriterBuilder`] before any entries are written:
+/// ```
+/// # use async_zip::base::write::ZipFileWriterBuilder;
+/// let writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).buffered(64 * 1024).reject_duplicates(true).build();
+/// ```
+///
 /// # Note
 /// - [`ZipFileWriter::close()`] must be called before a stream writer goes out of scope.
 pub struct ZipFileWriter<W> {
     pub(crate) writer: AsyncOffsetWriter<W>,
     pub(crate) cd_entries: Vec<CentralDirectoryEntry>,
     /// If true, will error if a Zip64 struct must be written.
-    force_no_zip64: bool,
+    pub(crate) force_no_zip64: bool,
     /// Whether to write Zip64 end of directory structs.
     pub(crate) is_zip64: bool,
-    comment_opt: Option<String>,
+    pub(crate) comment_opt: Option<Vec<u8>>,
+    /// A buffer reused for the compressed output of whole entries, if enabled.
+    pub(crate) scratch: Option<Vec<u8>>,
+    /// The filenames of all entries written so far, if duplicate names are being rejected.
+    pub(crate) names: Option<HashSet<Vec<u8>>>,
+    /// The source of last modification dates for entries which don't set their own, if any.
+    pub(crate) clock: Option<Box<dyn Fn() -> ZipDateTime + Send + Sync>>,
+    /// The compression method of entries which inherit it from this writer.
+    pub(crate) default_compression: Compression,
+    /// The constructor of hashers used for streamed entries, if not the default.
+    pub(crate) hasher: Option<Box<dyn Fn() -> Box<dyn Crc32> + Send + Sync>>,
+    /// The specification version written as the lower byte of each entry's version made by.
+    pub(crate) spec_version: u8,
+    /// The order in which central directory records are written, if not the order entries were written in.
+    pub(crate) cd_order: Option<SortKey>,
+    /// Whether to flush the inner writer after each whole entry is written.
+    pub(crate) flush_after_each_entry: bool,
+    pub(crate) guard: CloseGuard,
 }
 
 impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     /// Construct a new ZIP file writer from a mutable reference to a writer.
+    #[must_use]
     pub fn new(writer: W) -> Self {
         Self {
             writer: AsyncOffsetWriter::new(writer),
@@ -115,11 +265,22 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             comment_opt: None,
             is_zip64: false,
             force_no_zip64: false,
+            scratch: None,
+            names: None,
+            clock: None,
+            default_compression: Compression::Stored,
+            hasher: None,
+            spec_version: crate::spec::version::SPEC_VERSION_MADE_BY as u8,
+            cd_order: None,
+            flush_after_each_entry: false,
+            guard: CloseGuard::new("ZipFileWriter", false),
         }
     }
 
     /// Force the ZIP writer to operate in non-ZIP64 mode.
     /// If any files would need ZIP64, an error will be raised.
+    ///
+    /// This is equivalent to [`ZipFileWriterBuilder::force_no_zip64()`].
     pub fn force_no_zip64(mut self) -> Self {
         self.force_no_zip64 = true;
         self
@@ -127,22 +288,250 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
 
     /// Force the ZIP writer to emit Zip64 structs at the end of the archive.
     /// Zip64 extended fields will only be written if needed.
+    ///
+    /// This is equivalent to [`ZipFileWriterBuilder::force_zip64()`].
     pub fn force_zip64(mut self) -> Self {
         self.is_zip64 = true;
         self
     }
 
+    /// Returns a new hasher from the constructor, if one is set, or the default hasher otherwise.
+    pub(crate) fn new_hasher(&self) -> Box<dyn Crc32> {
+        match &self.hasher {
+            Some(hasher) => hasher(),
+            None => Box::new(crc32fast::Hasher::new()),
+        }
+    }
+
+    /// Sets the entry's compression method to this writer's default, if the entry inherits it.
+    pub(crate) fn inherit_compression(&self, entry: &mut ZipEntry) {
+        if entry.inherit_compression {
+            entry.compression = self.default_compression;
+        }
+    }
+
+    /// Sets the entry's last modification date from the clock, if one is set and the entry doesn't set its own.
+    pub(crate) fn stamp(&self, entry: &mut ZipEntry) {
+        if let Some(clock) = &self.clock {
+            if entry.last_modification_date == ZipDateTime::default() {
+                entry.last_modification_date = clock();
+            }
+        }
+    }
+
+    /// Returns an error if duplicates are being rejected and an entry with the same filename has already been written.
+    pub(crate) fn check_name(&self, entry: &ZipEntry) -> Result<()> {
+        let Some(names) = self.names.as_ref() else {
+            return Ok(());
+        };
+
+        if names.contains(entry.filename().as_bytes()) {
+            let name = String::from_utf8_lossy(entry.filename().as_bytes()).into_owned();
+            return Err(crate::error::ZipError::DuplicateEntryName(name));
+        }
+
+        Ok(())
+    }
+
     /// Write a new ZIP entry of known size and data.
-    pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
-        EntryWholeWriter::from_raw(self, entry.into(), data).write().await
+    ///
+    /// On success, the number of bytes the entry occupies within the archive is returned.
+    pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<WrittenEntry> {
+        let written = EntryWholeWriter::from_raw(self, entry.into(), data).write().await?;
+        self.flush_if_enabled().await?;
+        Ok(written)
     }
 
     /// Write a new ZIP entry of known size and data, with the data already being compressed.
     ///
     /// The provided entry's compression method, CRC, and uncompressed size must be set. Use with `base::write::compress`
     /// and `base::write::crc32` to precompress.
-    pub async fn write_entry_whole_precompressed<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
-        EntryWholeWriter::from_precompressed(self, entry.into(), data).write().await
+    pub async fn write_entry_whole_precompressed<E: Into<ZipEntry>>(
+        &mut self,
+        entry: E,
+        data: &[u8],
+    ) -> Result<WrittenEntry> {
+        let written = EntryWholeWriter::from_precompressed(self, entry.into(), data).write().await?;
+        self.flush_if_enabled().await?;
+        Ok(written)
+    }
+
+    /// Flushes the inner writer if enabled via [`ZipFileWriterBuilder::flush_after_each_entry()`].
+    async fn flush_if_enabled(&mut self) -> Result<()> {
+        if self.flush_after_each_entry {
+            self.writer.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a new ZIP entry from data compressed by another pipeline (eg. a raw Deflate stream), with a known CRC and
+    /// uncompressed size.
+    ///
+    /// The data is written as-is without being hashed or recompressed, and its compressed size is that of the provided
+    /// data. The start of the data is checked against the entry's compression method before anything is written, so
+    /// this fails with [`ZipError::PrecompressedMethodMismatch`] if:
+    /// - Stored data's length differs from the uncompressed size.
+    /// - Deflate data is empty, starts with a block of the reserved type, or starts with a stored block whose length
+    ///   doesn't match its complement.
+    ///
+    /// Data compressed with other methods isn't checked.
+    pub async fn write_entry_precompressed<E: Into<ZipEntry>>(
+        &mut self,
+        entry: E,
+        crc: u32,
+        uncompressed_size: u64,
+        data: &[u8],
+    ) -> Result<WrittenEntry> {
+        let entry = entry.into();
+        if !precompressed_matches_method(entry.compression(), data, uncompressed_size) {
+            return Err(ZipError::PrecompressedMethodMismatch);
+        }
+
+        self.copy_entry_raw(entry, crc, uncompressed_size, data).await
+    }
+
+    /// Write a new ZIP entry from raw compressed data with a known CRC and uncompressed size.
+    ///
+    /// No hashing or (de)compression is performed, so this is suitable for copying entries verbatim from another
+    /// archive. The compressed size is that of the provided data, and the entry's compression method must match the
+    /// method the data was compressed with. Unlike [`ZipFileWriter::write_entry_precompressed()`], the data itself isn't
+    /// checked, though Stored data still fails with [`ZipError::PrecompressedMethodMismatch`] if its length differs
+    /// from the uncompressed size.
+    pub async fn copy_entry_raw<E: Into<ZipEntry>>(
+        &mut self,
+        entry: E,
+        crc: u32,
+        uncompressed_size: u64,
+        data: &[u8],
+    ) -> Result<WrittenEntry> {
+        let mut entry = entry.into();
+        if entry.compression() == Compression::Stored && data.len() as u64 != uncompressed_size {
+            return Err(ZipError::PrecompressedMethodMismatch);
+        }
+
+        entry.crc32 = crc;
+        entry.uncompressed_size = uncompressed_size;
+
+        let written = EntryWholeWriter::from_precompressed(self, entry, data).write().await?;
+        self.flush_if_enabled().await?;
+        Ok(written)
+    }
+
+    /// Write a new ZIP entry from a hand-built local file header and central directory record.
+    ///
+    /// Both headers, the filename, the extra field, and the data are written exactly as given, without any validation
+    /// or consistency checks, so this suits constructing archives which exercise edge cases of the specification. The
+    /// writer's offset is still tracked, so the record's `lh_offset` should be set to the sum of the `bytes_written` of
+    /// all previously written entries. The record is written with the same filename and extra field, and no comment.
+    #[cfg(feature = "unstable")]
+    pub async fn write_raw_entry(
+        &mut self,
+        lfh: crate::spec::header::LocalFileHeader,
+        central_header: CentralDirectoryRecord,
+        name: &[u8],
+        extra: &[u8],
+        data: &[u8],
+    ) -> Result<WrittenEntry> {
+        let start = self.writer.offset();
+
+        self.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
+        self.writer.write_all(&lfh.as_slice()).await?;
+        self.writer.write_all(name).await?;
+        self.writer.write_all(extra).await?;
+        self.writer.write_all(data).await?;
+
+        let entry = crate::ZipEntryBuilder::from_raw_name(name.to_vec(), Compression::Stored).build();
+        self.cd_entries.push(CentralDirectoryEntry { header: central_header, entry, raw_extra: Some(extra.to_vec()) });
+        if self.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
+            if self.force_no_zip64 {
+                return Err(ZipError::Zip64Needed(crate::error::Zip64ErrorCase::TooManyFiles));
+            }
+            self.is_zip64 = true;
+        }
+
+        Ok(WrittenEntry { compressed_size: data.len() as u64, bytes_written: self.writer.offset() - start })
+    }
+
+    /// Write a new ZIP entry copied from another archive, without decompressing or recompressing its data.
+    ///
+    /// The entry's metadata (including its compression method, CRC, sizes, and timestamps) is taken from the source's
+    /// central directory, so entries written with a data descriptor are copied correctly. The entry's compressed data
+    /// is buffered in memory whilst being copied.
+    ///
+    /// Encrypted entries can't yet be copied, so fail with [`ZipError::FeatureNotSupported`] before anything is written.
+    pub async fn copy_entry_from<R>(&mut self, reader: &mut SeekZipFileReader<R>, index: usize) -> Result<WrittenEntry>
+    where
+        R: AsyncBufRead + AsyncSeek + Unpin,
+    {
+        let stored_entry = reader.file().entries().get(index).ok_or(ZipError::EntryIndexOutOfBounds)?.clone();
+
+        // The encrypted flag, AES method, and AES extra field would otherwise be lost, corrupting the copied entry.
+        if stored_entry.is_encrypted() {
+            return Err(ZipError::FeatureNotSupported("copying encrypted entries"));
+        }
+        stored_entry.seek_to_data_offset(reader.inner_mut()).await?;
+
+        let mut data = Vec::with_capacity(stored_entry.compressed_size() as usize);
+        reader.inner_mut().take(stored_entry.compressed_size()).read_to_end(&mut data).await?;
+
+        if data.len() as u64 != stored_entry.compressed_size() {
+            return Err(ZipError::UnexpectedEof);
+        }
+
+        // A ZIP64 extra field is regenerated as needed for the entry's position within this archive.
+        let mut entry = stored_entry.entry;
+        entry.extra_fields.retain(|field| !matches!(field, ExtraField::Zip64ExtendedInformation(_)));
+
+        let (crc, uncompressed_size) = (entry.crc32(), entry.uncompressed_size());
+        self.copy_entry_raw(entry, crc, uncompressed_size, &data).await
+    }
+
+    /// Write a batch of new ZIP entries of known size and data, compressing them in parallel.
+    ///
+    /// Each entry's data is compressed on [`tokio`]'s blocking thread pool, with the number of entries being compressed
+    /// at any one time bounded by the available parallelism. Compressed entries are then written in the order provided,
+    /// so the output is identical to that of calling [`ZipFileWriter::write_entry_whole()`] for each entry in turn.
+    ///
+    /// Note that this requires the `tokio-parallel` feature and must be called from within a [`tokio`] runtime.
+    #[cfg(feature = "tokio-parallel")]
+    pub async fn write_entries_parallel<E: Into<ZipEntry>>(&mut self, entries: Vec<(E, Vec<u8>)>) -> Result<()> {
+        let parallelism = std::thread::available_parallelism().map(|count| count.get()).unwrap_or(1);
+        let recycle = self.scratch.is_some();
+        let mut entries = entries.into_iter();
+        let mut pending = std::collections::VecDeque::with_capacity(parallelism);
+
+        loop {
+            while pending.len() < parallelism {
+                let Some((entry, data)) = entries.next() else {
+                    break;
+                };
+
+                let mut entry: ZipEntry = entry.into();
+                self.inherit_compression(&mut entry);
+                let mut scratch = self.scratch.take();
+                pending.push_back(tokio::task::spawn_blocking(move || {
+                    let data =
+                        futures_lite::future::block_on(entry_whole::prepare(&mut entry, data.into(), scratch.as_mut()));
+                    data.map(|data| (entry, data.into_owned()))
+                }));
+            }
+
+            let Some(handle) = pending.pop_front() else {
+                break;
+            };
+
+            let (entry, data) = handle.await.unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))?;
+            EntryWholeWriter::from_precompressed(self, entry, &data).write().await?;
+            self.flush_if_enabled().await?;
+
+            // The compressed output's allocation is handed back for reuse by the next entry to be compressed.
+            if recycle {
+                self.scratch = Some(data);
+            }
+        }
+
+        Ok(())
     }
 
     /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
@@ -152,6 +541,91 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         EntryStreamWriter::from_raw(self, entry.into()).await
     }
 
+    /// Write a new ZIP entry whose data is streamed from the provided reader until it reaches EOF.
+    ///
+    /// This is equivalent to copying the reader into a writer from [`ZipFileWriter::write_entry_stream()`] and then
+    /// closing it, with the entry's CRC and sizes being computed as its data is written. Any error returned by the
+    /// reader is propagated, leaving the entry unfinished.
+    pub async fn write_entry_from_reader<E, R>(&mut self, entry: E, reader: R) -> Result<WrittenEntry>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        let mut entry_writer = self.write_entry_stream(entry).await?;
+
+        if let Err(err) = futures_lite::io::copy(reader, &mut entry_writer).await {
+            entry_writer.guard.disarm();
+            return Err(err.into());
+        }
+
+        entry_writer.close().await
+    }
+
+    /// Write a new ZIP entry whose data is the concatenation of the chunks yielded by the provided stream.
+    ///
+    /// Each chunk is written through to the entry (and compressed) before the next is polled, so only a single chunk
+    /// is buffered at a time and a slow destination applies backpressure to the stream. Chunks may be of any type which
+    /// derefs to bytes (eg. `Vec<u8>` or `bytes::Bytes`). If the stream yields an error, the entry is aborted without
+    /// being recorded within the central directory and the error is returned.
+    pub async fn write_entry_from_bytes_stream<E, S, B>(&mut self, entry: E, stream: S) -> Result<WrittenEntry>
+    where
+        E: Into<ZipEntry>,
+        S: Stream<Item = std::io::Result<B>>,
+        B: AsRef<[u8]>,
+    {
+        futures_lite::pin!(stream);
+        let mut entry_writer = self.write_entry_stream(entry).await?;
+
+        while let Some(chunk) = stream.next().await {
+            let result = match chunk {
+                Ok(chunk) => entry_writer.write_all(chunk.as_ref()).await,
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = result {
+                entry_writer.guard.disarm();
+                return Err(err.into());
+            }
+        }
+
+        entry_writer.close().await
+    }
+
+    /// Write every entry from the provided stream, each with data read from its paired reader until EOF, and then close
+    /// the ZIP file.
+    ///
+    /// Entries are written in turn as per [`ZipFileWriter::write_entry_from_reader()`], which suits converting other
+    /// streamed formats (eg. a tar archive) on the fly. If any entry fails to be written, its error is returned and the
+    /// partially-written archive is abandoned without being closed.
+    pub async fn write_entries_from_stream<S, E, R>(mut self, entries: S) -> Result<W>
+    where
+        S: Stream<Item = (E, R)>,
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        futures_lite::pin!(entries);
+
+        while let Some((entry, reader)) = entries.next().await {
+            let result = match self.write_entry_stream(entry).await {
+                Ok(mut entry_writer) => match futures_lite::io::copy(reader, &mut entry_writer).await {
+                    Ok(_) => entry_writer.close().await.map(|_| ()),
+                    Err(err) => {
+                        entry_writer.guard.disarm();
+                        Err(err.into())
+                    }
+                },
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = result {
+                self.guard.disarm();
+                return Err(err);
+            }
+        }
+
+        self.close().await
+    }
+
     /// Write an entry of unknown size and data via streaming (ie. using a data descriptor), with the data already being compressed.
     ///
     /// The provided entry's compression method, CRC, and uncompressed size must be set. Use with `base::write::compress`
@@ -168,39 +642,76 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
 
     /// Set the ZIP file comment.
     pub fn comment(&mut self, comment: String) {
+        self.comment_opt = Some(comment.into_bytes());
+    }
+
+    /// Set the ZIP file comment from raw bytes.
+    ///
+    /// Unlike [`ZipFileWriter::comment()`], the comment isn't required to be valid UTF-8 and so can be used to store
+    /// arbitrary binary data. An error will be returned if the comment exceeds the maximum length of 65535 bytes.
+    pub fn comment_bytes(&mut self, comment: Vec<u8>) -> Result<()> {
+        if comment.len() > u16::MAX as usize {
+            return Err(crate::error::ZipError::CommentTooLarge);
+        }
+
         self.comment_opt = Some(comment);
+        Ok(())
     }
 
     /// Returns a mutable reference to the inner writer.
     ///
-    /// Care should be taken when using this inner writer as doing so may invalidate internal state of this writer.
+    /// Care should be taken when using this inner writer as doing so may invalidate internal state of this writer. If
+    /// writes are being buffered, data written to this inner writer may be interleaved with previously buffered data.
     pub fn inner_mut(&mut self) -> &mut W {
         self.writer.inner_mut()
     }
 
+    /// Flushes any buffered data to the inner writer, and then flushes the inner writer itself.
+    pub async fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush().await?)
+    }
+
     /// Consumes this ZIP writer and completes all closing tasks.
     ///
     /// This includes:
     /// - Writing all central directory headers.
     /// - Writing the end of central directory header.
     /// - Writing the file comment.
+    /// - Flushing any buffered data to the inner writer.
     ///
-    /// Failure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<W> {
-        let cd_offset = self.writer.offset();
+    /// Failure to call this function before going out of scope would result in a corrupted ZIP file. This is flagged on
+    /// drop by logging a warning, or by panicking if enabled via [`ZipFileWriterBuilder::panic_if_unclosed()`].
+    pub async fn close(self) -> Result<W> {
+        Ok(self.close_with_summary().await?.0)
+    }
 
-        for entry in &self.cd_entries {
-            let filename_basic =
-                entry.entry.filename().alternative().unwrap_or_else(|| entry.entry.filename().as_bytes());
-            let comment_basic = entry.entry.comment().alternative().unwrap_or_else(|| entry.entry.comment().as_bytes());
+    /// Consumes this ZIP writer and completes all closing tasks, additionally returning a summary of the archive's
+    /// layout.
+    ///
+    /// See [`ZipFileWriter::close()`]. The summary includes the offset and size of the central directory, which would
+    /// otherwise require the archive to be re-parsed (eg. when splitting or signing it).
+    pub async fn close_with_summary(mut self) -> Result<(W, CloseSummary)> {
+        self.guard.disarm();
+        let cd_offset = self.writer.offset();
 
-            self.writer.write_all(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes()).await?;
-            self.writer.write_all(&entry.header.as_slice()).await?;
-            self.writer.write_all(filename_basic).await?;
-            self.writer.write_all(&entry.entry.extra_fields().as_bytes()).await?;
-            self.writer.write_all(comment_basic).await?;
+        match self.cd_order {
+            Some(SortKey::Name) => {
+                self.cd_entries.sort_by(|a, b| a.entry.raw_name_bytes().cmp(b.entry.raw_name_bytes()));
+            }
+            Some(SortKey::NameIgnoringAsciiCase) => self.cd_entries.sort_by(|a, b| {
+                let a = a.entry.raw_name_bytes().iter().map(u8::to_ascii_lowercase);
+                a.cmp(b.entry.raw_name_bytes().iter().map(u8::to_ascii_lowercase))
+            }),
+            None => (),
         }
 
+        #[cfg(feature = "rayon")]
+        let central_directory = central_directory_parallel(&self.cd_entries);
+        #[cfg(not(feature = "rayon"))]
+        let central_directory = central_directory(&self.cd_entries);
+
+        self.writer.write_all(&central_directory).await?;
+
         let central_directory_size = self.writer.offset() - cd_offset;
         let central_directory_size_u32 = if central_directory_size > NON_ZIP64_MAX_SIZE as u64 {
             NON_ZIP64_MAX_SIZE
@@ -230,7 +741,7 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
 
             let eocdr = Zip64EndOfCentralDirectoryRecord {
                 size_of_zip64_end_of_cd_record: 44,
-                version_made_by: crate::spec::version::as_made_by(),
+                version_made_by: self.spec_version.into(),
                 version_needed_to_extract: 46,
                 disk_number: 0,
                 disk_number_start_of_cd: 0,
@@ -258,16 +769,28 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             num_of_entries: num_entries_in_directory_u16,
             size_cent_dir: central_directory_size_u32,
             cent_dir_offset: cd_offset_u32,
-            file_comm_length: self.comment_opt.as_ref().map(|v| v.len() as u16).unwrap_or_default(),
+            file_comm_length: match &self.comment_opt {
+                Some(comment) => comment.len().try_into().map_err(|_| crate::error::ZipError::CommentTooLarge)?,
+                None => 0,
+            },
         };
 
         self.writer.write_all(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes()).await?;
         self.writer.write_all(&header.as_slice()).await?;
         if let Some(comment) = self.comment_opt {
-            self.writer.write_all(comment.as_bytes()).await?;
+            self.writer.write_all(&comment).await?;
         }
 
-        Ok(self.writer.into_inner())
+        self.writer.flush().await?;
+
+        let summary = CloseSummary {
+            cd_offset,
+            cd_size: central_directory_size,
+            total_size: self.writer.offset(),
+            entry_count: num_entries_in_directory,
+        };
+
+        Ok((self.writer.into_inner(), summary))
     }
 }
 
@@ -277,15 +800,90 @@ where
     W: tokio::io::AsyncWrite + Unpin,
 {
     /// Construct a new ZIP file writer from a mutable reference to a writer.
+    #[must_use]
     pub fn with_tokio(writer: W) -> TokioZipFileWriter<W> {
-        Self {
-            writer: AsyncOffsetWriter::new(writer.compat_write()),
-            cd_entries: Vec::new(),
-            comment_opt: None,
-            is_zip64: false,
-            force_no_zip64: false,
+        ZipFileWriter::new(writer.compat_write())
+    }
+}
+
+/// Returns the extra fields to be written within an entry's local file header, which starts at the provided offset.
+///
+/// These match the central directory record's fields unless overridden, in which case any ZIP64 extended information
+/// field generated by the writer is carried over. If the entry has an alignment set, a padding field is appended so
+/// that its data starts at a multiple of it.
+pub(crate) fn local_extra_fields(entry: &ZipEntry, offset: u64) -> Cow<'_, [ExtraField]> {
+    let mut fields = match &entry.local_extra_fields {
+        None => Cow::Borrowed(entry.extra_fields.as_slice()),
+        Some(local) => {
+            let zip64 =
+                entry.extra_fields.iter().filter(|field| matches!(field, ExtraField::Zip64ExtendedInformation(_)));
+            Cow::Owned(local.iter().chain(zip64).cloned().collect())
+        }
+    };
+
+    let alignment = entry.alignment as u64;
+    if alignment <= 1 {
+        return fields;
+    }
+
+    let filename_length = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes()).len();
+    let data_offset = offset + (SIGNATURE_LENGTH + LFH_LENGTH + filename_length + fields.as_ref().count_bytes()) as u64;
+
+    // The padding is held within an Android alignment field (as written by zipalign), which needs at least 6 bytes.
+    let mut padding = (alignment - data_offset % alignment) % alignment;
+    if padding == 0 {
+        return fields;
+    }
+    while padding < 6 {
+        padding += alignment;
+    }
+
+    let mut content = entry.alignment.to_le_bytes().to_vec();
+    content.resize(padding as usize - 4, 0);
+    let data_size = content.len() as u16;
+
+    let header_id = HeaderId::ANDROID_ALIGNMENT_EXTRA_FIELD;
+    fields.to_mut().push(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content }));
+    fields
+}
+
+/// Returns whether the UTF-8 flag (general purpose bit 11) should be set within both of the entry's headers.
+///
+/// The flag covers both the filename and the comment, so it's only set when both can be stored directly as UTF-8 (and
+/// so is always set when either contains non-ASCII characters). Otherwise, each UTF-8 string is instead stored within
+/// an Info-ZIP Unicode extra field alongside its basic (alternative) bytes.
+pub(crate) fn unicode_flag(entry: &mut ZipEntry) -> bool {
+    let utf8_without_alternative =
+        entry.filename().is_utf8_without_alternative() && entry.comment().is_utf8_without_alternative();
+
+    if !utf8_without_alternative {
+        if matches!(entry.filename().encoding(), StringEncoding::Utf8) {
+            let u_file_name = entry.filename().as_bytes().to_vec();
+            if !u_file_name.is_empty() {
+                let basic_crc32 =
+                    crc32fast::hash(entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes()));
+                let upath_field = get_or_put_info_zip_unicode_path_extra_field_mut(entry.extra_fields.as_mut());
+                if let InfoZipUnicodePathExtraField::V1 { crc32, unicode } = upath_field {
+                    *crc32 = basic_crc32;
+                    *unicode = u_file_name;
+                }
+            }
+        }
+        if matches!(entry.comment().encoding(), StringEncoding::Utf8) {
+            let u_comment = entry.comment().as_bytes().to_vec();
+            if !u_comment.is_empty() {
+                let basic_crc32 =
+                    crc32fast::hash(entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes()));
+                let ucom_field = get_or_put_info_zip_unicode_comment_extra_field_mut(entry.extra_fields.as_mut());
+                if let InfoZipUnicodeCommentExtraField::V1 { crc32, unicode } = ucom_field {
+                    *crc32 = basic_crc32;
+                    *unicode = u_comment;
+                }
+            }
         }
     }
+
+    utf8_without_alternative
 }
 
 pub(crate) fn get_or_put_info_zip_unicode_path_extra_field_mut(
@@ -0,0 +1,106 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports patching the fixed-size metadata of an existing ZIP file in place.
+
+use crate::date::ZipDateTime;
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::spec::consts::{CDH_SIGNATURE, SIGNATURE_LENGTH};
+use crate::spec::header::CentralDirectoryRecord;
+
+use futures_lite::io::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, SeekFrom};
+
+/// The offset of the last modification time within both local file headers and central directory records, relative
+/// to the end of their signatures. The date immediately follows.
+const LFH_MOD_TIME_OFFSET: u64 = 6;
+const CDH_MOD_TIME_OFFSET: u64 = 8;
+const CDH_EXTERNAL_ATTRIBUTE_OFFSET: u64 = 34;
+const CDH_LENGTH: u64 = 42;
+
+/// A patcher which overwrites the fixed-size metadata of entries within an existing ZIP file, in place.
+///
+/// Only fields whose size can't change are supported, so the rest of the file is preserved byte-for-byte without
+/// being rewritten. An entry's comment can only be replaced by one of the same length.
+pub struct ZipArchivePatcher<RW> {
+    inner: RW,
+    file: ZipFile,
+    /// The offset of each entry's central directory record.
+    records: Vec<u64>,
+}
+
+impl<RW: AsyncRead + AsyncWrite + AsyncSeek + Unpin> ZipArchivePatcher<RW> {
+    /// Constructs a new patcher from a readable, writable, and seekable ZIP file.
+    pub async fn new(mut inner: RW) -> Result<ZipArchivePatcher<RW>> {
+        let (file, mut offset) = crate::base::read::file_with_directory_offset(&mut inner, &Default::default()).await?;
+        let mut records = Vec::with_capacity(file.entries.len());
+
+        inner.seek(SeekFrom::Start(offset)).await?;
+        for _ in 0..file.entries.len() {
+            records.push(offset);
+
+            crate::utils::assert_signature(&mut inner, CDH_SIGNATURE).await?;
+            let header = CentralDirectoryRecord::from_reader(&mut inner).await?;
+            let trailing = header.file_name_length as u64 + header.extra_field_length as u64;
+
+            offset += SIGNATURE_LENGTH as u64 + CDH_LENGTH + trailing + header.file_comment_length as u64;
+            inner.seek(SeekFrom::Start(offset)).await?;
+        }
+
+        Ok(ZipArchivePatcher { inner, file, records })
+    }
+
+    /// Returns the ZIP file's information, as it was prior to any patches.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Overwrites an entry's last modification date within both its local file header and central directory record.
+    pub async fn set_last_modification_date(&mut self, index: usize, date: ZipDateTime) -> Result<()> {
+        let record = *self.records.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let header = self.file.entries[index].header_offset();
+
+        let mut bytes = date.time.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&date.date.to_le_bytes());
+
+        self.write_at(header + SIGNATURE_LENGTH as u64 + LFH_MOD_TIME_OFFSET, &bytes).await?;
+        self.write_at(record + SIGNATURE_LENGTH as u64 + CDH_MOD_TIME_OFFSET, &bytes).await
+    }
+
+    /// Overwrites an entry's external file attribute within its central directory record.
+    pub async fn set_external_file_attribute(&mut self, index: usize, attribute: u32) -> Result<()> {
+        let record = *self.records.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        self.write_at(record + SIGNATURE_LENGTH as u64 + CDH_EXTERNAL_ATTRIBUTE_OFFSET, &attribute.to_le_bytes()).await
+    }
+
+    /// Overwrites an entry's comment within its central directory record.
+    ///
+    /// The new comment must be the same length as the existing one, otherwise [`ZipError::FeatureNotSupported`] is
+    /// returned. Note that a comment held within an Info-ZIP Unicode Comment extra field isn't updated.
+    pub async fn set_comment(&mut self, index: usize, comment: &[u8]) -> Result<()> {
+        let record = *self.records.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        self.inner.seek(SeekFrom::Start(record)).await?;
+        crate::utils::assert_signature(&mut self.inner, CDH_SIGNATURE).await?;
+        let header = CentralDirectoryRecord::from_reader(&mut self.inner).await?;
+
+        if comment.len() != header.file_comment_length as usize {
+            return Err(ZipError::FeatureNotSupported("changing the length of an entry's comment"));
+        }
+
+        let trailing = header.file_name_length as u64 + header.extra_field_length as u64;
+        self.write_at(record + SIGNATURE_LENGTH as u64 + CDH_LENGTH + trailing, comment).await
+    }
+
+    /// Flushes all patches and returns the inner ZIP file, consuming self.
+    pub async fn close(mut self) -> Result<RW> {
+        self.inner.flush().await?;
+        Ok(self.inner)
+    }
+
+    async fn write_at(&mut self, offset: u64, bytes: &[u8]) -> Result<()> {
+        self.inner.seek(SeekFrom::Start(offset)).await?;
+        self.inner.write_all(bytes).await?;
+        Ok(())
+    }
+}
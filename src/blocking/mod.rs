@@ -0,0 +1,130 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A blocking facade over the [`base`] implementation for synchronous callers.
+//!
+//! # Usage
+//! The types within this module accept [`std::io`] readers and writers, and drive the [`base`] implementation to
+//! completion on the calling thread. No async runtime is required; each operation is polled via
+//! [`futures_lite::future::block_on()`] over the provided IO type, which is assumed to block as any [`std::io`] type
+//! would.
+//!
+//! These types shouldn't be used from within an async context, as they'll block the executor's thread.
+//!
+//! ### Example
+//! ```
+//! # use async_zip::blocking::{ZipFileReader, ZipFileWriter};
+//! # use async_zip::{Compression, ZipEntryBuilder};
+//! # use async_zip::error::Result;
+//! # use std::io::Cursor;
+//! #
+//! # fn run() -> Result<()> {
+//! let mut writer = ZipFileWriter::new(Vec::new());
+//! writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"bar")?;
+//! let data = writer.close()?;
+//!
+//! let mut reader = ZipFileReader::new(Cursor::new(data))?;
+//! assert_eq!(reader.entries()[0].filename().as_str()?, "foo.txt");
+//! assert_eq!(reader.reader_with_entry(0)?.read_to_vec()?, b"bar");
+//! # Ok(())
+//! # }
+//! # run().unwrap();
+//! ```
+
+#[cfg(doc)]
+use crate::base;
+
+use crate::base::read::{seek, WithEntry};
+use crate::base::write::WrittenEntry;
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::Result;
+use crate::file::ZipFile;
+
+use futures_lite::future::block_on;
+use futures_lite::io::{AssertAsync, AsyncReadExt, BufReader};
+
+use std::io::{Read, Seek, Write};
+
+/// A blocking ZIP file writer which acts over a [`Write`] implementer.
+///
+/// See [`base::write::ZipFileWriter`] for the underlying implementation.
+pub struct ZipFileWriter<W> {
+    inner: crate::base::write::ZipFileWriter<AssertAsync<W>>,
+}
+
+impl<W: Write + Unpin> ZipFileWriter<W> {
+    /// Construct a new ZIP file writer from a mutable reference to a writer.
+    pub fn new(writer: W) -> Self {
+        Self { inner: crate::base::write::ZipFileWriter::new(AssertAsync::new(writer)) }
+    }
+
+    /// Write a new ZIP entry of known size and data.
+    pub fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<WrittenEntry> {
+        block_on(self.inner.write_entry_whole(entry, data))
+    }
+
+    /// Close the ZIP file by writing all central directory headers.
+    pub fn close(self) -> Result<W> {
+        Ok(block_on(self.inner.close())?.into_inner())
+    }
+}
+
+/// A blocking ZIP file reader which acts over a [`Read`] + [`Seek`] implementer.
+///
+/// See [`base::read::seek::ZipFileReader`] for the underlying implementation.
+pub struct ZipFileReader<R> {
+    inner: seek::ZipFileReader<BufReader<AssertAsync<R>>>,
+}
+
+impl<R: Read + Seek + Unpin> ZipFileReader<R> {
+    /// Constructs a new ZIP reader from a seekable source.
+    pub fn new(reader: R) -> Result<Self> {
+        let inner = block_on(seek::ZipFileReader::new(BufReader::new(AssertAsync::new(reader))))?;
+        Ok(Self { inner })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        self.inner.file()
+    }
+
+    /// Returns this ZIP file's entries.
+    pub fn entries(&self) -> &[StoredZipEntry] {
+        self.inner.file().entries()
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub fn reader_with_entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R>> {
+        Ok(ZipEntryReader { inner: block_on(self.inner.reader_with_entry(index))? })
+    }
+
+    /// Returns the inner reader, consuming self.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner().into_inner().into_inner()
+    }
+}
+
+/// A blocking ZIP entry reader which implements [`Read`].
+///
+/// See [`base::read::ZipEntryReader`] for the underlying implementation.
+pub struct ZipEntryReader<'a, R> {
+    inner: crate::base::read::ZipEntryReader<'a, BufReader<AssertAsync<R>>, WithEntry<'a>>,
+}
+
+impl<R: Read + Seek + Unpin> ZipEntryReader<'_, R> {
+    /// Returns an immutable reference to the associated entry data.
+    pub fn entry(&self) -> &ZipEntry {
+        self.inner.entry()
+    }
+
+    /// Reads all of the entry's data into a new vector, checking the CRC32 value once complete.
+    pub fn read_to_vec(&mut self) -> Result<Vec<u8>> {
+        block_on(self.inner.read_to_vec())
+    }
+}
+
+impl<R: Read + Seek + Unpin> Read for ZipEntryReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        block_on(self.inner.read(buf))
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A pluggable CRC32 hasher used when streaming entries and when checking read data.
+
+/// A CRC32 (IEEE) hasher, allowing an alternative implementation (eg. one using platform intrinsics) to be supplied.
+///
+/// This is implemented for [`crc32fast::Hasher`], which is used by default. See
+/// [`ZipFileWriterBuilder::crc32_hasher()`] and [`ZipEntryReader::with_crc32_hasher()`].
+///
+/// [`ZipFileWriterBuilder::crc32_hasher()`]: crate::base::write::ZipFileWriterBuilder::crc32_hasher
+/// [`ZipEntryReader::with_crc32_hasher()`]: crate::base::read::ZipEntryReader::with_crc32_hasher
+pub trait Crc32: Send + Sync {
+    /// Updates the checksum with the provided data.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the checksum of all data provided since construction or the last reset.
+    fn sum(&self) -> u32;
+
+    /// Resets the hasher to its initial state.
+    fn reset(&mut self);
+}
+
+impl Crc32 for crc32fast::Hasher {
+    fn update(&mut self, data: &[u8]) {
+        crc32fast::Hasher::update(self, data);
+    }
+
+    fn sum(&self) -> u32 {
+        self.clone().finalize()
+    }
+
+    fn reset(&mut self) {
+        crc32fast::Hasher::reset(self);
+    }
+}
@@ -74,19 +74,55 @@ impl From<ZipDateTimeBuilder> for ZipDateTime {
     }
 }
 
+/// Converts chrono's [`DateTime`] representation into its raw MS-DOS date & time fields.
+///
+/// The returned tuple is ordered as `(mod_date, mod_time)`, matching the layout used within ZIP headers.
 #[cfg(feature = "chrono")]
-impl From<&DateTime<Utc>> for ZipDateTime {
-    fn from(value: &DateTime<Utc>) -> Self {
-        let mut builder = ZipDateTimeBuilder::new();
+pub(crate) fn chrono_to_zip_time(dt: &DateTime<Utc>) -> (u16, u16) {
+    let mut builder = ZipDateTimeBuilder::new();
+
+    builder = builder.year(dt.date_naive().year());
+    builder = builder.month(dt.date_naive().month());
+    builder = builder.day(dt.date_naive().day());
+    builder = builder.hour(dt.time().hour());
+    builder = builder.minute(dt.time().minute());
+    builder = builder.second(dt.time().second());
+
+    let built = builder.build();
+    (built.date, built.time)
+}
+
+/// The number of seconds between the Windows FILETIME epoch (1601-01-01) and the Unix epoch (1970-01-01).
+#[cfg(feature = "chrono")]
+const FILETIME_UNIX_OFFSET: i64 = 11_644_473_600;
 
-        builder = builder.year(value.date_naive().year());
-        builder = builder.month(value.date_naive().month());
-        builder = builder.day(value.date_naive().day());
-        builder = builder.hour(value.time().hour());
-        builder = builder.minute(value.time().minute());
-        builder = builder.second(value.time().second());
+/// Converts chrono's [`DateTime`] representation into a Windows FILETIME value.
+///
+/// Dates prior to the FILETIME epoch (1601-01-01 00:00:00) can't be represented and so are clamped to it.
+#[cfg(feature = "chrono")]
+pub(crate) fn chrono_to_filetime(dt: &DateTime<Utc>) -> u64 {
+    let seconds = dt.timestamp().saturating_add(FILETIME_UNIX_OFFSET);
+    match u64::try_from(seconds) {
+        Ok(seconds) => seconds.saturating_mul(10_000_000).saturating_add((dt.timestamp_subsec_nanos() / 100).into()),
+        Err(_) => 0,
+    }
+}
 
-        builder.build()
+/// Interprets a Windows FILETIME value as chrono's [`DateTime`] representation.
+#[cfg(feature = "chrono")]
+pub(crate) fn filetime_to_chrono(filetime: u64) -> DateTime<Utc> {
+    let seconds = (filetime / 10_000_000) as i64 - FILETIME_UNIX_OFFSET;
+    let nanos = (filetime % 10_000_000) as u32 * 100;
+
+    // Even the largest FILETIME value falls well within chrono's supported range.
+    Utc.timestamp_opt(seconds, nanos).unwrap()
+}
+
+#[cfg(feature = "chrono")]
+impl From<&DateTime<Utc>> for ZipDateTime {
+    fn from(value: &DateTime<Utc>) -> Self {
+        let (date, time) = chrono_to_zip_time(value);
+        ZipDateTime { date, time }
     }
 }
 
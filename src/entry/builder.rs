@@ -2,8 +2,12 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
-use crate::spec::{attribute::AttributeCompatibility, header::ExtraField, Compression};
-use crate::{date::ZipDateTime, string::ZipString};
+use crate::spec::header::{ExtraField, HeaderId, InfoZipUnixExtraField, UnknownExtraField};
+use crate::spec::{attribute::AttributeCompatibility, Compression};
+use crate::{
+    date::ZipDateTime,
+    string::{NameEncoding, StringEncoding, ZipString},
+};
 
 /// A builder for [`ZipEntry`].
 pub struct ZipEntryBuilder(pub(crate) ZipEntry);
@@ -22,15 +26,71 @@ impl ZipEntryBuilder {
         Self(ZipEntry::new(filename, compression))
     }
 
+    /// Constructs a new builder whose filename is written exactly as the provided bytes.
+    ///
+    /// This preserves names which aren't valid UTF-8, or are in an unknown encoding, without any lossy conversion.
+    pub fn from_raw_name(filename: Vec<u8>, compression: Compression) -> Self {
+        Self::new(ZipString::new(filename, StringEncoding::Raw), compression)
+    }
+
+    /// Constructs a new builder whose compression method is inherited from the writer it's written with.
+    ///
+    /// See [`ZipFileWriterBuilder::default_compression()`]. Explicitly setting a compression method via
+    /// [`ZipEntryBuilder::compression()`] takes precedence over the writer's default.
+    ///
+    /// [`ZipFileWriterBuilder::default_compression()`]: crate::base::write::ZipFileWriterBuilder::default_compression
+    pub fn new_named(filename: ZipString) -> Self {
+        let mut builder = Self::new(filename, Compression::Stored);
+        builder.0.inherit_compression = true;
+        builder
+    }
+
     /// Sets the entry's filename.
     pub fn filename(mut self, filename: ZipString) -> Self {
+        self.0.name_encoding = NameEncoding::of(&filename);
         self.0.filename = filename;
         self
     }
 
+    /// Sets the entry's true UTF-8 name, stored within an Info-ZIP Unicode Path extra field (`0x7075`).
+    ///
+    /// The filename previously set on the builder is retained as the legacy name within the headers themselves, so
+    /// readers unaware of the extra field still see it. Readers which support the field prefer this name so long as
+    /// the field's CRC32 matches the legacy name, which is computed when the entry is written.
+    pub fn unicode_path(self, name: impl Into<String>) -> Self {
+        let filename = &self.0.filename;
+        let legacy = filename.alternative().unwrap_or_else(|| filename.as_bytes()).to_vec();
+        self.filename(ZipString::new_with_alternative(name.into(), legacy))
+    }
+
+    /// Normalises the entry's filename into the portable form required by the specification.
+    ///
+    /// Backslashes are converted to forward slashes and any leading slashes are removed, so `\dir\a.txt` becomes
+    /// `dir/a.txt`. Filenames are otherwise written byte-exact, so callers who need names such as these preserved
+    /// should simply not call this method.
+    pub fn normalize_path(self) -> Self {
+        fn normalize(bytes: &[u8]) -> Vec<u8> {
+            let start = bytes.iter().position(|byte| !matches!(byte, b'/' | b'\\')).unwrap_or(bytes.len());
+            bytes[start..].iter().map(|byte| if *byte == b'\\' { b'/' } else { *byte }).collect()
+        }
+
+        let filename = &self.0.filename;
+        let normalized = match filename.alternative() {
+            Some(alternative) => {
+                // Only ASCII bytes are changed, so the UTF-8 name remains valid.
+                let utf8 = String::from_utf8(normalize(filename.as_bytes())).expect("normalised name is valid UTF-8");
+                ZipString::new_with_alternative(utf8, normalize(alternative))
+            }
+            None => ZipString::new(normalize(filename.as_bytes()), filename.encoding()),
+        };
+
+        self.filename(normalized)
+    }
+
     /// Sets the entry's compression method.
     pub fn compression(mut self, compression: Compression) -> Self {
         self.0.compression = compression;
+        self.0.inherit_compression = false;
         self
     }
 
@@ -40,6 +100,40 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets a CRC32 checksum which is trusted to match the entry's data when written via
+    /// [`ZipFileWriter::write_entry_whole()`].
+    ///
+    /// If `Some`, the value is written verbatim to both headers and the data isn't hashed. If `None` (the default), the
+    /// checksum is computed from the data. The provided value isn't verified, so a mismatch will only be detected once
+    /// the entry is read.
+    ///
+    /// [`ZipFileWriter::write_entry_whole()`]: crate::base::write::ZipFileWriter::write_entry_whole
+    pub fn trusted_crc32(mut self, crc: Option<u32>) -> Self {
+        self.0.trusted_crc32 = crc;
+        self
+    }
+
+    /// Sets whether the entry is written with a data descriptor via [`ZipFileWriter::write_entry_whole()`].
+    ///
+    /// If enabled, the local file header holds a zero CRC32 and sizes, and the actual values follow the entry's data
+    /// within a data descriptor. This matches the format produced when streaming an entry. Defaults to `false`.
+    ///
+    /// [`ZipFileWriter::write_entry_whole()`]: crate::base::write::ZipFileWriter::write_entry_whole
+    pub fn force_data_descriptor(mut self, force: bool) -> Self {
+        self.0.force_data_descriptor = force;
+        self
+    }
+
+    /// Sets the boundary at which the entry's data must start, as used by `zipalign` for memory-mapped access.
+    ///
+    /// The local file header's extra field is padded so that the data starts at a multiple of `alignment` bytes from
+    /// the start of the archive. The padding isn't written to the central directory record. Alignments of `0` and `1`
+    /// (the default) add no padding.
+    pub fn align(mut self, alignment: u16) -> Self {
+        self.0.alignment = alignment;
+        self
+    }
+
     // Sets the entry's compressed size.
     pub fn compressed_size<N: Into<u64>>(mut self, size: N) -> Self {
         self.0.compressed_size = size.into();
@@ -61,6 +155,15 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Set the strategy used by the deflate compressor.
+    ///
+    /// If the compression type isn't deflate, this option has no effect.
+    #[cfg(feature = "deflate")]
+    pub fn deflate_strategy(mut self, strategy: crate::DeflateStrategy) -> Self {
+        self.0.deflate_strategy = strategy;
+        self
+    }
+
     /// Sets the entry's attribute host compatibility.
     pub fn attribute_compatibility(mut self, compatibility: AttributeCompatibility) -> Self {
         self.0.attribute_compatibility = compatibility;
@@ -73,6 +176,17 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets the minimum version needed to extract the entry, overriding the version derived from its features.
+    ///
+    /// Writing the entry fails with [`ZipError::InvalidVersion`] if the provided version is below that which its
+    /// features require.
+    ///
+    /// [`ZipError::InvalidVersion`]: crate::error::ZipError::InvalidVersion
+    pub fn min_version(mut self, version: u16) -> Self {
+        self.0.min_version = Some(version);
+        self
+    }
+
     /// Sets the entry's internal file attribute.
     pub fn internal_file_attribute(mut self, attribute: u16) -> Self {
         self.0.internal_file_attribute = attribute;
@@ -80,17 +194,86 @@ impl ZipEntryBuilder {
     }
 
     /// Sets the entry's external file attribute.
+    ///
+    /// The low 16 bits hold MS-DOS attributes (eg. `0x01` for read-only and `0x02` for hidden), whilst the high 16 bits
+    /// hold Unix permissions. As this replaces all 32 bits, call [`ZipEntryBuilder::unix_permissions()`] afterwards to
+    /// compose the two.
     pub fn external_file_attribute(mut self, attribute: u32) -> Self {
         self.0.external_file_attribute = attribute;
         self
     }
 
+    /// Sets the UID and GID of the entry's owner, stored within an Info-ZIP Unix extra field.
+    ///
+    /// Any existing Info-ZIP Unix extra field is replaced, whilst other extra fields are retained. Note that a later
+    /// call to [`ZipEntryBuilder::extra_fields()`] replaces all extra fields, including this one.
+    pub fn unix_uid_gid(mut self, uid: u32, gid: u32) -> Self {
+        self.0.extra_fields.retain(|field| !matches!(field, ExtraField::InfoZipUnix(_)));
+        self.0.extra_fields.push(ExtraField::InfoZipUnix(InfoZipUnixExtraField::V1 { uid, gid }));
+        self
+    }
+
+    /// Sets the entry's last modification, access, and creation times, stored within an NTFS extra field.
+    ///
+    /// Unlike the MS-DOS last modification date, these retain a precision of 100 nanoseconds. Any existing NTFS extra
+    /// field is replaced, whilst other extra fields are retained.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn ntfs_times(
+        mut self,
+        mtime: chrono::DateTime<chrono::Utc>,
+        atime: chrono::DateTime<chrono::Utc>,
+        ctime: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        use crate::date::chrono_to_filetime;
+        use crate::spec::header::NtfsExtraField;
+
+        let field = NtfsExtraField {
+            mtime: chrono_to_filetime(&mtime),
+            atime: chrono_to_filetime(&atime),
+            ctime: chrono_to_filetime(&ctime),
+        };
+
+        self.0.extra_fields.retain(|field| !matches!(field, ExtraField::Ntfs(_)));
+        self.0.extra_fields.push(ExtraField::Ntfs(field));
+        self
+    }
+
+    /// Appends an extra field with the provided header ID and data, retaining any existing extra fields.
+    ///
+    /// The field is framed with its header ID and data size when written. For full control over the entry's extra
+    /// fields, use [`ZipEntryBuilder::extra_fields()`] instead. Writing the entry fails with
+    /// [`ZipError::ExtraFieldTooLarge`] if the entry's extra fields exceed [`u16::MAX`] bytes in total.
+    ///
+    /// [`ZipError::ExtraFieldTooLarge`]: crate::error::ZipError::ExtraFieldTooLarge
+    pub fn add_extra_field(mut self, tag: u16, data: &[u8]) -> Self {
+        // Data this large can't fit within the extra field, so is rejected by the writer.
+        let data_size = u16::try_from(data.len()).unwrap_or(u16::MAX);
+        let field = UnknownExtraField { header_id: HeaderId(tag), data_size, content: data.to_vec() };
+
+        self.0.extra_fields.push(ExtraField::Unknown(field));
+        self
+    }
+
     /// Sets the entry's extra field data.
+    ///
+    /// This replaces any existing extra fields, including those added via [`ZipEntryBuilder::add_extra_field()`].
     pub fn extra_fields(mut self, field: Vec<ExtraField>) -> Self {
         self.0.extra_fields = field;
         self
     }
 
+    /// Sets the extra field data written within the entry's local file header.
+    ///
+    /// By default, the local file header holds the same extra fields as the central directory record (those set via
+    /// [`ZipEntryBuilder::extra_fields()`]). Any ZIP64 extended information field required by the writer is still
+    /// appended to both.
+    pub fn local_extra_fields(mut self, fields: Vec<ExtraField>) -> Self {
+        self.0.local_extra_fields = Some(fields);
+        self
+    }
+
     /// Sets the entry's file comment.
     pub fn comment(mut self, comment: ZipString) -> Self {
         self.0.comment = comment;
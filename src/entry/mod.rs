@@ -4,18 +4,25 @@
 pub mod builder;
 
 use std::ops::Deref;
+use std::path::{Component, Path, PathBuf};
 
-use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+use futures_lite::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
 
+use crate::base::read::io::read_bytes;
+use crate::base::read::{get_combined_sizes, get_zip64_extra_field, SizeSource};
 use crate::entry::builder::ZipEntryBuilder;
 use crate::error::{Result, ZipError};
+use crate::spec::parse::parse_extra_fields;
 use crate::spec::{
     attribute::AttributeCompatibility,
-    consts::LFH_SIGNATURE,
-    header::{ExtraField, LocalFileHeader},
-    Compression,
+    consts::{AES_COMPRESSION_METHOD, LFH_LENGTH, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH},
+    header::{ExtraField, InfoZipUnixExtraField, LocalFileHeader},
+    Compression, Encryption,
+};
+use crate::{
+    string::{NameEncoding, ZipString},
+    ZipDateTime,
 };
-use crate::{string::ZipString, ZipDateTime};
 
 /// An immutable store of data about a ZIP entry.
 ///
@@ -25,6 +32,7 @@ use crate::{string::ZipString, ZipDateTime};
 #[derive(Clone, Debug)]
 pub struct ZipEntry {
     pub(crate) filename: ZipString,
+    pub(crate) name_encoding: NameEncoding,
     pub(crate) compression: Compression,
     #[cfg(any(
         feature = "deflate",
@@ -35,6 +43,8 @@ pub struct ZipEntry {
         feature = "deflate64"
     ))]
     pub(crate) compression_level: async_compression::Level,
+    #[cfg(feature = "deflate")]
+    pub(crate) deflate_strategy: crate::spec::compression::DeflateStrategy,
     pub(crate) crc32: u32,
     pub(crate) uncompressed_size: u64,
     pub(crate) compressed_size: u64,
@@ -43,8 +53,15 @@ pub struct ZipEntry {
     pub(crate) internal_file_attribute: u16,
     pub(crate) external_file_attribute: u32,
     pub(crate) extra_fields: Vec<ExtraField>,
+    pub(crate) local_extra_fields: Option<Vec<ExtraField>>,
     pub(crate) comment: ZipString,
     pub(crate) data_descriptor: bool,
+    pub(crate) encrypted: bool,
+    pub(crate) min_version: Option<u16>,
+    pub(crate) trusted_crc32: Option<u32>,
+    pub(crate) inherit_compression: bool,
+    pub(crate) force_data_descriptor: bool,
+    pub(crate) alignment: u16,
 }
 
 impl From<ZipEntryBuilder> for ZipEntry {
@@ -56,6 +73,7 @@ impl From<ZipEntryBuilder> for ZipEntry {
 impl ZipEntry {
     pub(crate) fn new(filename: ZipString, compression: Compression) -> Self {
         ZipEntry {
+            name_encoding: NameEncoding::of(&filename),
             filename,
             compression,
             #[cfg(any(
@@ -67,6 +85,8 @@ impl ZipEntry {
                 feature = "deflate64"
             ))]
             compression_level: async_compression::Level::Default,
+            #[cfg(feature = "deflate")]
+            deflate_strategy: crate::spec::compression::DeflateStrategy::Default,
             crc32: 0,
             uncompressed_size: 0,
             compressed_size: 0,
@@ -75,8 +95,15 @@ impl ZipEntry {
             internal_file_attribute: 0,
             external_file_attribute: 0,
             extra_fields: Vec::new(),
+            local_extra_fields: None,
             comment: String::new().into(),
             data_descriptor: false,
+            encrypted: false,
+            min_version: None,
+            trusted_crc32: None,
+            inherit_compression: false,
+            force_data_descriptor: false,
+            alignment: 0,
         }
     }
 
@@ -90,11 +117,47 @@ impl ZipEntry {
         &self.filename
     }
 
+    /// Returns the entry's filename bytes exactly as they're stored within its headers.
+    ///
+    /// This is useful for callers who wish to implement their own decoding of legacy filenames.
+    pub fn raw_name_bytes(&self) -> &[u8] {
+        self.filename.alternative().unwrap_or_else(|| self.filename.as_bytes())
+    }
+
+    /// Returns the encoding which was used to decode the entry's filename.
+    ///
+    /// When the UTF-8 flag (general purpose bit 11) is unset, filenames which are valid UTF-8 are still decoded as
+    /// such, with CP437 only being used as a fallback. Filenames consisting solely of ASCII characters are identical in
+    /// both encodings and so report [`NameEncoding::Ascii`].
+    pub fn name_encoding(&self) -> NameEncoding {
+        self.name_encoding
+    }
+
     /// Returns the entry's compression method.
+    ///
+    /// For entries encrypted with WinZip AES, this is the underlying method stored within the WinZip AES extra field
+    /// rather than the AES marker method (99).
     pub fn compression(&self) -> Compression {
         self.compression
     }
 
+    /// Returns the entry's compression method as its raw stored value, whether or not it's supported.
+    ///
+    /// As with [`ZipEntry::compression()`], this is the underlying method for entries encrypted with WinZip AES.
+    pub fn compression_method_raw(&self) -> u16 {
+        self.compression.into()
+    }
+
+    /// Returns the entry's compression method if its data can be read, ie. the method is supported and the data is
+    /// unencrypted.
+    pub(crate) fn readable_compression(&self) -> Result<Compression> {
+        if self.encrypted {
+            return Err(ZipError::FeatureNotSupported("encryption"));
+        }
+
+        self.compression.supported()
+    }
+
     /// Returns the entry's CRC32 value.
     pub fn crc32(&self) -> u32 {
         self.crc32
@@ -120,6 +183,19 @@ impl ZipEntry {
         &self.last_modification_date
     }
 
+    /// Returns the entry's last modification time & date as chrono's [`DateTime`](chrono::DateTime) representation.
+    ///
+    /// Zeroed or out-of-range MS-DOS values are floored to 1980-01-01 00:00:00.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn last_modified(&self) -> chrono::DateTime<chrono::Utc> {
+        use chrono::TimeZone;
+
+        let epoch = || chrono::Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap();
+        self.last_modification_date.as_chrono().single().unwrap_or_else(epoch)
+    }
+
     /// Returns the entry's internal file attribute.
     pub fn internal_file_attribute(&self) -> u16 {
         self.internal_file_attribute
@@ -135,11 +211,39 @@ impl ZipEntry {
         &self.extra_fields
     }
 
+    /// Returns whether the entry carries a ZIP64 extended information extra field.
+    pub fn is_zip64(&self) -> bool {
+        self.extra_fields.iter().any(|field| matches!(field, ExtraField::Zip64ExtendedInformation(_)))
+    }
+
     /// Returns the entry's file comment.
     pub fn comment(&self) -> &ZipString {
         &self.comment
     }
 
+    /// Returns whether the entry's data is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    /// Returns the method used to encrypt the entry's data.
+    ///
+    /// Entries which are encrypted and hold a WinZip AES extra field use AES, whilst all other encrypted entries use
+    /// the traditional PKWARE encryption (ZipCrypto).
+    pub fn encryption(&self) -> Encryption {
+        if !self.encrypted {
+            return Encryption::None;
+        }
+
+        self.extra_fields
+            .iter()
+            .find_map(|field| match field {
+                ExtraField::WinZipAes(field) => Some(Encryption::from_aes_strength(field.strength)),
+                _ => None,
+            })
+            .unwrap_or(Encryption::ZipCrypto)
+    }
+
     /// Returns the entry's integer-based UNIX permissions.
     ///
     /// # Note
@@ -152,10 +256,137 @@ impl ZipEntry {
         Some(((self.external_file_attribute) >> 16) as u16)
     }
 
+    /// Returns the UID and GID of the entry's owner, if stored within an Info-ZIP Unix extra field.
+    pub fn uid_gid(&self) -> Option<(u32, u32)> {
+        self.extra_fields.iter().find_map(|field| match field {
+            ExtraField::InfoZipUnix(InfoZipUnixExtraField::V1 { uid, gid }) => Some((*uid, *gid)),
+            _ => None,
+        })
+    }
+
+    /// Returns the entry's last modification, access, and creation times, if stored within an NTFS extra field.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn ntfs_times(
+        &self,
+    ) -> Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> {
+        use crate::date::filetime_to_chrono;
+
+        self.extra_fields.iter().find_map(|field| match field {
+            ExtraField::Ntfs(field) => Some((
+                filetime_to_chrono(field.mtime),
+                filetime_to_chrono(field.atime),
+                filetime_to_chrono(field.ctime),
+            )),
+            _ => None,
+        })
+    }
+
+    /// Returns the host system whose mapping is used for the entry's external file attributes.
+    ///
+    /// This is the value stored within the upper byte of the "version made by" field. Entries with Unix attribute host
+    /// compatibility but no Unix permissions set are written with (and so report) the MS-DOS host, ensuring other tools
+    /// don't interpret zeroed Unix permissions.
+    pub fn host_os(&self) -> AttributeCompatibility {
+        match self.unix_permissions() {
+            Some(0) => AttributeCompatibility::Dos,
+            _ => self.attribute_compatibility,
+        }
+    }
+
     /// Returns whether or not the entry represents a directory.
     pub fn dir(&self) -> Result<bool> {
         Ok(self.filename.as_str()?.ends_with('/'))
     }
+
+    /// Returns a relative path derived from the entry's filename which is safe to extract to.
+    ///
+    /// Any components which could refer to a location outside of the extraction directory (eg. `..` or a root) are
+    /// removed to prevent [directory traversal attacks](https://en.wikipedia.org/wiki/Directory_traversal_attack).
+    /// Filenames beginning with a Windows drive (eg. `C:..\x`) or UNC prefix (eg. `\\server\share\x`) are rejected
+    /// with [`ZipError::UnsafePathPrefix`]; see [`ZipEntry::safe_path_with()`] to strip them instead.
+    pub fn safe_path(&self) -> Result<PathBuf> {
+        self.safe_path_with(PathPrefixPolicy::default())
+    }
+
+    /// Returns a relative path derived from the entry's filename which is safe to extract to, handling any Windows
+    /// drive or UNC prefix as specified by the provided policy.
+    ///
+    /// See [`ZipEntry::safe_path()`] for more information.
+    pub fn safe_path_with(&self, policy: PathPrefixPolicy) -> Result<PathBuf> {
+        let filename = self.filename.as_str()?;
+
+        let relative = match strip_windows_prefix(filename) {
+            Some(_) if policy == PathPrefixPolicy::Reject => {
+                return Err(ZipError::UnsafePathPrefix(filename.to_string()));
+            }
+            Some(relative) => relative,
+            None => filename,
+        };
+
+        Ok(relative
+            .split(['/', '\\'])
+            .filter(|component| matches!(Path::new(component).components().next(), Some(Component::Normal(_))))
+            .collect())
+    }
+}
+
+/// The handling of Windows drive and UNC prefixes within an entry's filename by [`ZipEntry::safe_path_with()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PathPrefixPolicy {
+    /// Fail with [`ZipError::UnsafePathPrefix`].
+    #[default]
+    Reject,
+    /// Remove the prefix, treating the remainder of the filename as relative.
+    Strip,
+}
+
+/// Returns the remainder of the filename if it begins with a Windows drive or UNC prefix.
+///
+/// This is done independently of the current platform, since archives created on one are often extracted on another.
+fn strip_windows_prefix(filename: &str) -> Option<&str> {
+    fn strip_drive(path: &str) -> Option<&str> {
+        match path.as_bytes() {
+            [letter, b':', ..] if letter.is_ascii_alphabetic() => Some(&path[2..]),
+            _ => None,
+        }
+    }
+
+    fn next_component(path: &str) -> (&str, &str) {
+        match path.find(['/', '\\']) {
+            Some(index) => (&path[..index], &path[index + 1..]),
+            None => (path, ""),
+        }
+    }
+
+    if let Some(relative) = strip_drive(filename) {
+        return Some(relative);
+    }
+
+    let path = match filename.as_bytes() {
+        [b'/' | b'\\', b'/' | b'\\', ..] => &filename[2..],
+        _ => return None,
+    };
+
+    let (server, path) = next_component(path);
+
+    // Verbatim (`\\?\`) and device (`\\.\`) paths are followed by a drive, a UNC server and share, or a device.
+    if server == "?" || server == "." {
+        if let Some(relative) = strip_drive(path) {
+            return Some(relative);
+        }
+
+        let (device, path) = next_component(path);
+
+        if device.eq_ignore_ascii_case("UNC") {
+            return Some(next_component(next_component(path).1).1);
+        }
+
+        return Some(path);
+    }
+
+    Some(next_component(path).1)
 }
 
 /// An immutable store of data about how a ZIP entry is stored within a specific archive.
@@ -168,6 +399,7 @@ pub struct StoredZipEntry {
     // pub(crate) general_purpose_flag: GeneralPurposeFlag,
     pub(crate) file_offset: u64,
     pub(crate) header_size: u64,
+    pub(crate) disk_start_number: u32,
 }
 
 impl StoredZipEntry {
@@ -176,6 +408,14 @@ impl StoredZipEntry {
         self.file_offset
     }
 
+    /// Returns the number of the disk on which the entry's local file header starts.
+    ///
+    /// This will be read from the zip64 extended information extra field when present and required (ie. when the
+    /// central directory record stores `0xFFFF`). Archives which don't span multiple disks will always return `0`.
+    pub fn disk_start_number(&self) -> u32 {
+        self.disk_start_number
+    }
+
     /// Returns the combined size in bytes of the header, the filename, and any extra fields.
     ///
     /// Note: This uses the extra field length stored in the central directory, which may differ from that stored in
@@ -184,29 +424,73 @@ impl StoredZipEntry {
         self.header_size
     }
 
+    /// Returns the offset in bytes to where the entry's compressed data starts.
+    ///
+    /// The central directory doesn't record the length of the local file header's extra field, so this reads the
+    /// local file header from the provided reader (which must be over the same ZIP file). The returned offset can be
+    /// used for external range reads of exactly `compressed_size()` bytes.
+    pub async fn data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, reader: &mut R) -> Result<u64> {
+        self.seek_to_data_offset(reader).await?;
+        Ok(reader.seek(SeekFrom::Current(0)).await?)
+    }
+
     /// Seek to the offset in bytes where the data of the entry starts.
-    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<()> {
+    ///
+    /// The local file header's compression method and flags are checked against those from the central directory, as
+    /// disagreement would otherwise lead to the entry's data being misread.
+    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, reader: &mut R) -> Result<()> {
+        self.seek_to_data(reader, SizeSource::Central).await.map(|_| ())
+    }
+
+    /// Seek to the offset in bytes where the data of the entry starts, returning that offset and the entry's compressed
+    /// size as per the source.
+    ///
+    /// Entries with a data descriptor don't store their sizes locally, so always use that from the central directory.
+    pub(crate) async fn seek_to_data<R: AsyncRead + AsyncSeek + Unpin>(
+        &self,
+        mut reader: &mut R,
+        source: SizeSource,
+    ) -> Result<(u64, u64)> {
         // Seek to the header
         reader.seek(SeekFrom::Start(self.file_offset)).await?;
 
         // Check the signature
-        let signature = {
-            let mut buffer = [0; 4];
-            reader.read_exact(&mut buffer).await?;
-            u32::from_le_bytes(buffer)
-        };
-
-        match signature {
-            LFH_SIGNATURE => (),
-            actual => return Err(ZipError::UnexpectedHeaderError(actual, LFH_SIGNATURE)),
-        };
+        crate::utils::assert_signature(&mut reader, LFH_SIGNATURE).await?;
 
         // Skip the local file header and trailing data
         let header = LocalFileHeader::from_reader(&mut reader).await?;
-        let trailing_size = (header.file_name_length as i64) + (header.extra_field_length as i64);
-        reader.seek(SeekFrom::Current(trailing_size)).await?;
 
-        Ok(())
+        // AES-encrypted entries store their actual compression method within an extra field, which we've resolved.
+        if header.compression != AES_COMPRESSION_METHOD && header.compression != u16::from(self.entry.compression) {
+            return Err(ZipError::HeaderMismatch("compression method"));
+        }
+        if header.flags.encrypted != self.entry.encrypted || header.flags.data_descriptor != self.entry.data_descriptor
+        {
+            return Err(ZipError::HeaderMismatch("general purpose flags"));
+        }
+
+        let trailing_size = u64::from(header.file_name_length) + u64::from(header.extra_field_length);
+        let data_offset = self.file_offset + (SIGNATURE_LENGTH + LFH_LENGTH) as u64 + trailing_size;
+
+        if source == SizeSource::Central || header.flags.data_descriptor {
+            reader.seek(SeekFrom::Current(trailing_size as i64)).await?;
+            return Ok((data_offset, self.entry.compressed_size));
+        }
+
+        reader.seek(SeekFrom::Current(header.file_name_length.into())).await?;
+
+        // The extra field only needs parsing if the size is held within a zip64 extended information field.
+        if header.compressed_size != NON_ZIP64_MAX_SIZE {
+            reader.seek(SeekFrom::Current(header.extra_field_length.into())).await?;
+            return Ok((data_offset, header.compressed_size.into()));
+        }
+
+        let extra_field = read_bytes(reader, header.extra_field_length.into()).await?;
+        let fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+        let (_, compressed_size) =
+            get_combined_sizes(header.uncompressed_size, header.compressed_size, &get_zip64_extra_field(&fields))?;
+
+        Ok((data_offset, compressed_size))
     }
 }
 
@@ -2,6 +2,17 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 //! A module which holds relevant error reporting structures/types.
+//!
+//! Common failures map onto [`ZipError`] variants as follows:
+//! - A computed CRC32 value not matching the stored value: [`ZipError::CRC32CheckError`].
+//! - An entry using an unknown or disabled compression method: [`ZipError::CompressionNotSupported`].
+//! - An archive needing ZIP64 structures whilst they're disabled: [`ZipError::Zip64Needed`].
+//! - A header starting with an unexpected signature: [`ZipError::UnexpectedHeaderError`].
+//! - A header cut short by the end of the source: [`ZipError::UnexpectedEof`].
+//! - Any failure of the underlying reader or writer: [`ZipError::UpstreamReadError`], whose
+//!   [`source()`](std::error::Error::source) is the originating [`std::io::Error`].
+//!
+//! Encrypted entries are rejected with [`ZipError::FeatureNotSupported`] as decryption isn't supported.
 
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
@@ -30,6 +41,8 @@ impl Display for Zip64ErrorCase {
 pub enum ZipError {
     #[error("feature not supported: '{0}'")]
     FeatureNotSupported(&'static str),
+    #[error("archives spanning multiple disks are not supported")]
+    MultiDiskUnsupported,
     #[error("compression not supported: {0}")]
     CompressionNotSupported(u16),
     #[error("host attribute compatibility not supported: {0}")]
@@ -46,27 +59,76 @@ pub enum ZipError {
     CommentTooLarge,
     #[error("filename exceeded maximum size")]
     FileNameTooLarge,
+    #[error("an entry with the name '{0}' has already been written")]
+    DuplicateEntryName(String),
+    #[error("version needed to extract ({0}) was below that required by the entry ({1})")]
+    InvalidVersion(u16, u16),
+    #[error("precompressed data didn't match the entry's compression method")]
+    PrecompressedMethodMismatch,
     #[error("attempted to convert non-UTF8 bytes to a string/str")]
     StringNotUtf8,
+    #[cfg(feature = "json")]
+    #[error("unable to deserialise the entry's JSON data: {0}")]
+    Json(#[source] serde_json::Error),
 
     #[error("unable to locate the end of central directory record")]
     UnableToLocateEOCDR,
+    #[error("unable to locate the end of central directory record within the final {0} bytes")]
+    EocdNotFound(u64),
     #[error("extra field size was indicated to be {0} but only {1} bytes remain")]
     InvalidExtraFieldHeader(u16, usize),
     #[error("zip64 extended information field was incomplete")]
     Zip64ExtendedFieldIncomplete,
 
     #[error("an upstream reader returned an error: {0}")]
-    UpstreamReadError(#[from] std::io::Error),
+    UpstreamReadError(#[source] std::io::Error),
     #[error("a computed CRC32 value did not match the expected value")]
     CRC32CheckError,
+    #[error("the central directory declared {0} entries, more than permitted or able to fit within its size")]
+    TooManyEntries(u64),
     #[error("entry index was out of bounds")]
     EntryIndexOutOfBounds,
     #[error("Encountered an unexpected header (actual: {0:#x}, expected: {1:#x}).")]
     UnexpectedHeaderError(u32, u32),
+    #[error("reached the end of the source before a header could be fully read")]
+    UnexpectedEof,
+    #[error("an entry's local file header disagreed with its central directory record on its {0}")]
+    HeaderMismatch(&'static str),
+    #[error("extraction target already exists: {0}")]
+    ExtractTargetExists(std::path::PathBuf),
+    #[error("entry filename has a Windows drive or UNC prefix: {0}")]
+    UnsafePathPrefix(String),
+    #[error("decompressed data exceeded the limit of {0} bytes")]
+    SizeLimitExceeded(u64),
+    #[error("decompressed data exceeded the limit on its ratio to the compressed size")]
+    CompressionRatioExceeded,
+    #[error("seek index restart point lies beyond the entry's compressed data")]
+    SeekIndexMismatch,
+    #[error("unable to locate a data descriptor matching the entry's data")]
+    DataDescriptorNotFound,
 
     #[error("Info-ZIP Unicode Comment Extra Field was incomplete")]
     InfoZipUnicodeCommentFieldIncomplete,
     #[error("Info-ZIP Unicode Path Extra Field was incomplete")]
     InfoZipUnicodePathFieldIncomplete,
+    #[error("Info-ZIP Unix Extra Field was incomplete")]
+    InfoZipUnixFieldIncomplete,
+    #[error("NTFS Extra Field was incomplete")]
+    NtfsFieldIncomplete,
+    #[error("WinZip AES Extra Field was incomplete")]
+    WinZipAesFieldIncomplete,
+    #[error("WinZip AES Extra Field holds an invalid strength: {0}")]
+    InvalidAesStrength(u8),
+    #[error("WinZip AES Extra Field holds an invalid vendor version: {0}")]
+    InvalidAesVersion(u16),
+}
+
+impl From<std::io::Error> for ZipError {
+    fn from(error: std::io::Error) -> Self {
+        // Errors raised by our own readers are wrapped so that they can pass through IO trait methods.
+        match error.get_ref().is_some_and(|inner| inner.is::<ZipError>()) {
+            true => *error.into_inner().unwrap().downcast::<ZipError>().unwrap(),
+            false => ZipError::UpstreamReadError(error),
+        }
+    }
 }
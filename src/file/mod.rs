@@ -2,9 +2,11 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 pub(crate) mod builder;
+pub(crate) mod stats;
 
 use crate::{entry::StoredZipEntry, string::ZipString};
 use builder::ZipFileBuilder;
+use stats::ArchiveStats;
 
 /// An immutable store of data about a ZIP file.
 #[derive(Clone)]
@@ -12,6 +14,8 @@ pub struct ZipFile {
     pub(crate) entries: Vec<StoredZipEntry>,
     pub(crate) zip64: bool,
     pub(crate) comment: ZipString,
+    /// The length of any stub (eg. a self-extracting executable) prepended to the archive this was read from.
+    pub(crate) stub_length: u64,
 }
 
 impl From<ZipFileBuilder> for ZipFile {
@@ -26,11 +30,56 @@ impl ZipFile {
         &self.entries
     }
 
+    /// Returns the number of entries within this ZIP file.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether this ZIP file holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the filenames of this ZIP file's entries, in central directory order.
+    ///
+    /// Filenames which can't be represented as a `&str` (see [`ZipString::as_str()`]) are skipped. Names read from an
+    /// archive are always decoded and so are never skipped.
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().filter_map(|entry| entry.filename().as_str().ok())
+    }
+
+    /// Returns the total compressed size of this ZIP file's entries, saturating at [`u64::MAX`].
+    pub fn total_compressed_size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.compressed_size()).fold(0, u64::saturating_add)
+    }
+
+    /// Returns the total uncompressed size of this ZIP file's entries, saturating at [`u64::MAX`].
+    pub fn total_uncompressed_size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.uncompressed_size()).fold(0, u64::saturating_add)
+    }
+
+    /// Returns aggregate statistics about this ZIP file's entries, computed without reading any entry data.
+    pub fn stats(&self) -> ArchiveStats {
+        let mut stats = ArchiveStats::default();
+        for entry in &self.entries {
+            stats.record(entry.compression(), entry.compressed_size(), entry.uncompressed_size());
+        }
+        stats
+    }
+
     /// Returns this ZIP file's trailing comment.
     pub fn comment(&self) -> &ZipString {
         &self.comment
     }
 
+    /// Returns this ZIP file's trailing comment as raw bytes, or None if no comment is present.
+    ///
+    /// Unlike [`ZipFile::comment()`], this makes no assumption that the comment is text and so is suitable for
+    /// archives which store binary data within the comment.
+    pub fn comment_bytes(&self) -> Option<&[u8]> {
+        Some(self.comment.as_bytes()).filter(|bytes| !bytes.is_empty())
+    }
+
     /// Returns whether or not this ZIP file is zip64
     pub fn zip64(&self) -> bool {
         self.zip64
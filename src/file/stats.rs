@@ -0,0 +1,46 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::Compression;
+
+/// Aggregate statistics about a ZIP file's entries.
+///
+/// These are computed from the central directory alone, so no entry data is read. See [`ZipFile::stats()`].
+///
+/// [`ZipFile::stats()`]: crate::ZipFile::stats
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArchiveStats {
+    /// The number of entries.
+    pub entries: usize,
+    /// The total compressed size of all entries, in bytes, saturating at [`u64::MAX`].
+    pub compressed_size: u64,
+    /// The total uncompressed size of all entries, in bytes, saturating at [`u64::MAX`].
+    pub uncompressed_size: u64,
+    /// The number of entries using each compression method, in order of each method's first appearance.
+    pub methods: Vec<(Compression, usize)>,
+}
+
+impl ArchiveStats {
+    /// Returns the ratio of the total compressed size to the total uncompressed size.
+    ///
+    /// Lower values indicate better compression, with `1.0` indicating none. If the entries hold no uncompressed data,
+    /// None is returned.
+    pub fn ratio(&self) -> Option<f64> {
+        match self.uncompressed_size {
+            0 => None,
+            size => Some(self.compressed_size as f64 / size as f64),
+        }
+    }
+
+    pub(crate) fn record(&mut self, compression: Compression, compressed_size: u64, uncompressed_size: u64) {
+        self.entries += 1;
+        // Sizes are taken from the central directory, so may be arbitrary within a malformed archive.
+        self.compressed_size = self.compressed_size.saturating_add(compressed_size);
+        self.uncompressed_size = self.uncompressed_size.saturating_add(uncompressed_size);
+
+        match self.methods.iter_mut().find(|(method, _)| *method == compression) {
+            Some((_, count)) => *count += 1,
+            None => self.methods.push((compression, 1)),
+        }
+    }
+}
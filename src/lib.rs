@@ -23,16 +23,24 @@
 //! ```
 //!
 //! ### Feature Flags
-//! - `full` - Enables all below features.
+//! - `full` - Enables all below features, except for `unstable`.
 //! - `full-wasm` - Enables all below features that are compatible with WASM.
 //! - `chrono` - Enables support for parsing dates via `chrono`.
 //! - `tokio` - Enables support for the `tokio` implementation module.
 //! - `tokio-fs` - Enables support for the `tokio::fs` reading module.
+//! - `tokio-parallel` - Enables compressing entries in parallel on `tokio`'s blocking thread pool.
+//! - `async-std` - Enables support for the `async_std::fs` reading module.
 //! - `deflate` - Enables support for the Deflate compression method.
 //! - `bzip2` - Enables support for the bzip2 compression method.
 //! - `lzma` - Enables support for the LZMA compression method.
 //! - `zstd` - Enables support for the zstd compression method.
 //! - `xz` - Enables support for the xz compression method.
+//! - `deflate64` - Enables support for reading the Deflate64 compression method.
+//! - `blocking` - Enables support for the blocking (synchronous) facade module.
+//! - `rayon` - Enables parallel serialisation of large central directories via `rayon`.
+//! - `json` - Enables deserialising JSON entries directly into typed values via `serde`.
+//! - `log` - Enables logging warnings about recoverable problems via the `log` crate.
+//! - `unstable` - Exposes low-level escape hatches whose signatures may change between releases.
 //!
 //! [Read more.](https://github.com/Majored/rs-async-zip)
 
@@ -42,6 +50,13 @@ pub mod error;
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+#[cfg(feature = "async-std")]
+pub mod async_std;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+pub(crate) mod crc;
 pub(crate) mod date;
 pub(crate) mod entry;
 pub(crate) mod file;
@@ -53,10 +68,14 @@ pub(crate) mod utils;
 pub(crate) mod tests;
 
 pub use crate::spec::attribute::AttributeCompatibility;
+#[cfg(feature = "deflate")]
+pub use crate::spec::compression::DeflateStrategy;
 pub use crate::spec::compression::{Compression, DeflateOption};
+pub use crate::spec::encryption::Encryption;
 
+pub use crate::crc::Crc32;
 pub use crate::date::{builder::ZipDateTimeBuilder, ZipDateTime};
-pub use crate::entry::{builder::ZipEntryBuilder, StoredZipEntry, ZipEntry};
-pub use crate::file::{builder::ZipFileBuilder, ZipFile};
+pub use crate::entry::{builder::ZipEntryBuilder, PathPrefixPolicy, StoredZipEntry, ZipEntry};
+pub use crate::file::{builder::ZipFileBuilder, stats::ArchiveStats, ZipFile};
 
-pub use crate::string::{StringEncoding, ZipString};
+pub use crate::string::{NameEncoding, StringEncoding, ZipString};
@@ -7,6 +7,7 @@ use crate::error::{Result, ZipError};
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AttributeCompatibility {
+    Dos,
     Unix,
 }
 
@@ -17,6 +18,7 @@ impl TryFrom<u16> for AttributeCompatibility {
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn try_from(value: u16) -> Result<Self> {
         match value {
+            0 => Ok(AttributeCompatibility::Dos),
             3 => Ok(AttributeCompatibility::Unix),
             _ => Err(ZipError::AttributeCompatibilityNotSupported(value)),
         }
@@ -28,6 +30,7 @@ impl From<&AttributeCompatibility> for u16 {
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn from(compatibility: &AttributeCompatibility) -> Self {
         match compatibility {
+            AttributeCompatibility::Dos => 0,
             AttributeCompatibility::Unix => 3,
         }
     }
@@ -23,6 +23,11 @@ pub enum Compression {
     Zstd,
     #[cfg(feature = "xz")]
     Xz,
+    /// A compression method which isn't supported (or whose feature isn't enabled), holding its raw value.
+    ///
+    /// Entries using such a method are still listed when reading, but opening a reader for them fails with
+    /// [`ZipError::CompressionNotSupported`]. Their raw data can still be copied to another archive.
+    Unsupported(u16),
 }
 
 impl TryFrom<u16> for Compression {
@@ -68,16 +73,85 @@ impl From<&Compression> for u16 {
             Compression::Zstd => 93,
             #[cfg(feature = "xz")]
             Compression::Xz => 95,
+            Compression::Unsupported(method) => *method,
         }
     }
 }
 
+impl Compression {
+    /// Returns this compression method, or an error if it isn't supported.
+    pub(crate) fn supported(self) -> Result<Self> {
+        match self {
+            Compression::Unsupported(method) => Err(ZipError::CompressionNotSupported(method)),
+            compression => Ok(compression),
+        }
+    }
+
+    /// Returns this compression method, or an error if data can't be written with it.
+    pub(crate) fn writable(self) -> Result<Self> {
+        match self.supported()? {
+            #[cfg(feature = "deflate64")]
+            Compression::Deflate64 => Err(ZipError::CompressionNotSupported(Compression::Deflate64.into())),
+            compression => Ok(compression),
+        }
+    }
+
+    /// Returns whether data written with this compression method ends with an end-of-stream marker, which is flagged
+    /// within the general purpose flag for LZMA.
+    pub(crate) fn writes_eos_marker(self) -> bool {
+        #[cfg(feature = "lzma")]
+        if self == Compression::Lzma {
+            return true;
+        }
+
+        false
+    }
+}
+
 impl From<Compression> for u16 {
     fn from(compression: Compression) -> u16 {
         (&compression).into()
     }
 }
 
+/// Strategy the deflate compressor should use when searching for matches.
+///
+/// The non-default strategies can compress some specialised data better (eg. data with many short runs).
+#[cfg(feature = "deflate")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeflateStrategy {
+    /// The default strategy, suitable for most data.
+    #[default]
+    Default,
+
+    /// Only use matches that are at least 5 bytes long.
+    Filtered,
+
+    /// Don't search for matches and only Huffman encode literals.
+    HuffmanOnly,
+
+    /// Only search for matches with a distance of 1 (ie. run-length encoding).
+    Rle,
+
+    /// Only use fixed Huffman codes.
+    Fixed,
+}
+
+#[cfg(feature = "deflate")]
+impl DeflateStrategy {
+    pub(crate) fn into_miniz(self) -> miniz_oxide::deflate::core::CompressionStrategy {
+        use miniz_oxide::deflate::core::CompressionStrategy;
+
+        match self {
+            Self::Default => CompressionStrategy::Default,
+            Self::Filtered => CompressionStrategy::Filtered,
+            Self::HuffmanOnly => CompressionStrategy::HuffmanOnly,
+            Self::Rle => CompressionStrategy::RLE,
+            Self::Fixed => CompressionStrategy::Fixed,
+        }
+    }
+}
+
 /// Level of compression data should be compressed with for deflate.
 #[derive(Debug, Clone, Copy)]
 pub enum DeflateOption {
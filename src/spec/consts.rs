@@ -42,3 +42,13 @@ pub const NON_ZIP64_MAX_NUM_FILES: u16 = 0xFFFF;
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#439
 pub const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x8074b50;
 pub const DATA_DESCRIPTOR_LENGTH: usize = 12;
+
+/// The compression method of entries encrypted with WinZip AES, whose actual method is held within an extra field.
+pub const AES_COMPRESSION_METHOD: u16 = 99;
+
+#[cfg(feature = "lzma")]
+/// The length of the LZMA properties which follow the version and properties size within LZMA entries' data.
+pub const LZMA_PROPERTIES_LENGTH: usize = 5;
+#[cfg(feature = "lzma")]
+/// The LZMA SDK version written at the start of LZMA entries' data, matching that written by other implementations.
+pub const LZMA_SDK_VERSION: [u8; 2] = [9, 20];
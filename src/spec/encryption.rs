@@ -0,0 +1,30 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+/// A method used to encrypt an entry's data.
+///
+/// Encrypted entries can be listed, but decryption isn't supported by this crate.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encryption {
+    None,
+    /// The traditional PKWARE encryption (ie. ZipCrypto).
+    ZipCrypto,
+    Aes128,
+    Aes192,
+    Aes256,
+    /// An encryption method which couldn't be determined.
+    Unknown,
+}
+
+impl Encryption {
+    /// Returns the encryption corresponding to a WinZip AES extra field's strength byte.
+    pub(crate) fn from_aes_strength(strength: u8) -> Self {
+        match strength {
+            1 => Encryption::Aes128,
+            2 => Encryption::Aes192,
+            3 => Encryption::Aes256,
+            _ => Encryption::Unknown,
+        }
+    }
+}
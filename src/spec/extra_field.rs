@@ -2,8 +2,8 @@
 
 use crate::error::{Result as ZipResult, ZipError};
 use crate::spec::header::{
-    ExtraField, HeaderId, InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField, UnknownExtraField,
-    Zip64ExtendedInformationExtraField,
+    ExtraField, HeaderId, InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField, InfoZipUnixExtraField,
+    NtfsExtraField, UnknownExtraField, WinZipAesExtraField, Zip64ExtendedInformationExtraField,
 };
 
 use super::consts::NON_ZIP64_MAX_SIZE;
@@ -34,6 +34,9 @@ impl ExtraFieldAsBytes for ExtraField {
             ExtraField::Zip64ExtendedInformation(field) => field.as_bytes(),
             ExtraField::InfoZipUnicodeComment(field) => field.as_bytes(),
             ExtraField::InfoZipUnicodePath(field) => field.as_bytes(),
+            ExtraField::InfoZipUnix(field) => field.as_bytes(),
+            ExtraField::Ntfs(field) => field.as_bytes(),
+            ExtraField::WinZipAes(field) => field.as_bytes(),
             ExtraField::Unknown(field) => field.as_bytes(),
         }
     }
@@ -43,6 +46,9 @@ impl ExtraFieldAsBytes for ExtraField {
             ExtraField::Zip64ExtendedInformation(field) => field.count_bytes(),
             ExtraField::InfoZipUnicodeComment(field) => field.count_bytes(),
             ExtraField::InfoZipUnicodePath(field) => field.count_bytes(),
+            ExtraField::InfoZipUnix(field) => field.count_bytes(),
+            ExtraField::Ntfs(field) => field.count_bytes(),
+            ExtraField::WinZipAes(field) => field.count_bytes(),
             ExtraField::Unknown(field) => field.count_bytes(),
         }
     }
@@ -153,6 +159,76 @@ impl ExtraFieldAsBytes for InfoZipUnicodePathExtraField {
     }
 }
 
+impl ExtraFieldAsBytes for InfoZipUnixExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::INFO_ZIP_UNIX_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        match self {
+            InfoZipUnixExtraField::V1 { uid, gid } => {
+                bytes.append(&mut 11u16.to_le_bytes().to_vec());
+                bytes.push(1);
+                bytes.push(4);
+                bytes.append(&mut uid.to_le_bytes().to_vec());
+                bytes.push(4);
+                bytes.append(&mut gid.to_le_bytes().to_vec());
+            }
+            InfoZipUnixExtraField::Unknown { version, data } => {
+                let data_size: u16 = (1 + data.len()).try_into().unwrap();
+                bytes.append(&mut data_size.to_le_bytes().to_vec());
+                bytes.push(*version);
+                bytes.append(&mut data.clone());
+            }
+        }
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        match self {
+            InfoZipUnixExtraField::V1 { .. } => 15,
+            InfoZipUnixExtraField::Unknown { data, .. } => 5 + data.len(),
+        }
+    }
+}
+
+impl ExtraFieldAsBytes for NtfsExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::NTFS_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut 32u16.to_le_bytes().to_vec());
+        bytes.append(&mut [0; 4].to_vec());
+        bytes.append(&mut 1u16.to_le_bytes().to_vec());
+        bytes.append(&mut 24u16.to_le_bytes().to_vec());
+        bytes.append(&mut self.mtime.to_le_bytes().to_vec());
+        bytes.append(&mut self.atime.to_le_bytes().to_vec());
+        bytes.append(&mut self.ctime.to_le_bytes().to_vec());
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        36
+    }
+}
+
+impl ExtraFieldAsBytes for WinZipAesExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::WINZIP_AES_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut 7u16.to_le_bytes().to_vec());
+        bytes.append(&mut self.version.to_le_bytes().to_vec());
+        bytes.append(&mut self.vendor_id.to_le_bytes().to_vec());
+        bytes.push(self.strength);
+        bytes.append(&mut self.compression.to_le_bytes().to_vec());
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        11
+    }
+}
+
 /// Parse a zip64 extra field from bytes.
 /// The content of "data" should exclude the header.
 fn zip64_extended_information_field_from_bytes(
@@ -249,6 +325,81 @@ fn info_zip_unicode_path_extra_field_from_bytes(
     }
 }
 
+fn info_zip_unix_extra_field_from_bytes(
+    _header_id: HeaderId,
+    data_size: u16,
+    data: &[u8],
+) -> ZipResult<InfoZipUnixExtraField> {
+    let data = &data[..(data_size as usize).min(data.len())];
+    let version = *data.first().ok_or(ZipError::InfoZipUnixFieldIncomplete)?;
+
+    // Reads a length-prefixed little-endian ID, returning None if it's wider than 32 bits.
+    let read_id = |data: &[u8]| -> ZipResult<(Option<u32>, usize)> {
+        let size = *data.first().ok_or(ZipError::InfoZipUnixFieldIncomplete)? as usize;
+        let bytes = data.get(1..1 + size).ok_or(ZipError::InfoZipUnixFieldIncomplete)?;
+
+        let id = (size <= 4).then(|| bytes.iter().rev().fold(0u32, |id, byte| (id << 8) | u32::from(*byte)));
+        Ok((id, 1 + size))
+    };
+
+    if version == 1 {
+        let (uid, consumed) = read_id(&data[1..])?;
+        let (gid, _) = read_id(&data[1 + consumed..])?;
+
+        if let (Some(uid), Some(gid)) = (uid, gid) {
+            return Ok(InfoZipUnixExtraField::V1 { uid, gid });
+        }
+    }
+
+    Ok(InfoZipUnixExtraField::Unknown { version, data: data[1..].to_vec() })
+}
+
+/// Parses the times attribute of an NTFS extra field, returning None if the field doesn't hold one.
+fn ntfs_extra_field_from_bytes(data: &[u8]) -> ZipResult<Option<NtfsExtraField>> {
+    // Skip the four reserved bytes preceding the attributes.
+    let mut data = data.get(4..).ok_or(ZipError::NtfsFieldIncomplete)?;
+
+    while !data.is_empty() {
+        let header = data.get(..4).ok_or(ZipError::NtfsFieldIncomplete)?;
+        let tag = u16::from_le_bytes([header[0], header[1]]);
+        let size = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let attribute = data.get(4..4 + size).ok_or(ZipError::NtfsFieldIncomplete)?;
+
+        if tag == 1 {
+            let times = attribute.get(..24).ok_or(ZipError::NtfsFieldIncomplete)?;
+            let time = |index: usize| u64::from_le_bytes(times[index * 8..(index + 1) * 8].try_into().unwrap());
+            return Ok(Some(NtfsExtraField { mtime: time(0), atime: time(1), ctime: time(2) }));
+        }
+
+        data = &data[4 + size..];
+    }
+
+    Ok(None)
+}
+
+fn winzip_aes_extra_field_from_bytes(data: &[u8]) -> ZipResult<WinZipAesExtraField> {
+    if data.len() < 7 {
+        return Err(ZipError::WinZipAesFieldIncomplete);
+    }
+
+    let field = WinZipAesExtraField {
+        version: u16::from_le_bytes([data[0], data[1]]),
+        vendor_id: u16::from_le_bytes([data[2], data[3]]),
+        strength: data[4],
+        compression: u16::from_le_bytes([data[5], data[6]]),
+    };
+
+    // Only AE-1 and AE-2 are defined, with strengths mapping to 128, 192, and 256-bit keys.
+    if !matches!(field.version, 1 | 2) {
+        return Err(ZipError::InvalidAesVersion(field.version));
+    }
+    if !matches!(field.strength, 1..=3) {
+        return Err(ZipError::InvalidAesStrength(field.strength));
+    }
+
+    Ok(field)
+}
+
 pub(crate) fn extra_field_from_bytes(
     header_id: HeaderId,
     data_size: u16,
@@ -266,6 +417,14 @@ pub(crate) fn extra_field_from_bytes(
         HeaderId::INFO_ZIP_UNICODE_PATH_EXTRA_FIELD => Ok(ExtraField::InfoZipUnicodePath(
             info_zip_unicode_path_extra_field_from_bytes(header_id, data_size, data)?,
         )),
+        HeaderId::INFO_ZIP_UNIX_EXTRA_FIELD => {
+            Ok(ExtraField::InfoZipUnix(info_zip_unix_extra_field_from_bytes(header_id, data_size, data)?))
+        }
+        HeaderId::WINZIP_AES_EXTRA_FIELD => Ok(ExtraField::WinZipAes(winzip_aes_extra_field_from_bytes(data)?)),
+        HeaderId::NTFS_EXTRA_FIELD => match ntfs_extra_field_from_bytes(data)? {
+            Some(field) => Ok(ExtraField::Ntfs(field)),
+            None => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
+        },
         _ => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
     }
 }
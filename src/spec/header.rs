@@ -30,8 +30,12 @@ pub struct HeaderId(pub u16);
 
 impl HeaderId {
     pub const ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD: HeaderId = HeaderId(0x0001);
+    pub const NTFS_EXTRA_FIELD: HeaderId = HeaderId(0x000A);
+    pub const WINZIP_AES_EXTRA_FIELD: HeaderId = HeaderId(0x9901);
     pub const INFO_ZIP_UNICODE_COMMENT_EXTRA_FIELD: HeaderId = HeaderId(0x6375);
     pub const INFO_ZIP_UNICODE_PATH_EXTRA_FIELD: HeaderId = HeaderId(0x7075);
+    pub const INFO_ZIP_UNIX_EXTRA_FIELD: HeaderId = HeaderId(0x7875);
+    pub const ANDROID_ALIGNMENT_EXTRA_FIELD: HeaderId = HeaderId(0xD935);
 }
 
 impl From<u16> for HeaderId {
@@ -54,6 +58,9 @@ pub enum ExtraField {
     Zip64ExtendedInformation(Zip64ExtendedInformationExtraField),
     InfoZipUnicodeComment(InfoZipUnicodeCommentExtraField),
     InfoZipUnicodePath(InfoZipUnicodePathExtraField),
+    InfoZipUnix(InfoZipUnixExtraField),
+    Ntfs(NtfsExtraField),
+    WinZipAes(WinZipAesExtraField),
     Unknown(UnknownExtraField),
 }
 
@@ -75,7 +82,7 @@ impl Zip64ExtendedInformationExtraField {
         self.uncompressed_size.map(|_| 8).unwrap_or_default()
             + self.compressed_size.map(|_| 8).unwrap_or_default()
             + self.relative_header_offset.map(|_| 8).unwrap_or_default()
-            + self.disk_start_number.map(|_| 8).unwrap_or_default()
+            + self.disk_start_number.map(|_| 4).unwrap_or_default()
     }
 }
 
@@ -95,6 +102,50 @@ pub enum InfoZipUnicodePathExtraField {
     Unknown { version: u8, data: Vec<u8> },
 }
 
+/// Stores the UID and GID of the entry's owner, as written by Info-ZIP on Unix hosts ("ux", 0x7875).
+///
+/// Version 1 stores each ID as a variable-length little-endian integer. IDs wider than 32 bits are left unparsed.
+#[derive(Clone, Debug)]
+pub enum InfoZipUnixExtraField {
+    V1 { uid: u32, gid: u32 },
+    Unknown { version: u8, data: Vec<u8> },
+}
+
+/// Stores the last modification, access, and creation times of the entry as written by NTFS hosts (0x000A).
+///
+/// Each time is a Windows FILETIME value, ie. the number of 100 nanosecond intervals since 1601-01-01 00:00:00 UTC.
+/// Fields which don't hold these times (attribute tag 0x0001) are left unparsed.
+#[derive(Clone, Debug)]
+pub struct NtfsExtraField {
+    pub mtime: u64,
+    pub atime: u64,
+    pub ctime: u64,
+}
+
+/// Describes the WinZip AES encryption of an entry (0x9901).
+///
+/// The strength is stored as `1`, `2`, or `3` for 128, 192, and 256-bit keys respectively, and the compression method
+/// is that used for the entry's data prior to encryption. Fields holding any other strength, or a vendor version other
+/// than AE-1 (`1`) or AE-2 (`2`), are rejected when parsed.
+/// https://www.winzip.com/en/support/aes-encryption/
+#[derive(Clone, Debug)]
+pub struct WinZipAesExtraField {
+    pub version: u16,
+    pub vendor_id: u16,
+    pub strength: u8,
+    pub compression: u16,
+}
+
+impl WinZipAesExtraField {
+    /// Returns whether the entry's headers hold the CRC32 value of its data.
+    ///
+    /// AE-2 entries store a CRC32 value of zero (relying on the authentication code instead), so it shouldn't be
+    /// checked against the decrypted data.
+    pub fn stores_crc32(&self) -> bool {
+        self.version == 1
+    }
+}
+
 /// Represents any unparsed extra field.
 #[derive(Clone, Debug)]
 pub struct UnknownExtraField {
@@ -4,9 +4,11 @@
 pub(crate) mod attribute;
 pub(crate) mod compression;
 pub(crate) mod consts;
+pub(crate) mod encryption;
 pub(crate) mod extra_field;
 pub mod header;
 pub(crate) mod parse;
 pub(crate) mod version;
 
 pub use compression::Compression;
+pub use encryption::Encryption;
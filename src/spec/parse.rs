@@ -6,6 +6,7 @@ use crate::spec::header::{
     CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, GeneralPurposeFlag, HeaderId, LocalFileHeader,
     Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
 };
+use crate::spec::Compression;
 
 use futures_lite::io::{AsyncRead, AsyncReadExt};
 
@@ -15,7 +16,7 @@ impl LocalFileHeader {
         let mut cursor = 0;
 
         array_push!(array, cursor, self.version.to_le_bytes());
-        array_push!(array, cursor, self.flags.as_slice());
+        array_push!(array, cursor, flag_bytes(&self.flags, self.compression));
         array_push!(array, cursor, self.compression.to_le_bytes());
         array_push!(array, cursor, self.mod_time.to_le_bytes());
         array_push!(array, cursor, self.mod_date.to_le_bytes());
@@ -29,6 +30,18 @@ impl LocalFileHeader {
     }
 }
 
+/// Returns the general purpose flag's bytes for an entry of the given compression method.
+///
+/// LZMA data is always written with an end-of-stream marker, which is flagged via bit 1 for such entries.
+fn flag_bytes(flags: &GeneralPurposeFlag, compression: u16) -> [u8; 2] {
+    let mut value = u16::from_le_bytes(flags.as_slice());
+    if Compression::try_from(compression).is_ok_and(Compression::writes_eos_marker) {
+        value |= 0x2;
+    }
+
+    value.to_le_bytes()
+}
+
 impl GeneralPurposeFlag {
     pub fn as_slice(&self) -> [u8; 2] {
         let encrypted: u16 = match self.encrypted {
@@ -55,7 +68,7 @@ impl CentralDirectoryRecord {
 
         array_push!(array, cursor, self.v_made_by.to_le_bytes());
         array_push!(array, cursor, self.v_needed.to_le_bytes());
-        array_push!(array, cursor, self.flags.as_slice());
+        array_push!(array, cursor, flag_bytes(&self.flags, self.compression));
         array_push!(array, cursor, self.compression.to_le_bytes());
         array_push!(array, cursor, self.mod_time.to_le_bytes());
         array_push!(array, cursor, self.mod_date.to_le_bytes());
@@ -185,8 +198,7 @@ impl From<[u8; 16]> for Zip64EndOfCentralDirectoryLocator {
 
 impl LocalFileHeader {
     pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<LocalFileHeader> {
-        let mut buffer: [u8; 26] = [0; 26];
-        reader.read_exact(&mut buffer).await?;
+        let buffer: [u8; 26] = crate::utils::read_header(reader).await?;
         Ok(LocalFileHeader::from(buffer))
     }
 }
@@ -267,7 +279,7 @@ impl Zip64EndOfCentralDirectoryLocator {
 pub fn parse_extra_fields(data: Vec<u8>, uncompressed_size: u32, compressed_size: u32) -> Result<Vec<ExtraField>> {
     let mut cursor = 0;
     let mut extra_fields = Vec::new();
-    while cursor + 4 < data.len() {
+    while cursor + 4 <= data.len() {
         let header_id: HeaderId = u16::from_le_bytes(data[cursor..cursor + 2].try_into().unwrap()).into();
         let field_size = u16::from_le_bytes(data[cursor + 2..cursor + 4].try_into().unwrap());
         if cursor + 4 + field_size as usize > data.len() {
@@ -2,6 +2,7 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
 #[cfg(any(
     feature = "deflate",
     feature = "bzip2",
@@ -35,8 +36,23 @@ pub fn as_needed_to_extract(entry: &ZipEntry) -> u16 {
     version
 }
 
+/// Returns the version needed to extract the entry, honouring any minimum version it sets.
+pub(crate) fn needed_to_extract(entry: &ZipEntry) -> Result<u16> {
+    let required = as_needed_to_extract(entry);
+
+    match entry.min_version {
+        Some(version) if version < required => Err(ZipError::InvalidVersion(version, required)),
+        Some(version) => Ok(version),
+        None => Ok(required),
+    }
+}
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#442
-pub fn as_made_by() -> u16 {
-    // Default to UNIX mapping for the moment.
-    3 << 8 | SPEC_VERSION_MADE_BY
+pub fn as_made_by(entry: &ZipEntry) -> u16 {
+    made_by(entry, SPEC_VERSION_MADE_BY as u8)
+}
+
+/// Returns the version made by the entry, with the provided specification version as its lower byte.
+pub(crate) fn made_by(entry: &ZipEntry, spec_version: u8) -> u16 {
+    u16::from(entry.host_os()) << 8 | u16::from(spec_version)
 }
@@ -10,6 +10,28 @@ pub enum StringEncoding {
     Raw,
 }
 
+/// The encoding which was used to decode an entry's filename.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameEncoding {
+    /// The filename consists solely of ASCII characters, and so is identical in both UTF-8 and CP437.
+    Ascii,
+    /// The filename was decoded as UTF-8.
+    Utf8,
+    /// The filename was decoded as CP437 (IBM Code Page 437).
+    Cp437,
+}
+
+impl NameEncoding {
+    /// Returns the encoding a filename will be written with, and so the encoding it'll be decoded with when read.
+    pub(crate) fn of(filename: &ZipString) -> Self {
+        match filename.encoding() {
+            StringEncoding::Utf8 if filename.as_bytes().is_ascii() => NameEncoding::Ascii,
+            StringEncoding::Utf8 => NameEncoding::Utf8,
+            StringEncoding::Raw => NameEncoding::Cp437,
+        }
+    }
+}
+
 /// A string wrapper for handling different encodings.
 #[derive(Debug, Clone)]
 pub struct ZipString {
@@ -110,3 +132,26 @@ impl From<&str> for ZipString {
         Self { encoding: StringEncoding::Utf8, raw: value.as_bytes().to_vec(), alternative: None }
     }
 }
+
+/// The characters represented by bytes `0x80..=0xFF` within CP437 (IBM Code Page 437).
+#[rustfmt::skip]
+const CP437_UPPER: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Decodes CP437 (IBM Code Page 437) bytes into an owned string.
+///
+/// Bytes below `0x80` are interpreted as ASCII, matching how the ZIP specification expects CP437 to be treated.
+pub(crate) fn decode_cp437(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| if byte.is_ascii() { *byte as char } else { CP437_UPPER[(*byte - 0x80) as usize] })
+        .collect()
+}
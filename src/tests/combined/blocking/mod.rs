@@ -0,0 +1,27 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::blocking::{ZipFileReader, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::{Cursor, Read};
+
+#[test]
+fn blocking_write_and_read() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").unwrap();
+    let data = writer.close().unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(data)).unwrap();
+    let names: Vec<_> = reader.entries().iter().map(|entry| entry.filename().as_str().unwrap().to_owned()).collect();
+    assert_eq!(names, ["foo.txt", "bar.txt"]);
+
+    let mut buffer = Vec::new();
+    let mut entry = reader.reader_with_entry(0).unwrap();
+    entry.read_to_end(&mut buffer).unwrap();
+    assert_eq!(entry.entry().filename().as_str().unwrap(), "foo.txt");
+    assert_eq!(buffer, b"foo");
+
+    assert_eq!(reader.reader_with_entry(1).unwrap().read_to_vec().unwrap(), b"bar");
+}
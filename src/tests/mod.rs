@@ -6,11 +6,92 @@ pub(crate) mod read;
 pub(crate) mod spec;
 pub(crate) mod write;
 
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::{Cell, RefCell};
 use std::sync::Once;
 static ENV_LOGGER: Once = Once::new();
 
+/// A logger which forwards to env_logger, and also captures warnings logged by the current thread.
+struct CapturingLogger(env_logger::Logger);
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn || self.0.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.level() <= log::Level::Warn {
+            let _ = WARNINGS.try_with(|warnings| warnings.borrow_mut().push(record.args().to_string()));
+        }
+
+        self.0.log(record);
+    }
+
+    fn flush(&self) {
+        self.0.flush();
+    }
+}
+
 /// Initialize the env logger for any tests that require it.
 /// Safe to call multiple times.
 fn init_logger() {
-    ENV_LOGGER.call_once(|| env_logger::Builder::from_default_env().format_module_path(true).init());
+    ENV_LOGGER.call_once(|| {
+        let logger = env_logger::Builder::from_default_env().format_module_path(true).build();
+        log::set_max_level(logger.filter().max(log::LevelFilter::Warn));
+        log::set_boxed_logger(Box::new(CapturingLogger(logger))).unwrap();
+    });
+}
+
+/// Returns the warnings logged by the current thread since the last call, and clears them.
+///
+/// Requires [`init_logger()`] to have been called.
+#[cfg(feature = "log")]
+pub(crate) fn take_warnings() -> Vec<String> {
+    WARNINGS.with(|warnings| warnings.take())
+}
+
+/// An allocator which counts the allocations (incl. reallocations) made by the current thread, and tracks the largest.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static LARGEST: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count(size: usize) {
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    let _ = LARGEST.try_with(|largest| largest.set(largest.get().max(size)));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the number of allocations made by the current thread so far.
+pub(crate) fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Returns the size of the largest allocation made by the current thread since the last call, and resets it.
+pub(crate) fn take_largest_allocation() -> usize {
+    LARGEST.with(|largest| largest.replace(0))
 }
@@ -0,0 +1,30 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::async_std::read::fs::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+#[test]
+fn read_concurrently_with_async_std() {
+    ::async_std::task::block_on(async {
+        let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+        for (name, data) in [("first.txt", b"first"), ("second.txt", b"other")] {
+            writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), data).await.unwrap();
+        }
+
+        let path = std::env::temp_dir().join(format!("async-zip-async-std-{}.zip", std::process::id()));
+        ::async_std::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+        let reader = ZipFileReader::new(&path).await.unwrap();
+        let reader = &reader;
+
+        let read = |index| async move { reader.reader_with_entry(index).await?.read_to_vec().await };
+        let (first, second) = futures_lite::future::zip(read(0), read(1)).await;
+        assert_eq!(first.unwrap(), b"first");
+        assert_eq!(second.unwrap(), b"other");
+
+        assert!(matches!(reader.reader_without_entry(2).await, Err(ZipError::EntryIndexOutOfBounds)));
+        ::async_std::fs::remove_file(&path).await.unwrap();
+    });
+}
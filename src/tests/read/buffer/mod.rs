@@ -0,0 +1,25 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::tokio::read::fs::ZipFileReader;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn reader_capacities_agree() {
+    let data: Vec<u8> = (0..2 * 1024 * 1024u32).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8 % 16).collect();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("data.bin".into(), Compression::Deflate), &data).await.unwrap();
+
+    let path = std::env::temp_dir().join(format!("async-zip-buffer-{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+    let reader = ZipFileReader::new(&path).await.unwrap();
+
+    for capacity in [4 * 1024, 1024 * 1024] {
+        let mut entry_reader = reader.reader_with_entry_and_capacity(0, capacity).await.unwrap();
+        assert_eq!(entry_reader.read_to_vec().await.unwrap(), data);
+    }
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
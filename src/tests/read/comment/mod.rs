@@ -0,0 +1,98 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::EOCDR_SIGNATURE;
+
+/// Builds an empty ZIP file consisting of only an EOCDR and the provided comment.
+fn empty_with_comment(comment: &[u8]) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&EOCDR_SIGNATURE.to_le_bytes());
+    data.extend_from_slice(&[0; 16]);
+    data.extend_from_slice(&(comment.len() as u16).to_le_bytes());
+    data.extend_from_slice(comment);
+    data
+}
+
+#[tokio::test]
+async fn binary_comment_bytes_mem() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let comment = [0xFF, 0x00, 0xFE, 0x80, b'z', b'i', b'p', 0xC0];
+    let reader = ZipFileReader::new(empty_with_comment(&comment)).await.unwrap();
+
+    assert_eq!(reader.comment_bytes(), Some(comment.as_slice()));
+    assert!(reader.file().comment().as_str().is_err());
+}
+
+#[tokio::test]
+async fn binary_comment_bytes_seek() {
+    use crate::base::read::seek::ZipFileReader;
+    use futures_lite::io::{BufReader, Cursor};
+
+    let comment = [0x00, 0x01, 0x02, 0x9F];
+    let data = empty_with_comment(&comment);
+    let reader = ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap();
+
+    assert_eq!(reader.comment_bytes(), Some(comment.as_slice()));
+}
+
+#[tokio::test]
+async fn no_comment_bytes() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let reader = ZipFileReader::new(empty_with_comment(&[])).await.unwrap();
+    assert_eq!(reader.comment_bytes(), None);
+}
+
+#[tokio::test]
+async fn comment_round_trip() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::base::write::ZipFileWriter;
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.comment(String::from("archive comment ✓"));
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    assert_eq!(reader.comment().as_deref(), Some("archive comment ✓"));
+}
+
+#[tokio::test]
+async fn comment_lossy() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let reader = ZipFileReader::new(empty_with_comment(&[b'z', 0xFF, b'p'])).await.unwrap();
+    assert_eq!(reader.comment().as_deref(), Some("z\u{FFFD}p"));
+
+    let reader = ZipFileReader::new(empty_with_comment(&[])).await.unwrap();
+    assert_eq!(reader.comment(), None);
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn comment_round_trip_fs() {
+    use crate::base::write::ZipFileWriter;
+    use crate::tokio::read::fs::ZipFileReader;
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.comment(String::from("archive comment"));
+
+    let path = std::env::temp_dir().join(format!("async-zip-comment-{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    assert_eq!(reader.comment().as_deref(), Some("archive comment"));
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn comment_longer_than_data() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let mut data = empty_with_comment(b"short");
+    let length = data.len();
+    data[length - 7..length - 5].copy_from_slice(&100u16.to_le_bytes());
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.comment_bytes(), Some(b"short".as_slice()));
+}
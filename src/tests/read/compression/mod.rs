@@ -15,12 +15,120 @@ compressed_test_helper!(bz_test, Compression::Bz, "foo bar", include_bytes!("bzi
 #[cfg(feature = "lzma")]
 compressed_test_helper!(lzma_test, Compression::Lzma, "foo bar", include_bytes!("lzma.data"));
 
+// Data framed with the `.lzma` header, as written by earlier versions of this crate.
+#[cfg(feature = "lzma")]
+compressed_test_helper!(lzma_legacy_test, Compression::Lzma, "foo bar", include_bytes!("lzma_legacy.data"));
+
 #[cfg(feature = "zstd")]
 compressed_test_helper!(zstd_test, Compression::Zstd, "foo bar", include_bytes!("zstd.data"));
 
 #[cfg(feature = "xz")]
 compressed_test_helper!(xz_test, Compression::Xz, "foo bar", include_bytes!("xz.data"));
 
+// A literal followed by a 1000-byte match, whose length is encoded via Deflate64's 16 extra bits for length code 285.
+#[cfg(feature = "deflate64")]
+compressed_test_helper!(deflate64_test, Compression::Deflate64, "a".repeat(1001), include_bytes!("deflate64.data"));
+
+/// Returns a ZIP file holding the Deflate64 test data as a single entry using compression method 9.
+async fn deflate64_archive() -> Vec<u8> {
+    use crate::base::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    // Written as a raw copy, so the method is stored without needing to be supported.
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("a.txt".into(), Compression::Unsupported(9));
+    writer.copy_entry_raw(entry, 0x7124D723, 1001, include_bytes!("deflate64.data")).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[cfg(feature = "deflate64")]
+#[tokio::test]
+async fn deflate64_entry() {
+    let reader = crate::base::read::mem::ZipFileReader::new(deflate64_archive().await).await.unwrap();
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Deflate64);
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), b"a".repeat(1001));
+}
+
+#[cfg(not(feature = "deflate64"))]
+#[tokio::test]
+async fn deflate64_entry_disabled() {
+    use crate::error::ZipError;
+
+    let reader = crate::base::read::mem::ZipFileReader::new(deflate64_archive().await).await.unwrap();
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Unsupported(9));
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::CompressionNotSupported(9))));
+}
+
+#[tokio::test]
+async fn raw_compression_method() {
+    use crate::base::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    // LZMA (method 14) is only mapped to a known method when its feature is enabled.
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("a.txt".into(), Compression::Unsupported(14));
+    writer.copy_entry_raw(entry, 0, 0, b"not really lzma").await.unwrap();
+
+    let reader = crate::base::read::mem::ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.compression_method_raw(), 14);
+
+    #[cfg(feature = "lzma")]
+    assert_eq!(entry.compression(), Compression::Lzma);
+    #[cfg(not(feature = "lzma"))]
+    assert_eq!(entry.compression(), Compression::Unsupported(14));
+}
+
+/// Reads an LZMA entry written by Python's `zipfile`, which frames its data as 7-Zip does and sets the EOS flag.
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn lzma_foreign_entry() {
+    let reader = crate::base::read::mem::ZipFileReader::new(include_bytes!("lzma.zip").to_vec()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Lzma);
+
+    let data = reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap();
+    assert_eq!(data, "The quick brown fox jumps over the lazy dog.\n".repeat(40).as_bytes());
+}
+
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn lzma_round_trip() {
+    use crate::base::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+    use futures_lite::io::AsyncWriteExt;
+
+    let data = "lorem ipsum dolor sit amet ".repeat(100);
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("whole.txt".into(), Compression::Lzma);
+    writer.write_entry_whole(builder, data.as_bytes()).await.unwrap();
+
+    let builder = ZipEntryBuilder::new("stream.txt".into(), Compression::Lzma);
+    let mut entry_writer = writer.write_entry_stream(builder).await.unwrap();
+    entry_writer.write_all(data.as_bytes()).await.unwrap();
+    entry_writer.close().await.unwrap();
+    let archive = writer.close().await.unwrap();
+
+    // The general purpose flag marks the end-of-stream marker, and the data is framed with the LZMA SDK version and
+    // the size of the properties.
+    assert_eq!(u16::from_le_bytes([archive[6], archive[7]]) & 0x2, 0x2);
+    let data_offset = 30 + "whole.txt".len();
+    assert_eq!(archive[data_offset + 2..data_offset + 4], [5, 0]);
+
+    let reader = crate::base::read::mem::ZipFileReader::new(archive.clone()).await.unwrap();
+    for index in 0..2 {
+        assert_eq!(reader.reader_with_entry(index).await.unwrap().read_to_vec().await.unwrap(), data.as_bytes());
+    }
+
+    // Streaming readers don't know the size of entries written with a data descriptor, so rely on the marker.
+    let mut stream = crate::base::read::stream::ZipFileReader::new(archive.as_slice());
+    while let Some(mut reading) = stream.next_with_entry().await.unwrap() {
+        let mut buffer = Vec::new();
+        futures_lite::io::AsyncReadExt::read_to_end(reading.reader_mut(), &mut buffer).await.unwrap();
+        assert_eq!(buffer, data.as_bytes());
+        stream = reading.skip().await.unwrap();
+    }
+}
+
 /// A helper macro for generating a CompressedReader test using a specific compression method.
 macro_rules! compressed_test_helper {
     ($name:ident, $type:expr, $data_raw:expr, $data:expr) => {
@@ -33,7 +141,7 @@ macro_rules! compressed_test_helper {
             let data_raw = $data_raw;
 
             let cursor = Cursor::new(data);
-            let mut reader = CompressedReader::new(cursor, $type);
+            let mut reader = CompressedReader::new(cursor, $type).unwrap();
 
             let mut read_data = String::new();
             reader.read_to_string(&mut read_data).await.expect("read into CompressedReader failed");
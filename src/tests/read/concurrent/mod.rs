@@ -0,0 +1,110 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::LFH_LENGTH;
+use crate::tokio::read::fs::ZipFileReader;
+use crate::{Compression, ZipEntryBuilder};
+
+async fn write_archive(name: &str, entries: &[(String, Vec<u8>)]) -> std::path::PathBuf {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (filename, data) in entries {
+        let builder = ZipEntryBuilder::new(filename.clone().into(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.unwrap();
+    }
+
+    let path = std::env::temp_dir().join(format!("async-zip-{name}-{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+    path
+}
+
+#[tokio::test]
+async fn concurrent_matches_sequential() {
+    let entries: Vec<(String, Vec<u8>)> = (0..12u8)
+        .map(|i| (format!("entry-{i}.bin"), (0..1000 * i as usize).map(|j| (j % 7) as u8 + i).collect()))
+        .collect();
+
+    let path = write_archive("concurrent", &entries).await;
+    let reader = ZipFileReader::new(&path).await.unwrap();
+
+    let mut sequential = Vec::new();
+    for index in 0..reader.file().entries().len() {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        let filename = entry.entry().filename().as_str().unwrap().to_owned();
+        sequential.push((filename, entry.read_to_vec().await.unwrap()));
+    }
+
+    for limit in [1, 3, 64] {
+        assert_eq!(reader.read_all_concurrent(limit).await.unwrap(), sequential);
+    }
+    assert_eq!(sequential, entries);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn concurrent_crc_mismatch() {
+    let entries = vec![(String::from("a.txt"), b"first".to_vec()), (String::from("b.txt"), b"second".to_vec())];
+    let path = write_archive("concurrent-crc", &entries).await;
+
+    // Corrupt the first entry's data, leaving its CRC32 value stale.
+    let mut data = tokio::fs::read(&path).await.unwrap();
+    data[4 + LFH_LENGTH + "a.txt".len()] ^= 0xFF;
+    tokio::fs::write(&path, data).await.unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    assert!(matches!(reader.read_all_concurrent(2).await, Err(ZipError::CRC32CheckError)));
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn lenient_skips_corrupt_entry() {
+    let entries: Vec<(String, Vec<u8>)> =
+        ["a.txt", "b.txt", "c.txt"].into_iter().map(|name| (name.to_owned(), name.repeat(4).into_bytes())).collect();
+    let path = write_archive("concurrent-lenient", &entries).await;
+
+    // Corrupt the second entry's data, leaving its CRC32 value stale.
+    let mut data = tokio::fs::read(&path).await.unwrap();
+    let first_entry_length = 4 + LFH_LENGTH + "a.txt".len() + entries[0].1.len();
+    data[first_entry_length + 4 + LFH_LENGTH + "b.txt".len()] ^= 0xFF;
+    tokio::fs::write(&path, data).await.unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    let results = reader.read_all_concurrent_lenient(2).await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].0, "a.txt");
+    assert_eq!(results[0].1.as_ref().unwrap(), &entries[0].1);
+    assert_eq!(results[1].0, "b.txt");
+    assert!(matches!(results[1].1, Err(ZipError::CRC32CheckError)));
+    assert_eq!(results[2].0, "c.txt");
+    assert_eq!(results[2].1.as_ref().unwrap(), &entries[2].1);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn concurrent_non_utf8_filename() {
+    use crate::{StringEncoding, ZipString};
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let filename = ZipString::new(b"caf\x82.txt".to_vec(), StringEncoding::Raw);
+    writer.write_entry_whole(ZipEntryBuilder::new(filename, Compression::Stored), b"data").await.unwrap();
+
+    let mut data = writer.close().await.unwrap();
+
+    // Claim the filename is UTF-8 encoded, so that it's read as raw bytes rather than decoded as CP437.
+    let central_offset = data.windows(4).position(|window| window == [0x50, 0x4B, 0x01, 0x02]).unwrap();
+    data[7] |= 0x08;
+    data[central_offset + 9] |= 0x08;
+
+    let path = std::env::temp_dir().join(format!("async-zip-concurrent-raw-{}.zip", std::process::id()));
+    tokio::fs::write(&path, data).await.unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    assert_eq!(reader.read_all_concurrent(2).await.unwrap(), [(String::from("caf\u{FFFD}.txt"), b"data".to_vec())]);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
@@ -0,0 +1,244 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek;
+use crate::base::read::stream::{read_data_descriptor, ZipFileReader};
+use crate::base::write::{compress, crc32, ZipFileWriter};
+use crate::spec::consts::{CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, EOCDR_SIGNATURE, LFH_SIGNATURE};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncBufReadExt, BufReader, Cursor};
+
+const ENTRIES: [(&str, &[u8], bool); 2] =
+    [("first.txt", b"the first entry's data", true), ("second.txt", b"the second entry's data", false)];
+
+/// Builds an archive whose entries are each followed by a data descriptor, either with or without its signature
+/// (as per `ENTRIES`), and either with or without its sizes.
+async fn archive(sizes: bool) -> Vec<u8> {
+    let mut archive = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for (name, data, signature) in ENTRIES {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Deflate).build();
+        let compressed = compress(&entry, data).await;
+        let crc = crc32(data);
+        let offset = archive.len() as u32;
+
+        archive.extend_from_slice(&LFH_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&[20, 0, 0x08, 0, 8, 0, 0, 0, 0, 0]);
+        archive.extend_from_slice(&[0; 12]);
+        archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&[0, 0]);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(&compressed);
+        if signature {
+            archive.extend_from_slice(&DATA_DESCRIPTOR_SIGNATURE.to_le_bytes());
+        }
+        archive.extend_from_slice(&crc.to_le_bytes());
+        if sizes {
+            archive.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+            archive.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        }
+
+        central_directory.extend_from_slice(&CDH_SIGNATURE.to_le_bytes());
+        central_directory.extend_from_slice(&[20, 0, 20, 0, 0x08, 0, 8, 0, 0, 0, 0, 0]);
+        central_directory.extend_from_slice(&crc.to_le_bytes());
+        central_directory.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central_directory.extend_from_slice(&[0; 12]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+    }
+
+    let offset = archive.len() as u32;
+    archive.extend_from_slice(&central_directory);
+    archive.extend_from_slice(&EOCDR_SIGNATURE.to_le_bytes());
+    archive.extend_from_slice(&[0, 0, 0, 0, 2, 0, 2, 0]);
+    archive.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    archive.extend_from_slice(&offset.to_le_bytes());
+    archive.extend_from_slice(&[0, 0]);
+    archive
+}
+
+#[tokio::test]
+async fn crc_only_descriptor_parse() {
+    let mut data = 0xDEADBEEFu32.to_le_bytes().to_vec();
+    data.extend_from_slice(&LFH_SIGNATURE.to_le_bytes());
+
+    let mut reader = BufReader::new(Cursor::new(data));
+    let descriptor = read_data_descriptor(&mut reader, false).await.unwrap();
+    assert_eq!((descriptor.crc, descriptor.sizes), (0xDEADBEEF, None));
+    assert_eq!(reader.fill_buf().await.unwrap(), LFH_SIGNATURE.to_le_bytes());
+}
+
+async fn stream_entries(sizes: bool) {
+    let mut reader = ZipFileReader::new(Cursor::new(archive(sizes).await));
+
+    for (name, data, _) in ENTRIES {
+        let mut entry = reader.next_with_entry().await.unwrap().unwrap();
+        assert_eq!(entry.reader().entry().filename().as_str().unwrap(), name);
+
+        let mut buffer = Vec::new();
+        futures_lite::io::AsyncReadExt::read_to_end(entry.reader_mut(), &mut buffer).await.unwrap();
+        assert_eq!(buffer, data);
+
+        reader = entry.done().await.unwrap();
+    }
+
+    assert!(reader.next_with_entry().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn crc_only_descriptor_stream() {
+    stream_entries(false).await;
+}
+
+#[tokio::test]
+async fn descriptor_straddling_buffer_stream() {
+    // Small buffers split the signature following each descriptor, so detecting it requires consuming part of it.
+    for (sizes, capacity) in [false, true].into_iter().flat_map(|sizes| (1..=16).map(move |capacity| (sizes, capacity)))
+    {
+        let source = BufReader::with_capacity(capacity, Cursor::new(archive(sizes).await));
+        let mut reader = ZipFileReader::new(source);
+
+        for (name, data, _) in ENTRIES {
+            let mut entry = reader.next_with_entry().await.unwrap().unwrap();
+            assert_eq!(entry.reader().entry().filename().as_str().unwrap(), name);
+
+            let mut buffer = Vec::new();
+            futures_lite::io::AsyncReadExt::read_to_end(entry.reader_mut(), &mut buffer).await.unwrap();
+            assert_eq!(buffer, data, "capacity {capacity}");
+
+            reader = entry.done().await.unwrap();
+        }
+
+        assert!(reader.next_with_entry().await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn descriptor_with_and_without_signature_stream() {
+    stream_entries(true).await;
+}
+
+#[tokio::test]
+async fn descriptor_without_signature_parse() {
+    let mut data = 0xDEADBEEFu32.to_le_bytes().to_vec();
+    data.extend_from_slice(&[10, 0, 0, 0, 20, 0, 0, 0]);
+    data.extend_from_slice(&CDH_SIGNATURE.to_le_bytes());
+
+    let mut reader = BufReader::new(Cursor::new(data));
+    let descriptor = read_data_descriptor(&mut reader, false).await.unwrap();
+    assert_eq!((descriptor.crc, descriptor.sizes), (0xDEADBEEF, Some((10, 20))));
+    assert_eq!(reader.fill_buf().await.unwrap(), CDH_SIGNATURE.to_le_bytes());
+}
+
+#[tokio::test]
+async fn zip64_descriptor_parse() {
+    // Both the 64-bit sizes required by the specification and the 32-bit sizes emitted by some writers.
+    for sizes in
+        [[10u64.to_le_bytes(), 20u64.to_le_bytes()].concat(), [10u32.to_le_bytes(), 20u32.to_le_bytes()].concat()]
+    {
+        let mut data = DATA_DESCRIPTOR_SIGNATURE.to_le_bytes().to_vec();
+        data.extend_from_slice(&0xDEADBEEFu32.to_le_bytes());
+        data.extend_from_slice(&sizes);
+        data.extend_from_slice(&LFH_SIGNATURE.to_le_bytes());
+
+        let mut reader = BufReader::new(Cursor::new(data));
+        let descriptor = read_data_descriptor(&mut reader, true).await.unwrap();
+        assert_eq!((descriptor.crc, descriptor.sizes), (0xDEADBEEF, Some((10, 20))));
+        assert_eq!(reader.fill_buf().await.unwrap(), LFH_SIGNATURE.to_le_bytes());
+    }
+}
+
+#[tokio::test]
+async fn zip64_descriptor_stream() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, data, _) in ENTRIES {
+        let mut entry =
+            writer.write_entry_stream(ZipEntryBuilder::new(name.into(), Compression::Deflate)).await.unwrap();
+        futures_lite::io::AsyncWriteExt::write_all(&mut entry, data).await.unwrap();
+        entry.close().await.unwrap();
+    }
+    let data = writer.close().await.unwrap();
+
+    // The stream writer always emits a zip64 extended information field, and so 64-bit descriptor sizes.
+    let descriptor = data.windows(4).position(|window| window == DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).unwrap();
+    let uncompressed = u64::from_le_bytes(data[descriptor + 16..descriptor + 24].try_into().unwrap());
+    assert_eq!(uncompressed, ENTRIES[0].1.len() as u64);
+
+    let mut reader = ZipFileReader::new(Cursor::new(data));
+    for (_, data, _) in ENTRIES {
+        let mut entry = reader.next_with_entry().await.unwrap().unwrap();
+        let mut buffer = Vec::new();
+        futures_lite::io::AsyncReadExt::read_to_end(entry.reader_mut(), &mut buffer).await.unwrap();
+        assert_eq!(buffer, data);
+        reader = entry.done().await.unwrap();
+    }
+    assert!(reader.next_with_entry().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn descriptor_with_local_sizes() {
+    // Redundantly populate each local file header's CRC32 value and sizes from its central directory record.
+    let mut data = archive(true).await;
+    let file = seek::ZipFileReader::new(BufReader::new(Cursor::new(data.clone()))).await.unwrap().file().clone();
+    for entry in file.entries() {
+        let header = entry.header_offset() as usize;
+        data[header + 14..header + 18].copy_from_slice(&entry.crc32().to_le_bytes());
+        data[header + 18..header + 22].copy_from_slice(&(entry.compressed_size() as u32).to_le_bytes());
+        data[header + 22..header + 26].copy_from_slice(&(entry.uncompressed_size() as u32).to_le_bytes());
+    }
+
+    let mut seek_reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(data.clone()))).await.unwrap();
+    let mut stream_reader = ZipFileReader::new(Cursor::new(data));
+
+    for (index, (_, expected, _)) in ENTRIES.iter().enumerate() {
+        let mut seeked = Vec::new();
+        seek_reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut seeked).await.unwrap();
+
+        let mut entry = stream_reader.next_with_entry().await.unwrap().unwrap();
+        let mut streamed = Vec::new();
+        entry.reader_mut().read_to_end_checked(&mut streamed).await.unwrap();
+        stream_reader = entry.done().await.unwrap();
+
+        assert_eq!(seeked, streamed);
+        assert_eq!(&streamed, expected);
+    }
+
+    assert!(stream_reader.next_with_entry().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn descriptor_seek() {
+    for sizes in [false, true] {
+        let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(archive(sizes).await))).await.unwrap();
+
+        for (index, (_, data, _)) in ENTRIES.iter().enumerate() {
+            assert_eq!(reader.file().entries()[index].crc32(), crc32(data));
+
+            let mut buffer = Vec::new();
+            reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+            assert_eq!(&buffer, data);
+        }
+    }
+}
+
+#[tokio::test]
+async fn truncated_descriptor_stream() {
+    let data = archive(true).await;
+    let descriptor = data.windows(4).position(|window| window == DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).unwrap();
+
+    // Missing entirely, and cut short within its CRC32 value.
+    for length in [descriptor, descriptor + 6] {
+        let reader = ZipFileReader::new(Cursor::new(data[..length].to_vec()));
+        let mut entry = reader.next_with_entry().await.unwrap().unwrap();
+
+        let mut buffer = Vec::new();
+        futures_lite::io::AsyncReadExt::read_to_end(entry.reader_mut(), &mut buffer).await.unwrap();
+        assert_eq!(buffer, ENTRIES[0].1);
+
+        assert!(matches!(entry.done().await, Err(crate::error::ZipError::UnexpectedEof)));
+    }
+}
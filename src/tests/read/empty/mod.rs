@@ -0,0 +1,40 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{mem, seek};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn zero_entry_archive() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.comment(String::from("nothing here"));
+    let data = writer.close().await.unwrap();
+
+    let reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 0);
+    assert_eq!(reader.comment().as_deref(), Some("nothing here"));
+
+    let reader = seek::ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 0);
+}
+
+#[tokio::test]
+async fn zero_entry_archive_without_comment() {
+    let data = ZipFileWriter::new(Vec::<u8>::new()).close().await.unwrap();
+    let reader = mem::ZipFileReader::new(data).await.unwrap();
+
+    assert_eq!(reader.file().entries().len(), 0);
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
+
+#[tokio::test]
+async fn empty_file() {
+    assert!(matches!(mem::ZipFileReader::new(Vec::new()).await, Err(ZipError::UnexpectedEof)));
+    assert!(matches!(seek::ZipFileReader::new(Cursor::new(Vec::new())).await, Err(ZipError::UnexpectedEof)));
+
+    // Shorter than an EOCDR.
+    assert!(matches!(mem::ZipFileReader::new(vec![0x50, 0x4b, 0x05, 0x06]).await, Err(ZipError::UnexpectedEof)));
+}
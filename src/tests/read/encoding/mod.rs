@@ -0,0 +1,74 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::header::ExtraField;
+use crate::{Compression, NameEncoding, StringEncoding, ZipEntry, ZipEntryBuilder, ZipString};
+
+/// Writes a single empty entry with the provided filename and returns the entry read back from the resulting file.
+async fn round_trip(filename: ZipString) -> ZipEntry {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new(filename, Compression::Stored), &[]).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    reader.file().entries()[0].entry.clone()
+}
+
+#[tokio::test]
+async fn name_encoding_ascii() {
+    let entry = round_trip("file.txt".into()).await;
+
+    assert_eq!(entry.name_encoding(), NameEncoding::Ascii);
+    assert_eq!(entry.filename().as_str().unwrap(), "file.txt");
+}
+
+#[tokio::test]
+async fn name_encoding_cp437() {
+    let entry = round_trip(ZipString::new(b"caf\x82.txt".to_vec(), StringEncoding::Raw)).await;
+
+    assert_eq!(entry.name_encoding(), NameEncoding::Cp437);
+    assert_eq!(entry.filename().as_str().unwrap(), "caf\u{e9}.txt");
+    assert_eq!(entry.filename().alternative(), Some(b"caf\x82.txt".as_ref()));
+}
+
+#[tokio::test]
+async fn name_encoding_utf8_flagged() {
+    let entry = round_trip("\u{444}\u{430}\u{439}\u{43b}.txt".into()).await;
+
+    assert_eq!(entry.name_encoding(), NameEncoding::Utf8);
+    assert_eq!(entry.filename().as_str().unwrap(), "\u{444}\u{430}\u{439}\u{43b}.txt");
+}
+
+#[tokio::test]
+async fn unicode_path_preferred_over_cp437() {
+    let builder = ZipEntryBuilder::from_raw_name(b"caf\x82.txt".to_vec(), Compression::Stored)
+        .unicode_path("\u{30ab}\u{30d5}\u{30a7}.txt");
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, &[]).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert!(entry.extra_fields().iter().any(|field| matches!(field, ExtraField::InfoZipUnicodePath(_))));
+    assert_eq!(entry.name_encoding(), NameEncoding::Utf8);
+    assert_eq!(entry.filename().as_str().unwrap(), "\u{30ab}\u{30d5}\u{30a7}.txt");
+    assert_eq!(entry.raw_name_bytes(), b"caf\x82.txt");
+}
+
+#[tokio::test]
+async fn cp437_filename_seek() {
+    use crate::base::read::seek::ZipFileReader;
+    use futures_lite::io::{BufReader, Cursor};
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let filename = ZipString::new(b"r\x82sum\x82.txt".to_vec(), StringEncoding::Raw);
+    writer.write_entry_whole(ZipEntryBuilder::new(filename, Compression::Stored), &[]).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.filename().as_str().unwrap(), "r\u{e9}sum\u{e9}.txt");
+    assert_eq!(entry.raw_name_bytes(), b"r\x82sum\x82.txt");
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::spec::header::ExtraField;
+use crate::{Compression, Encryption, ZipEntryBuilder};
+
+/// Returns the data of a WinZip AES extra field (AE-2) with the provided strength over Deflate data.
+fn aes(strength: u8) -> [u8; 7] {
+    [2, 0, b'A', b'E', strength, 8, 0]
+}
+
+#[tokio::test]
+async fn classify_mixed_archive() {
+    let entries = [
+        ZipEntryBuilder::new("plain.txt".into(), Compression::Stored),
+        ZipEntryBuilder::new("zipcrypto.txt".into(), Compression::Stored),
+        ZipEntryBuilder::new("aes256.txt".into(), Compression::Stored).add_extra_field(0x9901, &aes(3)),
+        ZipEntryBuilder::new("aes128.txt".into(), Compression::Stored).add_extra_field(0x9901, &aes(1)),
+    ];
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for builder in entries {
+        writer.write_entry_whole(builder, b"data").await.unwrap();
+    }
+    let mut data = writer.close().await.unwrap();
+
+    // Set the encrypted flag (general purpose bit 0) within all but the first central directory record.
+    let records: Vec<usize> = data
+        .windows(4)
+        .enumerate()
+        .filter(|(_, bytes)| *bytes == CDH_SIGNATURE.to_le_bytes())
+        .map(|(i, _)| i)
+        .collect();
+    for record in &records[1..] {
+        data[record + 8] |= 1;
+    }
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let classified: Vec<_> =
+        reader.file().entries().iter().map(|entry| (entry.is_encrypted(), entry.encryption())).collect();
+
+    assert_eq!(
+        classified,
+        [
+            (false, Encryption::None),
+            (true, Encryption::ZipCrypto),
+            (true, Encryption::Aes256),
+            (true, Encryption::Aes128),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn aes_actual_compression_method() {
+    let builder = ZipEntryBuilder::new("aes.txt".into(), Compression::Stored).add_extra_field(0x9901, &aes(3));
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Mark the entry as AES-encrypted within both its local file header and central directory record.
+    let record = data.windows(4).position(|bytes| bytes == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    for (flags, method) in [(6, 8), (record + 8, record + 10)] {
+        data[flags] |= 1;
+        data[method..method + 2].copy_from_slice(&99u16.to_le_bytes());
+    }
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    #[cfg(feature = "deflate")]
+    assert_eq!(entry.compression(), Compression::Deflate);
+    #[cfg(not(feature = "deflate"))]
+    assert_eq!(entry.compression(), Compression::Unsupported(8));
+    assert_eq!(entry.encryption(), Encryption::Aes256);
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::FeatureNotSupported("encryption"))));
+}
+
+#[tokio::test]
+async fn invalid_aes_fields_rejected() {
+    let mut invalid_version = aes(3);
+    invalid_version[0] = 3;
+
+    for (field, expected) in
+        [(aes(4), ZipError::InvalidAesStrength(4)), (invalid_version, ZipError::InvalidAesVersion(3))]
+    {
+        let builder = ZipEntryBuilder::new("aes.txt".into(), Compression::Stored).add_extra_field(0x9901, &field);
+        let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+        writer.write_entry_whole(builder, b"data").await.unwrap();
+        let data = writer.close().await.unwrap();
+
+        match ZipFileReader::new(data).await {
+            Err(error) => assert_eq!(error.to_string(), expected.to_string()),
+            Ok(_) => panic!("expected the WinZip AES extra field to be rejected"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn aes_crc_convention() {
+    let mut ae1 = aes(1);
+    ae1[0] = 1;
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, field) in [("ae1.txt", ae1), ("ae2.txt", aes(1))] {
+        let builder = ZipEntryBuilder::new(name.into(), Compression::Stored).add_extra_field(0x9901, &field);
+        writer.write_entry_whole(builder, b"data").await.unwrap();
+    }
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let stores_crc32: Vec<_> = reader
+        .file()
+        .entries()
+        .iter()
+        .flat_map(|entry| entry.extra_fields().iter())
+        .filter_map(|field| match field {
+            ExtraField::WinZipAes(field) => Some(field.stores_crc32()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(stores_crc32, [true, false]);
+}
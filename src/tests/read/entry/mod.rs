@@ -0,0 +1,233 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, AsyncSeekExt, BufReader, Cursor, SeekFrom};
+
+const DATA: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+
+async fn single_stored_entry() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("lorem.txt".into(), Compression::Stored), DATA).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn into_inner_owned_position() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = single_stored_entry().await;
+    let data_offset = data.windows(DATA.len()).position(|window| window == DATA).unwrap() as u64;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let mut entry_reader = reader.reader_without_entry(0).await.unwrap();
+
+    let mut buffer = [0; 5];
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, &DATA[..5]);
+
+    let mut inner = entry_reader.into_inner().unwrap();
+    assert_eq!(inner.seek(SeekFrom::Current(0)).await.unwrap(), data_offset + 5);
+}
+
+#[tokio::test]
+async fn into_inner_trailing_bytes() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::spec::consts::LFH_SIGNATURE;
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("lorem.txt".into(), Compression::Stored), DATA).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("next.txt".into(), Compression::Stored), b"next").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let mut entry_reader = reader.reader_without_entry(0).await.unwrap();
+
+    let mut buffer = Vec::new();
+    entry_reader.read_to_end(&mut buffer).await.unwrap();
+    assert_eq!(buffer, DATA);
+
+    // The recovered reader continues directly after the entry's data, at the next local file header.
+    let mut inner = entry_reader.into_inner().unwrap();
+    let mut signature = [0; 4];
+    inner.read_exact(&mut signature).await.unwrap();
+    assert_eq!(u32::from_le_bytes(signature), LFH_SIGNATURE);
+}
+
+#[tokio::test]
+async fn into_inner_borrowed() {
+    use crate::base::read::seek::ZipFileReader;
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(single_stored_entry().await))).await.unwrap();
+    let entry_reader = reader.reader_without_entry(0).await.unwrap();
+
+    assert!(entry_reader.into_inner().is_none());
+}
+
+#[tokio::test]
+async fn read_to_vec_preallocated() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let reader = ZipFileReader::new(single_stored_entry().await).await.unwrap();
+    let data = reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap();
+
+    assert_eq!(data.len() as u64, reader.file().entries()[0].uncompressed_size());
+    assert_eq!(data.capacity(), DATA.len());
+    assert_eq!(data, DATA);
+
+    let string = reader.reader_with_entry(0).await.unwrap().read_to_string_owned().await.unwrap();
+    assert_eq!(string.as_bytes(), DATA);
+}
+
+#[tokio::test]
+async fn read_to_vec_bounds_declared_size() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::spec::consts::CDH_SIGNATURE;
+    use crate::tests::take_largest_allocation;
+
+    // Claim an uncompressed size of almost 4 GiB within the central directory record.
+    let mut archive = single_stored_entry().await;
+    let record = archive.windows(4).position(|bytes| bytes == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    archive[record + 24..record + 28].copy_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+
+    let reader = ZipFileReader::new(archive).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+
+    take_largest_allocation();
+    let _ = entry_reader.read_to_vec().await;
+    assert!(take_largest_allocation() <= 64 * 1024);
+}
+
+#[tokio::test]
+async fn data_offset_points_at_data() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("first.txt".into(), Compression::Stored).add_extra_field(0xCAFE, &[0; 7]);
+    writer.write_entry_whole(builder, b"first").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("lorem.txt".into(), Compression::Stored), DATA).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let mut cursor = Cursor::new(reader.data());
+
+    for (index, expected) in [b"first".as_slice(), DATA].into_iter().enumerate() {
+        let entry = &reader.file().entries()[index];
+        let offset = entry.data_offset(&mut cursor).await.unwrap() as usize;
+
+        assert!(offset > entry.header_offset() as usize);
+        assert_eq!(&reader.data()[offset..offset + entry.compressed_size() as usize], expected);
+    }
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn entry_raw_reader_deflate() {
+    use crate::base::read::seek::ZipFileReader;
+    use std::io::Write;
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("stored.txt".into(), Compression::Stored), b"first").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("lorem.txt".into(), Compression::Deflate), DATA).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(DATA).unwrap();
+    let expected = encoder.finish().unwrap();
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap();
+    let mut raw = Vec::new();
+    reader.entry_raw_reader(1).await.unwrap().read_to_end(&mut raw).await.unwrap();
+    assert_eq!(raw, expected);
+
+    raw.clear();
+    reader.entry_raw_reader(0).await.unwrap().read_to_end(&mut raw).await.unwrap();
+    assert_eq!(raw, b"first");
+}
+
+#[tokio::test]
+async fn running_crc_in_chunks() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let reader = ZipFileReader::new(single_stored_entry().await).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    assert_eq!(entry_reader.current_crc(), 0);
+
+    let mut chunk = [0; 7];
+    let mut read = Vec::new();
+    loop {
+        match entry_reader.read(&mut chunk).await.unwrap() {
+            0 => break,
+            len => read.extend_from_slice(&chunk[..len]),
+        }
+        assert_eq!(entry_reader.current_crc(), crc32fast::hash(&read));
+    }
+
+    assert_eq!(read, DATA);
+    assert_eq!(entry_reader.current_crc(), entry_reader.expected_crc());
+}
+
+#[tokio::test]
+async fn copy_to_writer() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::error::ZipError;
+
+    let mut data = single_stored_entry().await;
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+
+    let mut sink = Cursor::new(Vec::new());
+    let (copied, crc) = reader.reader_with_entry(0).await.unwrap().copy_to(&mut sink).await.unwrap();
+    assert_eq!(copied, DATA.len() as u64);
+    assert_eq!(crc, crc32fast::hash(DATA));
+    assert_eq!(sink.into_inner(), DATA);
+
+    // Corrupt the entry's data so its CRC32 value no longer matches.
+    let offset = 4 + crate::spec::consts::LFH_LENGTH + "lorem.txt".len();
+    data[offset] ^= 0xFF;
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let result = reader.reader_with_entry(0).await.unwrap().copy_to(Vec::new()).await;
+    assert!(matches!(result, Err(ZipError::CRC32CheckError)));
+}
+
+#[tokio::test]
+async fn compressed_bytes_read_half() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let reader = ZipFileReader::new(single_stored_entry().await).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let compressed_size = entry_reader.entry().compressed_size();
+    assert_eq!(entry_reader.compressed_bytes_read(), 0);
+
+    let mut buffer = vec![0; DATA.len() / 2];
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    let consumed = entry_reader.compressed_bytes_read();
+    assert!(consumed >= compressed_size / 2 && consumed < compressed_size);
+
+    entry_reader.read_to_end(&mut buffer).await.unwrap();
+    assert_eq!(entry_reader.compressed_bytes_read(), compressed_size);
+}
+
+#[tokio::test]
+async fn read_lines() {
+    use crate::base::read::mem::ZipFileReader;
+    use tokio::io::AsyncBufReadExt;
+    use tokio_util::compat::FuturesAsyncReadCompatExt;
+
+    let text: String = (0..2000).map(|index| format!("line {index}\n")).collect();
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer
+        .write_entry_whole(ZipEntryBuilder::new("lines.txt".into(), Compression::Stored), text.as_bytes())
+        .await
+        .unwrap();
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let mut lines = (&mut entry_reader).compat().lines();
+    let mut read = Vec::new();
+    while let Some(line) = lines.next_line().await.unwrap() {
+        read.push(line);
+    }
+
+    assert_eq!(read, text.lines().collect::<Vec<_>>());
+    assert_eq!(entry_reader.compute_hash(), entry_reader.expected_crc());
+}
@@ -0,0 +1,134 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::tokio::read::fs::{ExtractOptions, OverwritePolicy, ZipFileReader};
+use crate::{Compression, ZipEntryBuilder};
+
+use std::path::{Path, PathBuf};
+
+/// Creates a fresh temporary directory containing a ZIP file with the provided entry data.
+async fn archive_in_temp_dir(name: &str, data: &[u8]) -> (PathBuf, PathBuf) {
+    let directory = std::env::temp_dir().join(format!("async-zip-extract-{}-{}", name, std::process::id()));
+    let _ = tokio::fs::remove_dir_all(&directory).await;
+    tokio::fs::create_dir_all(&directory).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("dir/".into(), Compression::Stored), &[]).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("dir/file.txt".into(), Compression::Stored), data).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("../escape.txt".into(), Compression::Stored), data).await.unwrap();
+
+    let archive = directory.join("archive.zip");
+    tokio::fs::write(&archive, writer.close().await.unwrap()).await.unwrap();
+
+    (directory, archive)
+}
+
+async fn extract_twice(name: &str, overwrite: OverwritePolicy) -> (PathBuf, Vec<u8>) {
+    let (directory, archive) = archive_in_temp_dir(name, b"archived").await;
+    let output = directory.join("out");
+    let options = ExtractOptions { overwrite, ..Default::default() };
+
+    let reader = ZipFileReader::new(&archive).await.unwrap();
+    reader.extract(&output, &options).await.unwrap();
+    assert_eq!(tokio::fs::read(output.join("dir/file.txt")).await.unwrap(), b"archived");
+    assert!(output.join("escape.txt").is_file());
+    assert!(!directory.join("escape.txt").exists());
+
+    tokio::fs::write(output.join("dir/file.txt"), b"modified").await.unwrap();
+    reader.extract(&output, &options).await.unwrap();
+
+    let contents = tokio::fs::read(output.join("dir/file.txt")).await.unwrap();
+    (directory, contents)
+}
+
+async fn cleanup(directory: &Path) {
+    tokio::fs::remove_dir_all(directory).await.unwrap();
+}
+
+#[tokio::test]
+async fn extract_twice_skip() {
+    let (directory, contents) = extract_twice("skip", OverwritePolicy::Skip).await;
+    assert_eq!(contents, b"modified");
+    cleanup(&directory).await;
+}
+
+#[tokio::test]
+async fn extract_twice_overwrite() {
+    let (directory, contents) = extract_twice("overwrite", OverwritePolicy::Overwrite).await;
+    assert_eq!(contents, b"archived");
+    cleanup(&directory).await;
+}
+
+#[tokio::test]
+async fn extract_twice_error() {
+    let (directory, archive) = archive_in_temp_dir("error", b"archived").await;
+    let output = directory.join("out");
+
+    let reader = ZipFileReader::new(&archive).await.unwrap();
+    reader.extract(&output, &ExtractOptions::default()).await.unwrap();
+
+    let result = reader.extract(&output, &ExtractOptions::default()).await;
+    assert!(matches!(result, Err(ZipError::ExtractTargetExists(path)) if path == output.join("dir/file.txt")));
+    cleanup(&directory).await;
+}
+
+#[tokio::test]
+async fn path_types_reopen_entries() {
+    let (directory, archive) = archive_in_temp_dir("path-types", b"archived").await;
+
+    let from_path = ZipFileReader::new(archive.as_path()).await.unwrap();
+    let from_path_buf = ZipFileReader::new(archive.clone()).await.unwrap();
+
+    // Each entry reader reopens the file from the stored path.
+    for reader in [from_path, from_path_buf] {
+        assert_eq!(reader.path(), archive);
+        assert_eq!(reader.reader_with_entry(1).await.unwrap().read_to_vec().await.unwrap(), b"archived");
+    }
+
+    cleanup(&directory).await;
+}
+
+#[tokio::test]
+async fn extract_crc_mismatch_leaves_nothing() {
+    let (directory, archive) = archive_in_temp_dir("crc", b"archived").await;
+    let output = directory.join("out");
+
+    // Corrupt the data of the second entry (the first file), leaving its CRC32 value stale.
+    let mut data = tokio::fs::read(&archive).await.unwrap();
+    let offset = data.windows(8).position(|window| window == b"archived").unwrap();
+    data[offset] ^= 0xFF;
+    tokio::fs::write(&archive, data).await.unwrap();
+
+    let reader = ZipFileReader::new(&archive).await.unwrap();
+    let result = reader.extract(&output, &ExtractOptions::default()).await;
+    assert!(matches!(result, Err(ZipError::CRC32CheckError)));
+
+    let mut remaining = tokio::fs::read_dir(output.join("dir")).await.unwrap();
+    assert!(remaining.next_entry().await.unwrap().is_none());
+    cleanup(&directory).await;
+}
+
+#[tokio::test]
+async fn extract_skips_empty_paths() {
+    let directory = std::env::temp_dir().join(format!("async-zip-extract-empty-{}", std::process::id()));
+    let _ = tokio::fs::remove_dir_all(&directory).await;
+    tokio::fs::create_dir_all(&directory).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for name in ["..", "./", "a.txt"] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), b"data").await.unwrap();
+    }
+
+    let archive = directory.join("archive.zip");
+    tokio::fs::write(&archive, writer.close().await.unwrap()).await.unwrap();
+
+    let output = directory.join("out");
+    let reader = ZipFileReader::new(&archive).await.unwrap();
+    reader.extract(&output, &ExtractOptions::default()).await.unwrap();
+    assert_eq!(tokio::fs::read(output.join("a.txt")).await.unwrap(), b"data");
+    assert!(output.is_dir());
+
+    cleanup(&directory).await;
+}
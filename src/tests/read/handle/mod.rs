@@ -0,0 +1,43 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::tokio::read::fs::ZipFileReader;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncReadExt;
+
+#[tokio::test]
+async fn cloned_entry_readers_concurrent() {
+    let data: Vec<u8> = (0..256 * 1024u32).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8).collect();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("first.txt".into(), Compression::Stored), b"first").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("data.bin".into(), Compression::Stored), &data).await.unwrap();
+
+    let path = std::env::temp_dir().join(format!("async-zip-handle-{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+    let reader = ZipFileReader::new(&path).await.unwrap();
+
+    // The clone starts from the beginning of the entry, despite the original having been partially read.
+    let mut first = reader.entry_reader(1).await.unwrap();
+    let mut prefix = [0; 16];
+    first.read_exact(&mut prefix).await.unwrap();
+    let mut second = first.try_clone().await.unwrap();
+    assert_eq!(second.index(), 1);
+
+    let (rest, cloned) = tokio::join!(
+        async {
+            let mut rest = Vec::new();
+            first.read_to_end(&mut rest).await.unwrap();
+            rest
+        },
+        second.read_to_vec()
+    );
+
+    assert_eq!([&prefix[..], &rest].concat(), data);
+    assert_eq!(cloned.unwrap(), data);
+    assert_eq!(first.compute_hash(), first.entry().crc32());
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
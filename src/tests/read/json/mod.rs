@@ -0,0 +1,88 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+const FIELDS: &[&str] = &["name", "retries", "verbose"];
+
+/// A configuration type with hand-written impls, standing in for one which derives them.
+///
+/// These mirror what `#[derive(Serialize, Deserialize)]` would generate. Deriving them requires `serde`'s `derive`
+/// feature as a dev-dependency, which pulls in `serde_derive`; that crate couldn't be fetched when these tests were
+/// written, so the impls should be replaced with the derive once it's available.
+#[derive(Debug, PartialEq)]
+struct Config {
+    name: String,
+    retries: u32,
+    verbose: bool,
+}
+
+impl Serialize for Config {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Config", 3)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("retries", &self.retries)?;
+        state.serialize_field("verbose", &self.verbose)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Config {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ConfigVisitor;
+
+        impl<'de> Visitor<'de> for ConfigVisitor {
+            type Value = Config;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("struct Config")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Config, A::Error> {
+                let (mut name, mut retries, mut verbose) = (None, None, None);
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "name" => name = Some(map.next_value()?),
+                        "retries" => retries = Some(map.next_value()?),
+                        "verbose" => verbose = Some(map.next_value()?),
+                        other => return Err(de::Error::unknown_field(other, FIELDS)),
+                    }
+                }
+
+                Ok(Config {
+                    name: name.ok_or_else(|| de::Error::missing_field("name"))?,
+                    retries: retries.ok_or_else(|| de::Error::missing_field("retries"))?,
+                    verbose: verbose.ok_or_else(|| de::Error::missing_field("verbose"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("Config", FIELDS, ConfigVisitor)
+    }
+}
+
+#[tokio::test]
+async fn read_json_round_trip() {
+    let config = Config { name: "async_zip".to_string(), retries: 3, verbose: true };
+    let data = serde_json::to_vec(&config).unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new("config.json".into(), Compression::Deflate);
+    writer.write_entry_whole(builder, &data).await.unwrap();
+    let builder = ZipEntryBuilder::new("invalid.json".into(), Compression::Deflate);
+    writer.write_entry_whole(builder, b"{\"name\": 1}").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let read: Config = reader.reader_with_entry(0).await.unwrap().read_json().await.unwrap();
+    assert_eq!(read, config);
+
+    let result = reader.reader_with_entry(1).await.unwrap().read_json::<Config>().await;
+    assert!(matches!(result, Err(ZipError::Json(_))));
+}
@@ -0,0 +1,79 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{mem::ZipFileReader, ZipFileReaderOptions};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::tests::allocations;
+use crate::{Compression, ZipEntryBuilder};
+
+async fn archive(entries: usize) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for index in 0..entries {
+        let builder = ZipEntryBuilder::new(format!("dir/file-{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(builder, &[]).await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn lazy_allocations() {
+    let data = archive(50_000).await;
+    let (eager_data, lazy_data) = (data.clone(), data);
+
+    let before = allocations();
+    let eager = ZipFileReader::new(eager_data).await.unwrap();
+    let eager_allocations = allocations() - before;
+
+    let before = allocations();
+    let lazy = ZipFileReader::new_lazy(lazy_data).await.unwrap();
+    let lazy_allocations = allocations() - before;
+
+    assert_eq!(lazy.len(), eager.file().entries().len());
+    assert!(eager_allocations >= 50_000, "eager: {eager_allocations}");
+    assert!(lazy_allocations < 100, "lazy: {lazy_allocations}");
+}
+
+#[tokio::test]
+async fn lazy_matches_eager() {
+    let data = archive(16).await;
+    let eager = ZipFileReader::new(data.clone()).await.unwrap();
+    let lazy = ZipFileReader::new_lazy(data).await.unwrap();
+
+    assert_eq!(lazy.entry_name_bytes(3), Some(b"dir/file-3.txt".as_slice()));
+    assert_eq!(lazy.entry_name_bytes(16), None);
+    assert!(matches!(lazy.entry_name(16), Err(ZipError::EntryIndexOutOfBounds)));
+
+    for (index, entry) in eager.file().entries().iter().enumerate() {
+        assert_eq!(lazy.entry_name(index).unwrap().as_bytes(), entry.filename().as_bytes());
+
+        let lazy_entry = lazy.entry(index).await.unwrap();
+        assert_eq!(lazy_entry.header_offset(), entry.header_offset());
+        assert_eq!(lazy_entry.crc32(), entry.crc32());
+    }
+
+    let converted = lazy.into_eager().await.unwrap();
+    assert_eq!(converted.file().entries().len(), 16);
+    assert_eq!(converted.reader_with_entry(5).await.unwrap().read_to_vec().await.unwrap(), b"");
+}
+
+#[tokio::test]
+async fn lazy_with_options() {
+    let data = archive(2).await;
+
+    let options = ZipFileReaderOptions::new().max_entries(1);
+    let result = ZipFileReader::new_lazy_with_options(data.clone(), options).await;
+    assert!(matches!(result, Err(ZipError::TooManyEntries(2))));
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment("a".repeat(1000));
+    let commented = writer.close().await.unwrap();
+
+    let options = ZipFileReaderOptions::new().eocd_search_window(512);
+    let result = ZipFileReader::new_lazy_with_options(commented.clone(), options).await;
+    assert!(matches!(result, Err(ZipError::EocdNotFound(512))));
+
+    let options = ZipFileReaderOptions::new().max_entries(2).eocd_search_window(1022);
+    assert_eq!(ZipFileReader::new_lazy_with_options(data, options).await.unwrap().len(), 2);
+    assert!(ZipFileReader::new_lazy_with_options(commented, options).await.unwrap().is_empty());
+}
@@ -0,0 +1,66 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+const SIZE: usize = 1024 * 1024;
+
+/// Returns a reader over an archive holding a single, highly compressible Deflate entry.
+async fn bomb() -> ZipFileReader {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("bomb.bin".into(), Compression::Deflate);
+    writer.write_entry_whole(builder, &vec![0; SIZE]).await.unwrap();
+    ZipFileReader::new(writer.close().await.unwrap()).await.unwrap()
+}
+
+#[tokio::test]
+async fn size_limit_exceeded() {
+    let reader = bomb().await;
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap().with_limit(64 * 1024);
+    let result = entry_reader.read_to_end_checked(&mut Vec::new()).await;
+    assert!(matches!(result, Err(ZipError::SizeLimitExceeded(65536))));
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap().with_limit(SIZE as u64);
+    assert_eq!(entry_reader.read_to_end_checked(&mut Vec::new()).await.unwrap(), SIZE);
+}
+
+#[tokio::test]
+async fn ratio_limit_exceeded() {
+    let reader = bomb().await;
+    assert!(reader.file().entries()[0].compressed_size() * 1000 < SIZE as u64);
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap().with_ratio_limit(100);
+    let result = entry_reader.read_to_end_checked(&mut Vec::new()).await;
+    assert!(matches!(result, Err(ZipError::CompressionRatioExceeded)));
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap().with_ratio_limit(100_000);
+    assert_eq!(entry_reader.read_to_end_checked(&mut Vec::new()).await.unwrap(), SIZE);
+}
+
+#[tokio::test]
+async fn ratio_limit_exceeded_stream() {
+    use crate::base::read::stream::ZipFileReader;
+    use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
+
+    // Streamed entries have a data descriptor, so their compressed size isn't known when read from a stream.
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("bomb.bin".into(), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream(builder).await.unwrap();
+    entry_writer.write_all(&vec![0; SIZE]).await.unwrap();
+    entry_writer.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data.as_slice()).next_with_entry().await.unwrap().unwrap();
+    let mut reader = reader.with_ratio_limit(100);
+    let result = reader.reader_mut().read_to_end_checked(&mut Vec::new()).await;
+    assert!(matches!(result, Err(ZipError::CompressionRatioExceeded)));
+
+    let reader = ZipFileReader::new(data.as_slice()).next_with_entry().await.unwrap().unwrap();
+    let mut reader = reader.with_ratio_limit(100_000);
+    // The CRC32 value is only known once the data descriptor has been read, so the data can't be checked here.
+    assert_eq!(reader.reader_mut().read_to_end(&mut Vec::new()).await.unwrap(), SIZE);
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn list_entries() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, data) in [("a.txt", &b"alpha"[..]), ("dir/", b""), ("dir/b.txt", b"bravo bravo")] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), data).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let file = reader.file();
+
+    assert_eq!(file.len(), 3);
+    assert!(!file.is_empty());
+    assert_eq!(file.file_names().collect::<Vec<_>>(), ["a.txt", "dir/", "dir/b.txt"]);
+    assert_eq!(file.total_uncompressed_size(), 16);
+    assert_eq!(file.total_compressed_size(), 16);
+}
+
+#[tokio::test]
+async fn list_empty() {
+    let reader = ZipFileReader::new(ZipFileWriter::new(Vec::<u8>::new()).close().await.unwrap()).await.unwrap();
+
+    assert!(reader.file().is_empty());
+    assert_eq!(reader.file().file_names().count(), 0);
+    assert_eq!(reader.file().total_uncompressed_size(), 0);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn mixed_method_stats() {
+    let data = vec![b'a'; 1000];
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, compression) in [("a", Compression::Deflate), ("b", Compression::Stored), ("c", Compression::Deflate)] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), compression), &data).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let stats = reader.file().stats();
+
+    assert_eq!(stats.entries, 3);
+    assert_eq!(stats.uncompressed_size, 3000);
+    assert_eq!(stats.compressed_size, reader.file().total_compressed_size());
+    assert_eq!(stats.methods, [(Compression::Deflate, 2), (Compression::Stored, 1)]);
+
+    let ratio = stats.ratio().unwrap();
+    assert!(ratio > 1.0 / 3.0 && ratio < 1.0);
+}
+
+#[tokio::test]
+async fn empty_stats() {
+    let reader = ZipFileReader::new(ZipFileWriter::new(Vec::<u8>::new()).close().await.unwrap()).await.unwrap();
+
+    assert_eq!(reader.file().stats(), crate::ArchiveStats::default());
+    assert_eq!(reader.file().stats().ratio(), None);
+}
+
+#[test]
+fn stats_saturate() {
+    let mut stats = crate::ArchiveStats::default();
+    stats.record(Compression::Stored, u64::MAX, u64::MAX);
+    stats.record(Compression::Stored, 1, 1);
+
+    assert_eq!(stats.entries, 2);
+    assert_eq!(stats.compressed_size, u64::MAX);
+    assert_eq!(stats.uncompressed_size, u64::MAX);
+}
+
+#[tokio::test]
+async fn total_sizes_saturate() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for name in ["a.txt", "b.txt"] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), b"data").await.unwrap();
+    }
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    // Sizes as they might be declared by a malformed archive's ZIP64 extended information fields.
+    let mut file = reader.file().clone();
+    for stored in &mut file.entries {
+        stored.entry.compressed_size = u64::MAX;
+        stored.entry.uncompressed_size = u64::MAX;
+    }
+
+    assert_eq!(file.total_compressed_size(), u64::MAX);
+    assert_eq!(file.total_uncompressed_size(), u64::MAX);
+}
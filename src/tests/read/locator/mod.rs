@@ -1,6 +1,8 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::base::read::DEFAULT_EOCD_SEARCH_WINDOW;
+
 #[test]
 fn search_one_byte_test() {
     let buffer: &[u8] = &[0x0, 0x0, 0x0, 0x0, 0x0, 0x0];
@@ -33,7 +35,7 @@ async fn locator_empty_test() {
 
     let data = &include_bytes!("empty.zip");
     let mut cursor = Cursor::new(data);
-    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor).await;
+    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor, DEFAULT_EOCD_SEARCH_WINDOW).await;
 
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 4);
@@ -45,7 +47,7 @@ async fn locator_empty_max_comment_test() {
 
     let data = &include_bytes!("empty-with-max-comment.zip");
     let mut cursor = Cursor::new(data);
-    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor).await;
+    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor, DEFAULT_EOCD_SEARCH_WINDOW).await;
 
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 4);
@@ -57,8 +59,31 @@ async fn locator_buffer_boundary_test() {
 
     let data = &include_bytes!("empty-buffer-boundary.zip");
     let mut cursor = Cursor::new(data);
-    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor).await;
+    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor, DEFAULT_EOCD_SEARCH_WINDOW).await;
 
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 4);
 }
+
+#[tokio::test]
+async fn eocd_search_window() {
+    use crate::base::read::{mem::ZipFileReader, ZipFileReaderOptions};
+    use crate::base::write::ZipFileWriter;
+    use crate::error::ZipError;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment("a".repeat(1000));
+    let data = writer.close().await.unwrap();
+
+    // The EOCDR starts 22 bytes before the comment.
+    let options = ZipFileReaderOptions::new().eocd_search_window(1022);
+    assert!(ZipFileReader::with_options(data.clone(), options).await.is_ok());
+
+    let options = ZipFileReaderOptions::new().eocd_search_window(1021);
+    let result = ZipFileReader::with_options(data.clone(), options).await;
+    assert!(matches!(result, Err(ZipError::EocdNotFound(1021))));
+
+    let options = ZipFileReaderOptions::new().eocd_search_window(512);
+    let result = ZipFileReader::with_options(data, options).await;
+    assert!(matches!(result, Err(ZipError::EocdNotFound(512))));
+}
@@ -0,0 +1,44 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncWriteExt, BufReader, Cursor};
+
+#[tokio::test]
+async fn manifest_three_entries() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"alpha").await.unwrap();
+    let builder = ZipEntryBuilder::new("b.txt".into(), Compression::Stored).add_extra_field(0xCAFE, &[0; 9]);
+    writer.write_entry_whole(builder, b"bravo!").await.unwrap();
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("c.txt".into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(b"charlie").await.unwrap();
+    entry_writer.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(data.clone()))).await.unwrap();
+    let manifest = reader.manifest().await.unwrap();
+    assert_eq!(manifest.len(), 3);
+
+    let expected = [("a.txt", &b"alpha"[..]), ("b.txt", b"bravo!"), ("c.txt", b"charlie")];
+    for (entry, (name, content)) in manifest.iter().zip(expected) {
+        assert_eq!(entry.name.as_str().unwrap(), name);
+        assert_eq!(entry.compression, Compression::Stored);
+        assert_eq!(entry.crc, crc32fast::hash(content));
+        assert_eq!(entry.compressed_size, content.len() as u64);
+        assert_eq!(entry.uncompressed_size, content.len() as u64);
+        assert_eq!(&data[entry.header_offset as usize..][..4], &crate::spec::consts::LFH_SIGNATURE.to_le_bytes());
+
+        let start = entry.data_offset as usize;
+        assert_eq!(&data[start..start + entry.compressed_size as usize], content);
+    }
+
+    let mem_manifest = crate::base::read::mem::ZipFileReader::new(data).await.unwrap().manifest().await.unwrap();
+    let offsets = |manifest: &[crate::base::read::EntryManifest]| {
+        manifest.iter().map(|entry| (entry.header_offset, entry.data_offset)).collect::<Vec<_>>()
+    };
+    assert_eq!(offsets(&mem_manifest), offsets(&manifest));
+}
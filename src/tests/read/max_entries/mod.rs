@@ -0,0 +1,37 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Returns an archive holding two small stored entries.
+async fn two_entries() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for name in ["a.txt", "b.txt"] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), b"data").await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn declared_entries_exceed_directory_size() {
+    let mut data = two_entries().await;
+
+    // Both the number of entries on this disk and in total follow the EOCDR's signature and disk numbers.
+    let eocdr = data.len() - 22;
+    data[eocdr + 8..eocdr + 12].copy_from_slice(&[0xFF; 4]);
+
+    let result = ZipFileReader::new(data).await;
+    assert!(matches!(result, Err(ZipError::TooManyEntries(0xFFFF))));
+}
+
+#[tokio::test]
+async fn declared_entries_exceed_max() {
+    let data = two_entries().await;
+
+    let result = ZipFileReader::with_max_entries(data.clone(), 1).await;
+    assert!(matches!(result, Err(ZipError::TooManyEntries(2))));
+    assert_eq!(ZipFileReader::with_max_entries(data, 2).await.unwrap().file().entries().len(), 2);
+}
@@ -1,6 +1,44 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "async-std")]
+pub(crate) mod async_std;
+#[cfg(all(feature = "tokio-fs", feature = "deflate"))]
+pub(crate) mod buffer;
+pub(crate) mod comment;
 pub(crate) mod compression;
+#[cfg(feature = "tokio-fs")]
+pub(crate) mod concurrent;
+#[cfg(feature = "deflate")]
+pub(crate) mod descriptor;
+pub(crate) mod empty;
+pub(crate) mod encoding;
+pub(crate) mod encryption;
+pub(crate) mod entry;
+#[cfg(feature = "tokio-fs")]
+pub(crate) mod extract;
+#[cfg(feature = "tokio-fs")]
+pub(crate) mod handle;
+#[cfg(all(feature = "json", feature = "deflate"))]
+pub(crate) mod json;
+pub(crate) mod lazy;
+#[cfg(feature = "deflate")]
+pub(crate) mod limit;
+pub(crate) mod listing;
 pub(crate) mod locator;
+pub(crate) mod manifest;
+pub(crate) mod max_entries;
+pub(crate) mod recompute;
+pub(crate) mod safe_path;
+pub(crate) mod seek;
+#[cfg(feature = "deflate")]
+pub(crate) mod seek_index;
+pub(crate) mod sfx;
+pub(crate) mod size_source;
+pub(crate) mod slice;
+pub(crate) mod spanned;
+pub(crate) mod truncated;
+#[cfg(feature = "deflate")]
+pub(crate) mod unsupported;
+pub(crate) mod validate;
 pub(crate) mod zip64;
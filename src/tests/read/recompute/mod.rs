@@ -0,0 +1,123 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncWriteExt, BufReader, Cursor};
+
+const ENTRIES: [(&str, &[u8]); 3] =
+    [("first.txt", b"first entry"), ("second.txt", b"second entry data"), ("third.txt", b"third")];
+
+/// Returns an archive of `ENTRIES`, the last of which is written with a data descriptor.
+async fn archive(force_no_zip64: bool) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    if force_no_zip64 {
+        writer = writer.force_no_zip64();
+    }
+    writer.comment("archive comment".to_string());
+    for (name, data) in &ENTRIES[..2] {
+        writer.write_entry_whole(ZipEntryBuilder::new((*name).into(), Compression::Stored), data).await.unwrap();
+    }
+
+    // Written with a data descriptor, so its compressed size is only held within the central directory.
+    let (name, data) = ENTRIES[2];
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new(name.into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(data).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    writer.close().await.unwrap()
+}
+
+/// Returns the offsets of each central directory record within the archive.
+fn records(archive: &[u8]) -> Vec<usize> {
+    let records: Vec<usize> = archive
+        .windows(4)
+        .enumerate()
+        .filter(|(_, bytes)| *bytes == CDH_SIGNATURE.to_le_bytes())
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(records.len(), ENTRIES.len());
+    records
+}
+
+/// Asserts that the archive holds `ENTRIES`, verifying each entry's CRC32 value.
+async fn assert_entries(archive: Vec<u8>) {
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    assert_eq!(reader.file().entries().len(), ENTRIES.len());
+
+    for (index, (name, data)) in ENTRIES.iter().enumerate() {
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        assert_eq!(entry_reader.entry().filename().as_str().unwrap(), *name);
+
+        let mut buffer = Vec::new();
+        entry_reader.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, *data);
+    }
+}
+
+#[tokio::test]
+async fn recompute_stale_central_directory() {
+    let mut archive = archive(false).await;
+
+    // Corrupt every record's local header offset, and the first record's sizes.
+    let records = records(&archive);
+    for record in &records {
+        archive[record + 42..record + 46].copy_from_slice(&0x1234u32.to_le_bytes());
+    }
+    archive[records[0] + 20..records[0] + 28].copy_from_slice(&[99, 0, 0, 0, 99, 0, 0, 0]);
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    assert!(reader.reader_with_entry(0).await.is_err());
+
+    let repaired = reader.recompute_central_directory(Vec::new()).await.unwrap();
+    let reader = ZipFileReader::new(BufReader::new(Cursor::new(repaired.clone()))).await.unwrap();
+    assert_eq!(reader.comment_bytes().unwrap(), b"archive comment");
+    assert_entries(repaired).await;
+}
+
+#[tokio::test]
+async fn recompute_with_stub_and_descriptor() {
+    let stub: Vec<u8> = (0..1024).map(|i| (i % 251) as u8).collect();
+    // Stubs are only detected before a non-ZIP64 end of central directory record.
+    let mut archive = [stub.clone(), archive(true).await].concat();
+
+    // Corrupt the CRC32 value and uncompressed size of the entry written with a data descriptor.
+    let record = records(&archive)[2];
+    archive[record + 16..record + 20].copy_from_slice(&0u32.to_le_bytes());
+    archive[record + 24..record + 28].copy_from_slice(&99u32.to_le_bytes());
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    let repaired = reader.recompute_central_directory(Vec::new()).await.unwrap();
+
+    assert_eq!(repaired[..stub.len()], stub);
+    assert_entries(repaired).await;
+}
+
+#[tokio::test]
+async fn recompute_with_stale_descriptor_size() {
+    let mut archive = archive(false).await;
+
+    // Corrupt the compressed size of the entry written with a data descriptor.
+    let record = records(&archive)[2];
+    archive[record + 20..record + 24].copy_from_slice(&2u32.to_le_bytes());
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    let repaired = reader.recompute_central_directory(Vec::new()).await.unwrap();
+    assert_entries(repaired).await;
+}
+
+#[tokio::test]
+async fn recompute_without_central_directory() {
+    let archive = archive(false).await;
+    let file = ZipFileReader::new(BufReader::new(Cursor::new(archive.clone()))).await.unwrap().file().clone();
+
+    // Truncate the archive directly after its local entries.
+    let truncated = archive[..records(&archive)[0]].to_vec();
+    let mut reader = ZipFileReader::from_raw_parts(BufReader::new(Cursor::new(truncated)), file);
+    let repaired = reader.recompute_central_directory(Vec::new()).await.unwrap();
+    assert_entries(repaired).await;
+}
@@ -0,0 +1,43 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::{Compression, PathPrefixPolicy, ZipEntry, ZipEntryBuilder};
+
+use std::path::PathBuf;
+
+fn entry(name: &str) -> ZipEntry {
+    ZipEntryBuilder::new(name.into(), Compression::Stored).build()
+}
+
+fn stripped(name: &str) -> PathBuf {
+    entry(name).safe_path_with(PathPrefixPolicy::Strip).unwrap()
+}
+
+#[test]
+fn unc_path() {
+    let result = entry(r"\\server\share\x").safe_path();
+    assert!(matches!(result, Err(ZipError::UnsafePathPrefix(name)) if name == r"\\server\share\x"));
+    assert!(matches!(entry("//server/share/x").safe_path(), Err(ZipError::UnsafePathPrefix(_))));
+
+    assert_eq!(stripped(r"\\server\share\x"), PathBuf::from("x"));
+    assert_eq!(stripped(r"\\?\UNC\server\share\dir\x"), ["dir", "x"].iter().collect::<PathBuf>());
+    assert_eq!(stripped(r"\\?\C:\dir\x"), ["dir", "x"].iter().collect::<PathBuf>());
+    assert_eq!(stripped(r"\\.\PhysicalDrive0"), PathBuf::new());
+}
+
+#[test]
+fn drive_relative_path() {
+    assert!(matches!(entry(r"C:..\y").safe_path(), Err(ZipError::UnsafePathPrefix(_))));
+    assert!(matches!(entry("c:/windows/x").safe_path(), Err(ZipError::UnsafePathPrefix(_))));
+
+    assert_eq!(stripped(r"C:..\y"), PathBuf::from("y"));
+    assert_eq!(stripped(r"C:\windows\x"), ["windows", "x"].iter().collect::<PathBuf>());
+}
+
+#[test]
+fn traversal_removed() {
+    assert_eq!(entry("../a/./b/../c").safe_path().unwrap(), ["a", "b", "c"].iter().collect::<PathBuf>());
+    assert_eq!(entry("/etc/passwd").safe_path().unwrap(), ["etc", "passwd"].iter().collect::<PathBuf>());
+    assert_eq!(entry(r"a\..\..\b").safe_path().unwrap(), ["a", "b"].iter().collect::<PathBuf>());
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, BufReader, Cursor, SeekFrom};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A reader which yields at most 1 KiB per read and fails once, upon reading beyond the provided position whilst armed.
+struct FlakyReader {
+    inner: Cursor<Vec<u8>>,
+    fail_beyond: u64,
+    armed: bool,
+}
+
+impl AsyncRead for FlakyReader {
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        if self.armed && self.inner.position() > self.fail_beyond {
+            self.armed = false;
+            return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset")));
+        }
+        let len = b.len().min(1024);
+        Pin::new(&mut self.inner).poll_read(c, &mut b[..len])
+    }
+}
+
+impl AsyncSeek for FlakyReader {
+    fn poll_seek(mut self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        Pin::new(&mut self.inner).poll_seek(c, pos)
+    }
+}
+
+#[tokio::test]
+async fn random_access_cursor() {
+    let entries: Vec<(String, Vec<u8>)> = (0..5)
+        .map(|index| (format!("entry-{index}.txt"), format!("data for entry {index}").repeat(index + 1).into()))
+        .collect();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (filename, data) in &entries {
+        writer
+            .write_entry_whole(ZipEntryBuilder::new(filename.clone().into(), Compression::Stored), data)
+            .await
+            .unwrap();
+    }
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(writer.close().await.unwrap()))).await.unwrap();
+
+    for index in [3, 0, 4, 1, 3, 2] {
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        assert_eq!(entry_reader.entry().filename().as_str().unwrap(), entries[index].0);
+
+        let mut buffer = Vec::new();
+        entry_reader.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, entries[index].1);
+    }
+}
+
+#[tokio::test]
+async fn reset_after_failed_read() {
+    let data: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("data.bin".into(), Compression::Stored), &data).await.unwrap();
+    let archive = writer.close().await.unwrap();
+
+    let flaky = FlakyReader { fail_beyond: archive.len() as u64 / 2, inner: Cursor::new(archive), armed: false };
+    let mut reader = ZipFileReader::new(BufReader::with_capacity(1024, flaky)).await.unwrap();
+    reader.inner_mut().get_mut().armed = true;
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = Vec::new();
+    assert!(entry_reader.read_to_end(&mut buffer).await.is_err());
+    assert!(!buffer.is_empty());
+
+    let mut entry_reader = entry_reader.reset().await.unwrap();
+    assert_eq!(entry_reader.current_crc(), 0);
+    assert_eq!(entry_reader.read_to_vec().await.unwrap(), data);
+}
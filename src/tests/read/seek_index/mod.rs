@@ -0,0 +1,96 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, AsyncWriteExt, BufReader, Cursor};
+
+/// Produces compressible but non-repeating data, so that it spans many Deflate blocks.
+fn data(length: usize) -> Vec<u8> {
+    let mut state = 0x2545F491u32;
+    (0..length)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            b"abcdefgh"[(state % 8) as usize]
+        })
+        .collect()
+}
+
+async fn archive(data: &[u8], flush_every: usize, compression: Compression) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("data.txt".into(), compression)).await.unwrap();
+
+    for chunk in data.chunks(flush_every) {
+        entry_writer.write_all(chunk).await.unwrap();
+        entry_writer.flush().await.unwrap();
+    }
+
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn seek_to_midpoint() {
+    let data = data(1024 * 1024);
+    let archive = archive(&data, 16 * 1024, Compression::Deflate).await;
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    let index = reader.build_seek_index(0, 64 * 1024).await.unwrap();
+    assert!(index.len() > 8);
+
+    // The closest restart point lies well past the start of the entry, so only part of it is decompressed.
+    let offset = data.len() as u64 / 2 + 1234;
+    let point = index.point_before(offset).unwrap();
+    assert!(point.uncompressed_offset > 0 && point.uncompressed_offset <= offset);
+    assert!(point.compressed_offset > 0);
+
+    let mut buffer = vec![0; 4096];
+    let mut entry_reader = reader.reader_at_offset(&index, offset).await.unwrap();
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data[offset as usize..offset as usize + 4096]);
+
+    let mut buffer = Vec::new();
+    reader.reader_at_offset(&index, offset).await.unwrap().read_to_end(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data[offset as usize..]);
+}
+
+#[tokio::test]
+async fn no_flush_points() {
+    let data = data(256 * 1024);
+    let archive = archive(&data, data.len(), Compression::Deflate).await;
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    let index = reader.build_seek_index(0, 0).await.unwrap();
+
+    let mut buffer = Vec::new();
+    reader.reader_at_offset(&index, 100_000).await.unwrap().read_to_end(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data[100_000..]);
+}
+
+#[tokio::test]
+async fn index_from_another_archive() {
+    let data = data(1024 * 1024);
+    let larger = archive(&data, 16 * 1024, Compression::Deflate).await;
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(larger))).await.unwrap();
+    let index = reader.build_seek_index(0, 64 * 1024).await.unwrap();
+
+    // The entry at the same index is far smaller, so the index's later restart points lie beyond its data.
+    let smaller = archive(&data[..1024], 1024, Compression::Deflate).await;
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(smaller))).await.unwrap();
+    let result = reader.reader_at_offset(&index, data.len() as u64 / 2).await;
+    assert!(matches!(result, Err(ZipError::SeekIndexMismatch)));
+}
+
+#[tokio::test]
+async fn non_deflate_entry() {
+    let archive = archive(b"stored", 6, Compression::Stored).await;
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    assert!(matches!(reader.build_seek_index(0, 0).await, Err(ZipError::FeatureNotSupported(_))));
+}
@@ -0,0 +1,40 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{mem, seek};
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+/// Returns an archive of two entries, preceded by 1024 bytes of junk standing in for a self-extracting stub.
+async fn sfx_archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"first").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("b.txt".into(), Compression::Stored), b"second").await.unwrap();
+
+    let mut data: Vec<u8> = (0..1024).map(|i| (i % 251) as u8).collect();
+    data.extend(writer.close().await.unwrap());
+    data
+}
+
+#[tokio::test]
+async fn prepended_stub_seek() {
+    let mut reader = seek::ZipFileReader::new(Cursor::new(sfx_archive().await)).await.unwrap();
+    assert_eq!(reader.file().entries()[0].header_offset(), 1024);
+
+    for (index, expected) in [b"first".as_slice(), b"second"].into_iter().enumerate() {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        assert_eq!(entry.read_to_vec().await.unwrap(), expected);
+    }
+}
+
+#[tokio::test]
+async fn prepended_stub_mem() {
+    let reader = mem::ZipFileReader::new(sfx_archive().await).await.unwrap();
+
+    for (index, expected) in [b"first".as_slice(), b"second"].into_iter().enumerate() {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        assert_eq!(entry.read_to_vec().await.unwrap(), expected);
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::read::SizeSource;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, Cursor};
+
+/// Returns an archive of a single entry whose central directory record understates its compressed size.
+async fn stale_central_archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("a.txt".into(), Compression::Stored);
+    writer.write_entry_whole(builder, b"hello world").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    let record = data.windows(4).position(|bytes| bytes == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    data[record + 20..record + 24].copy_from_slice(&5u32.to_le_bytes());
+    data
+}
+
+async fn read_with(source: SizeSource) -> Vec<u8> {
+    let data = stale_central_archive().await;
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap().size_source(source);
+
+    let mut buffer = Vec::new();
+    reader.reader_without_entry(0).await.unwrap().read_to_end(&mut buffer).await.unwrap();
+    buffer
+}
+
+#[tokio::test]
+async fn central_and_local_sizes() {
+    assert_eq!(read_with(SizeSource::default()).await, b"hello");
+    assert_eq!(read_with(SizeSource::Central).await, b"hello");
+    assert_eq!(read_with(SizeSource::Local).await, b"hello world");
+}
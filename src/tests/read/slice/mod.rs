@@ -0,0 +1,53 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipSliceReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+const ENTRIES: [(&str, &[u8]); 3] = [("a.txt", b"alpha"), ("dir/", b""), ("dir/b.txt", b"bravo bravo bravo")];
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, data) in ENTRIES {
+        #[cfg(feature = "deflate")]
+        let compression = if data.is_empty() { Compression::Stored } else { Compression::Deflate };
+        #[cfg(not(feature = "deflate"))]
+        let compression = Compression::Stored;
+
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), compression), data).await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn read_all_entries_from_slice() {
+    let data = archive().await;
+    let reader = ZipSliceReader::new(&data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), ENTRIES.len());
+
+    for (index, (name, expected)) in ENTRIES.iter().enumerate() {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        assert_eq!(entry.entry().filename().as_str().unwrap(), *name);
+        assert_eq!(entry.read_to_vec().await.unwrap(), *expected);
+    }
+
+    // Raw data is a sub-slice of the original bytes, rather than a copy.
+    let raw = reader.entry_data(0).await.unwrap();
+    assert_eq!(raw.len() as u64, reader.file().entries()[0].compressed_size());
+    assert!(data.as_ptr_range().contains(&raw.as_ptr()));
+}
+
+#[tokio::test]
+async fn overlong_slice_entry() {
+    // Claim the first entry's data extends beyond the end of the slice.
+    let mut data = archive().await;
+    let record = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    data[record + 20..record + 24].copy_from_slice(&0x00FF_FFFFu32.to_le_bytes());
+
+    let reader = ZipSliceReader::new(&data).await.unwrap();
+    assert!(matches!(reader.entry_data(0).await, Err(ZipError::UnexpectedEof)));
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::UnexpectedEof)));
+}
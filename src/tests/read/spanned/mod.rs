@@ -0,0 +1,42 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::EOCDR_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes an archive of two entries, returning its data and the offset of its EOCDR.
+async fn archive() -> (Vec<u8>, usize) {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for name in ["first.txt", "second.txt"] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), b"data").await.unwrap();
+    }
+
+    let data = writer.close().await.unwrap();
+    let offset = data.windows(4).rposition(|bytes| bytes == EOCDR_SIGNATURE.to_le_bytes()).unwrap();
+    (data, offset)
+}
+
+#[tokio::test]
+async fn single_disk_with_disk_numbers() {
+    let (mut data, eocdr) = archive().await;
+    assert!(ZipFileReader::new(data.clone()).await.is_ok());
+
+    // Both the current disk and the central directory's start disk are claimed to be disk 1.
+    data[eocdr + 4..eocdr + 8].copy_from_slice(&[1, 0, 1, 0]);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 2);
+}
+
+#[tokio::test]
+async fn false_multi_disk_claim() {
+    let (mut data, eocdr) = archive().await;
+
+    // Only one of the two entries is claimed to be on this disk.
+    data[eocdr + 8..eocdr + 10].copy_from_slice(&1u16.to_le_bytes());
+
+    assert!(matches!(ZipFileReader::new(data).await, Err(ZipError::MultiDiskUnsupported)));
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{seek, stream};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::LFH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{BufReader, Cursor};
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("entry.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"entry data").await.unwrap();
+    writer.close().await.unwrap()
+}
+
+async fn next_entry_error(data: Vec<u8>) -> ZipError {
+    match stream::ZipFileReader::new(Cursor::new(data)).next_with_entry().await {
+        Ok(_) => panic!("expected reading the local file header to fail"),
+        Err(error) => error,
+    }
+}
+
+#[tokio::test]
+async fn truncated_local_header() {
+    let mut data = archive().await;
+
+    // Within the fixed-length portion of the header.
+    data.truncate(20);
+    assert!(matches!(next_entry_error(data.clone()).await, ZipError::UnexpectedEof));
+
+    // Within the signature.
+    data.truncate(2);
+    assert!(matches!(next_entry_error(data).await, ZipError::UnexpectedEof));
+}
+
+#[tokio::test]
+async fn truncated_local_filename() {
+    let mut data = archive().await;
+    data.truncate(30 + 4);
+
+    assert!(matches!(next_entry_error(data).await, ZipError::UnexpectedEof));
+}
+
+#[tokio::test]
+async fn invalid_local_signature() {
+    let mut data = archive().await;
+    data[..4].copy_from_slice(&0xDEADBEEFu32.to_le_bytes());
+
+    assert!(matches!(next_entry_error(data.clone()).await, ZipError::UnexpectedHeaderError(0xDEADBEEF, LFH_SIGNATURE)));
+
+    let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap();
+    assert!(matches!(
+        reader.reader_with_entry(0).await,
+        Err(ZipError::UnexpectedHeaderError(0xDEADBEEF, LFH_SIGNATURE))
+    ));
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+/// PPMd, which this crate never supports regardless of enabled features.
+const PPMD: u16 = 98;
+
+/// Returns an archive of three entries, the second of which uses PPMd.
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, compression) in
+        [("a.txt", Compression::Deflate), ("b.txt", Compression::Stored), ("c.txt", Compression::Deflate)]
+    {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), compression), name.as_bytes()).await.unwrap();
+    }
+    let mut data = writer.close().await.unwrap();
+
+    // Rewrite the second entry's compression method within both its local and central directory headers.
+    let lfh_offset = ZipFileReader::new(data.clone()).await.unwrap().file().entries()[1].header_offset() as usize;
+    data[lfh_offset + 8..lfh_offset + 10].copy_from_slice(&PPMD.to_le_bytes());
+
+    let cdh_offset =
+        data.windows(4).enumerate().filter(|(_, bytes)| *bytes == CDH_SIGNATURE.to_le_bytes()).nth(1).unwrap().0;
+    data[cdh_offset + 10..cdh_offset + 12].copy_from_slice(&PPMD.to_le_bytes());
+    data
+}
+
+#[tokio::test]
+async fn unsupported_entry_alongside_supported() {
+    let reader = ZipFileReader::new(archive().await).await.unwrap();
+    assert_eq!(reader.file().len(), 3);
+    assert_eq!(reader.file().entries()[1].compression(), Compression::Unsupported(PPMD));
+    assert!(matches!(reader.reader_with_entry(1).await, Err(ZipError::CompressionNotSupported(PPMD))));
+
+    for index in [0, 2] {
+        let mut buffer = Vec::new();
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        entry_reader.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, entry_reader.entry().filename().as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn unsupported_entry_alongside_supported_stream() {
+    use crate::base::read::stream::ZipFileReader;
+    use futures_lite::io::AsyncReadExt;
+
+    let data = archive().await;
+    let mut reader = ZipFileReader::new(data.as_slice());
+    let mut names = Vec::new();
+
+    while let Some(mut entry_reader) = reader.next_with_entry().await.unwrap() {
+        let entry = entry_reader.reader().entry();
+        names.push(entry.filename().as_str().unwrap().to_string());
+
+        if entry.compression() == Compression::Unsupported(PPMD) {
+            let result = entry_reader.reader_mut().read(&mut [0; 16]).await;
+            let error = result.unwrap_err().into_inner().unwrap().downcast::<ZipError>().unwrap();
+            assert!(matches!(*error, ZipError::CompressionNotSupported(PPMD)));
+            reader = entry_reader.skip().await.unwrap();
+        } else {
+            let mut buffer = Vec::new();
+            entry_reader.reader_mut().read_to_end_checked(&mut buffer).await.unwrap();
+            assert_eq!(buffer, entry_reader.reader().entry().filename().as_bytes());
+            reader = entry_reader.done().await.unwrap();
+        }
+    }
+
+    assert_eq!(names, ["a.txt", "b.txt", "c.txt"]);
+}
+
+#[tokio::test]
+async fn unsupported_entry_write() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("a.txt".into(), Compression::Unsupported(PPMD));
+
+    assert!(matches!(writer.write_entry_whole(entry, b"data").await, Err(ZipError::CompressionNotSupported(PPMD))));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+#[should_panic(expected = "compression not supported: 98")]
+async fn unsupported_entry_compress() {
+    let entry = ZipEntryBuilder::new("a.txt".into(), Compression::Unsupported(PPMD)).build();
+    crate::base::write::compress(&entry, b"data").await;
+}
+
+#[cfg(feature = "deflate64")]
+#[tokio::test]
+async fn deflate64_entry_write() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("a.txt".into(), Compression::Deflate64);
+    assert!(matches!(writer.write_entry_whole(entry, b"data").await, Err(ZipError::CompressionNotSupported(9))));
+
+    let entry = ZipEntryBuilder::new("b.txt".into(), Compression::Deflate64);
+    assert!(matches!(writer.write_entry_stream(entry).await, Err(ZipError::CompressionNotSupported(9))));
+
+    // Nothing is written for either entry.
+    assert!(writer.close().await.unwrap().starts_with(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes()));
+}
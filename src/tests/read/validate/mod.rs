@@ -0,0 +1,75 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::read::{Discrepancy, DiscrepancyKind};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncWriteExt, BufReader, Cursor};
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, data) in [("first.txt", b"first entry"), ("second.txt", b"second data")] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), data).await.unwrap();
+    }
+
+    // Written with a data descriptor, so has no local values to compare against.
+    let builder = ZipEntryBuilder::new("third.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.unwrap();
+    entry_writer.write_all(b"third").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn validate_consistent_archive() {
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive().await))).await.unwrap();
+    assert!(reader.validate().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn validate_mismatched_archive() {
+    let mut archive = archive().await;
+    let records: Vec<usize> = archive
+        .windows(4)
+        .enumerate()
+        .filter(|(_, bytes)| *bytes == CDH_SIGNATURE.to_le_bytes())
+        .map(|(i, _)| i)
+        .collect();
+
+    // Tamper with the first record's uncompressed size, and the CRC32 values of the second and third records.
+    archive[records[0] + 24..records[0] + 28].copy_from_slice(&1u32.to_le_bytes());
+    let crc = u32::from_le_bytes(archive[records[1] + 16..records[1] + 20].try_into().unwrap());
+    for record in &records[1..] {
+        archive[record + 16..record + 20].copy_from_slice(&0xDEADBEEFu32.to_le_bytes());
+    }
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    let discrepancies = reader.validate().await.unwrap();
+
+    assert_eq!(
+        discrepancies,
+        vec![
+            Discrepancy { index: 0, kind: DiscrepancyKind::UncompressedSize { central: 1, local: 11 } },
+            Discrepancy { index: 1, kind: DiscrepancyKind::Crc32 { central: 0xDEADBEEF, local: crc } },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn mismatched_local_header_detected() {
+    // The first entry's local file header claims Deflate, and the second's a data descriptor.
+    let mut archive = archive().await;
+    archive[8..10].copy_from_slice(&8u16.to_le_bytes());
+    let second = archive.windows(4).skip(1).position(|bytes| bytes == LFH_SIGNATURE.to_le_bytes()).unwrap() + 1;
+    archive[second + 6] |= 0x08;
+
+    let mut reader = ZipFileReader::new(BufReader::new(Cursor::new(archive))).await.unwrap();
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::HeaderMismatch("compression method"))));
+    assert!(matches!(reader.reader_with_entry(1).await, Err(ZipError::HeaderMismatch("general purpose flags"))));
+    assert!(reader.reader_with_entry(2).await.is_ok());
+}
@@ -31,6 +31,33 @@ async fn test_read_zip64_archive_mem() {
     assert_eq!(read_data, ZIP64_ZIP_CONTENTS);
 }
 
+/// Tests that both the archive and its entries are flagged as zip64.
+#[tokio::test]
+async fn test_read_zip64_flags() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::base::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+    use futures_lite::io::AsyncWriteExt;
+
+    // The fixture only holds a zip64 extended information field within its local file header.
+    let reader = ZipFileReader::new(include_bytes!("zip64.zip").to_vec()).await.unwrap();
+    assert!(reader.is_zip64());
+    assert!(!reader.file().entries()[0].is_zip64());
+
+    // Streamed entries always carry a zip64 extended information field within their central directory record.
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new()).force_zip64();
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("streamed.txt".into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(b"streamed").await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("whole.txt".into(), Compression::Stored), b"whole").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert!(reader.is_zip64());
+    assert!(reader.file().entries()[0].is_zip64());
+    assert!(!reader.file().entries()[1].is_zip64());
+}
+
 /// Like test_read_zip64_archive_mem() but for the streaming version
 #[tokio::test]
 async fn test_read_zip64_archive_stream() {
@@ -106,3 +133,36 @@ async fn test_read_zip64_archive_many_entries() {
         assert_eq!(contents, "\n");
     }
 }
+
+/// Tests that a disk start number carried within a zip64 extended information extra field is parsed and exposed.
+#[tokio::test]
+async fn test_read_zip64_disk_start_number() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::base::write::ZipFileWriter;
+    use crate::spec::consts::CDH_SIGNATURE;
+    use crate::spec::header::{ExtraField, HeaderId, Zip64ExtendedInformationExtraField};
+    use crate::{Compression, ZipEntryBuilder};
+
+    init_logger();
+
+    let field = Zip64ExtendedInformationExtraField {
+        header_id: HeaderId::ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD,
+        uncompressed_size: None,
+        compressed_size: None,
+        relative_header_offset: None,
+        disk_start_number: Some(7),
+    };
+    let builder = ZipEntryBuilder::new("disk.txt".into(), Compression::Stored)
+        .extra_fields(vec![ExtraField::Zip64ExtendedInformation(field)]);
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, ZIP64_ZIP_CONTENTS.as_bytes()).await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Mark the central directory record's disk start number as stored within the zip64 extra field.
+    let cdh = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    data[cdh + 34..cdh + 36].copy_from_slice(&u16::MAX.to_le_bytes());
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].disk_start_number(), 7);
+}
@@ -42,3 +42,40 @@ fn date_conversion_test() {
     assert_eq!(minute, built.minute());
     assert_eq!(second, built.second());
 }
+
+#[test]
+#[cfg(feature = "chrono")]
+fn last_modified_epoch_test() {
+    use crate::{Compression, ZipDateTime, ZipEntry, ZipEntryBuilder};
+
+    let epoch = Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap();
+    let last_modified = |date, time| {
+        let builder = ZipEntryBuilder::new("a.txt".into(), Compression::Stored);
+        ZipEntry::from(builder.last_modification_date(ZipDateTime { date, time })).last_modified()
+    };
+
+    // 1980-01-01 00:00:00 is stored as a day & month of one.
+    assert_eq!(last_modified(0x21, 0), epoch);
+
+    // A zeroed date has no valid month or day, so should be floored.
+    assert_eq!(last_modified(0, 0), epoch);
+
+    // An hour of 31 is out of range, so should be floored.
+    assert_eq!(last_modified(0x21, 0xF800), epoch);
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn chrono_to_zip_time_round_trip_test() {
+    use crate::date::chrono_to_zip_time;
+    use crate::ZipDateTime;
+
+    let original_dt = Utc.with_ymd_and_hms(2011, 6, 15, 13, 47, 22).unwrap();
+    let (date, time) = chrono_to_zip_time(&original_dt);
+    assert_eq!(ZipDateTime { date, time }.as_chrono().single(), Some(original_dt));
+
+    // MS-DOS has a maximum granularity of two seconds, so odd seconds are truncated.
+    let odd_dt = Utc.with_ymd_and_hms(2011, 6, 15, 13, 47, 23).unwrap();
+    let (date, time) = chrono_to_zip_time(&odd_dt);
+    assert_eq!(ZipDateTime { date, time }.as_chrono().single(), Some(original_dt));
+}
@@ -0,0 +1,24 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+
+use std::error::Error;
+use std::io::ErrorKind;
+
+#[test]
+fn io_error_source_chain() {
+    let error = ZipError::from(std::io::Error::new(ErrorKind::ConnectionReset, "connection reset"));
+    assert!(matches!(error, ZipError::UpstreamReadError(_)));
+
+    let source = error.source().unwrap().downcast_ref::<std::io::Error>().unwrap();
+    assert_eq!(source.kind(), ErrorKind::ConnectionReset);
+    assert!(error.to_string().contains("connection reset"));
+}
+
+#[test]
+fn io_wrapped_zip_error_unwrapped() {
+    let error = ZipError::from(std::io::Error::other(ZipError::CRC32CheckError));
+    assert!(matches!(error, ZipError::CRC32CheckError));
+    assert!(error.source().is_none());
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::header::ExtraField;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncWriteExt, Cursor};
+
+/// Returns the data offset of each entry, checking that its data reads back correctly.
+async fn data_offsets(data: Vec<u8>, expected: &[&[u8]]) -> Vec<u64> {
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let mut cursor = Cursor::new(data);
+    let mut offsets = Vec::new();
+
+    for (index, entry) in reader.file().entries().iter().enumerate() {
+        // The padding is only written within the local file header.
+        assert!(!entry.extra_fields().iter().any(|field| matches!(field, ExtraField::Unknown(_))));
+        assert_eq!(reader.reader_with_entry(index).await.unwrap().read_to_vec().await.unwrap(), expected[index]);
+        offsets.push(entry.data_offset(&mut cursor).await.unwrap());
+    }
+
+    offsets
+}
+
+#[tokio::test]
+async fn aligned_whole_entries() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for (name, data) in [("a.txt", b"x".as_slice()), ("bb.txt", b"yy"), ("ccc.txt", b"zzz")] {
+        let builder = ZipEntryBuilder::new(name.into(), Compression::Stored).align(4);
+        writer.write_entry_whole(builder, data).await.unwrap();
+    }
+    let builder = ZipEntryBuilder::new("page.bin".into(), Compression::Stored).align(4096);
+    writer.write_entry_whole(builder, b"page").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let offsets = data_offsets(data, &[b"x", b"yy", b"zzz", b"page"]).await;
+    assert!(offsets[..3].iter().all(|offset| offset % 4 == 0));
+    assert_eq!(offsets[3] % 4096, 0);
+}
+
+#[tokio::test]
+async fn aligned_stream_entry() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"x").await.unwrap();
+
+    let builder = ZipEntryBuilder::new("b.txt".into(), Compression::Stored).align(8);
+    let mut entry = writer.write_entry_stream(builder).await.unwrap();
+    entry.write_all(b"streamed").await.unwrap();
+    entry.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let offsets = data_offsets(data, &[b"x", b"streamed"]).await;
+    assert_eq!(offsets[1] % 8, 0);
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::spec::extra_field::extra_field_from_bytes;
+use crate::spec::header::{ExtraField, HeaderId, InfoZipUnicodePathExtraField, InfoZipUnixExtraField};
+use crate::{AttributeCompatibility, Compression, ZipEntry, ZipEntryBuilder};
+
+/// Writes a single empty entry and returns the resulting ZIP file's data.
+async fn write_single(builder: ZipEntryBuilder) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, &[]).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+/// Returns the internal and external file attributes of the first central directory record within the provided data.
+fn file_attributes(data: &[u8]) -> (u16, u32) {
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let start = data.windows(4).position(|window| window == signature).unwrap();
+    let internal = u16::from_le_bytes(data[start + 36..start + 38].try_into().unwrap());
+    (internal, u32::from_le_bytes(data[start + 38..start + 42].try_into().unwrap()))
+}
+
+/// Returns the "version made by" field of the first central directory record within the provided data.
+fn version_made_by(data: &[u8]) -> u16 {
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let start = data.windows(4).position(|window| window == signature).unwrap() + 4;
+    u16::from_le_bytes([data[start], data[start + 1]])
+}
+
+#[tokio::test]
+async fn unix_permissions_host_os_round_trip() {
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).unix_permissions(0o644);
+    let data = write_single(builder).await;
+    assert_eq!(version_made_by(&data) >> 8, 3);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.host_os(), AttributeCompatibility::Unix);
+    assert_eq!(entry.unix_permissions(), Some(0o644));
+}
+
+#[tokio::test]
+async fn no_permissions_host_os_dos() {
+    let data = write_single(ZipEntryBuilder::new("file.txt".into(), Compression::Stored)).await;
+    assert_eq!(version_made_by(&data) >> 8, 0);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.host_os(), AttributeCompatibility::Dos);
+    assert_eq!(entry.unix_permissions(), None);
+}
+
+#[tokio::test]
+async fn unix_uid_gid_round_trip() {
+    let unicode = ExtraField::InfoZipUnicodePath(InfoZipUnicodePathExtraField::Unknown { version: 9, data: vec![1] });
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored)
+        .extra_fields(vec![unicode])
+        .unix_uid_gid(0, 0)
+        .unix_uid_gid(1000, 1000);
+
+    let reader = ZipFileReader::new(write_single(builder).await).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.uid_gid(), Some((1000, 1000)));
+    assert_eq!(entry.extra_fields().len(), 2);
+    assert!(matches!(entry.extra_fields()[0], ExtraField::InfoZipUnicodePath(_)));
+}
+
+#[test]
+fn unix_uid_gid_variable_width() {
+    // A 2-byte UID and a 1-byte GID, as other writers may emit for small IDs.
+    let data = [1, 2, 0xE8, 0x03, 1, 0x64];
+    let field = extra_field_from_bytes(HeaderId::INFO_ZIP_UNIX_EXTRA_FIELD, data.len() as u16, &data, 0, 0).unwrap();
+    assert!(matches!(field, ExtraField::InfoZipUnix(InfoZipUnixExtraField::V1 { uid: 1000, gid: 100 })));
+
+    let data = [1, 2, 0xE8];
+    assert!(extra_field_from_bytes(HeaderId::INFO_ZIP_UNIX_EXTRA_FIELD, data.len() as u16, &data, 0, 0).is_err());
+}
+
+#[tokio::test]
+async fn dos_attributes_compose_with_unix_permissions() {
+    let entry: ZipEntry = ZipEntryBuilder::new("file.txt".into(), Compression::Stored)
+        .internal_file_attribute(0x01)
+        .external_file_attribute(0x01 | 0x02)
+        .unix_permissions(0o444)
+        .build();
+
+    let data = write_single(entry.clone().into()).await;
+    assert_eq!(file_attributes(&data), (0x01, 0o444 << 16 | 0x03));
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_stream(entry).await.unwrap().close().await.unwrap();
+    let data = writer.close().await.unwrap();
+    assert_eq!(file_attributes(&data), (0x01, 0o444 << 16 | 0x03));
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.external_file_attribute() & 0x01, 0x01);
+    assert_eq!(entry.unix_permissions(), Some(0o444));
+}
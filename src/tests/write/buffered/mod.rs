@@ -0,0 +1,84 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWrite;
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+const ENTRY_COUNT: usize = 10_000;
+
+/// A writer which collects written data whilst counting the number of calls made to write it.
+#[derive(Default)]
+struct CountingWriter {
+    data: Vec<u8>,
+    writes: usize,
+}
+
+impl AsyncWrite for CountingWriter {
+    fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        self.writes += 1;
+        self.data.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn write_tiny_entries(mut writer: ZipFileWriter<CountingWriter>) -> CountingWriter {
+    for index in 0..ENTRY_COUNT {
+        let entry = ZipEntryBuilder::new(format!("{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"tiny").await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn buffered_many_tiny_entries() {
+    let unbuffered = write_tiny_entries(ZipFileWriter::new(CountingWriter::default())).await;
+    let buffered =
+        write_tiny_entries(ZipFileWriterBuilder::new(CountingWriter::default()).buffered(64 * 1024).build()).await;
+
+    assert_eq!(buffered.data, unbuffered.data);
+    assert!(buffered.writes * 100 < unbuffered.writes, "{} vs {} writes", buffered.writes, unbuffered.writes);
+
+    let reader = crate::base::read::mem::ZipFileReader::new(buffered.data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), ENTRY_COUNT);
+}
+
+#[tokio::test]
+async fn buffered_offset_tracks_logical_bytes() {
+    use futures_lite::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriterBuilder::new(CountingWriter::default()).buffered(1024).build();
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    // Nothing has reached the inner writer yet, but the offset must still reflect the written entry.
+    assert_eq!(writer.inner_mut().writes, 0);
+    assert_eq!(writer.writer.offset(), 30 + 7 + 3);
+
+    // Writes larger than the buffer's capacity are passed straight through.
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(&[0; 2048]).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    writer.writer.flush().await.unwrap();
+    assert_eq!(writer.writer.offset() as usize, writer.inner_mut().data.len());
+
+    let inner = writer.close().await.unwrap();
+    let reader = crate::base::read::mem::ZipFileReader::new(inner.data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 2);
+}
@@ -0,0 +1,22 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{central_directory, central_directory_parallel, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn parallel_central_directory_matches_sequential() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+
+    for index in 0..50_000 {
+        let builder = ZipEntryBuilder::new(format!("dir-{}/file-{index}.txt", index % 7).into(), Compression::Stored)
+            .comment("x".repeat(index % 5).into());
+        writer.write_entry_whole(builder, &[]).await.unwrap();
+    }
+
+    let sequential = central_directory(&writer.cd_entries);
+    let parallel = central_directory_parallel(&writer.cd_entries);
+
+    assert!(!sequential.is_empty());
+    assert_eq!(sequential, parallel);
+}
@@ -0,0 +1,44 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::{Compression, ZipDateTime, ZipDateTimeBuilder, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+fn date(year: i32) -> ZipDateTime {
+    ZipDateTimeBuilder::new().year(year).month(6).day(15).hour(12).minute(30).second(10).build()
+}
+
+#[tokio::test]
+async fn fixed_clock_shared_timestamp() {
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).clock(|| date(2020)).build();
+
+    for name in ["first.txt", "second.txt"] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), b"data").await.unwrap();
+    }
+
+    let builder = ZipEntryBuilder::new("stream.txt".into(), Compression::Stored);
+    let mut stream = writer.write_entry_stream(builder).await.unwrap();
+    stream.write_all(b"data").await.unwrap();
+    stream.close().await.unwrap();
+
+    // Entries which set their own date keep it.
+    let builder = ZipEntryBuilder::new("dated.txt".into(), Compression::Stored).last_modification_date(date(2010));
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let dates: Vec<_> = reader.file().entries().iter().map(|entry| *entry.last_modification_date()).collect();
+
+    assert_eq!(dates, [date(2020), date(2020), date(2020), date(2010)]);
+}
+
+#[tokio::test]
+async fn no_clock_zeroed_timestamp() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("file.txt".into(), Compression::Stored), b"data").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(*reader.file().entries()[0].last_modification_date(), ZipDateTime::default());
+}
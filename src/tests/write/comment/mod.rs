@@ -0,0 +1,37 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{EOCDR_LENGTH, EOCDR_SIGNATURE, SIGNATURE_LENGTH};
+
+#[tokio::test]
+async fn binary_comment_bytes() {
+    let comment = vec![0xFF, 0x00, 0xFE, 0x80, 0x7F];
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment_bytes(comment.clone()).unwrap();
+    let data = writer.close().await.unwrap();
+
+    // With no entries, the output solely consists of the EOCDR and the trailing comment.
+    assert_eq!(data.len(), SIGNATURE_LENGTH + EOCDR_LENGTH + comment.len());
+    assert_eq!(data[0..SIGNATURE_LENGTH], EOCDR_SIGNATURE.to_le_bytes());
+
+    let length_offset = SIGNATURE_LENGTH + EOCDR_LENGTH - 2;
+    assert_eq!(data[length_offset..length_offset + 2], (comment.len() as u16).to_le_bytes());
+    assert_eq!(data[SIGNATURE_LENGTH + EOCDR_LENGTH..], comment[..]);
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.comment_bytes(), Some(comment.as_slice()));
+}
+
+#[tokio::test]
+async fn binary_comment_bytes_too_large() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let result = writer.comment_bytes(vec![0; u16::MAX as usize + 1]);
+    assert!(matches!(result, Err(ZipError::CommentTooLarge)));
+
+    writer.comment_bytes(vec![0; u16::MAX as usize]).unwrap();
+    writer.close().await.unwrap();
+}
@@ -0,0 +1,54 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::{SortKey, ZipFileWriterBuilder};
+use crate::error::ZipError;
+use crate::{Compression, ZipDateTimeBuilder, ZipEntryBuilder};
+
+#[tokio::test]
+async fn builder_options() {
+    let date = ZipDateTimeBuilder::new().year(2020).month(6).day(15).build();
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new())
+        .buffered(1024)
+        .reject_duplicates(true)
+        .clock(move || date)
+        .force_zip64()
+        .scratch_buffer(Vec::with_capacity(64))
+        .sort_central_directory(SortKey::Name)
+        .panic_if_unclosed()
+        .build();
+
+    for name in ["second.txt", "first.txt"] {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), Compression::Stored), b"data").await.unwrap();
+    }
+
+    // Duplicates are rejected, and nothing is written whilst the headers remain buffered.
+    let duplicate = ZipEntryBuilder::new("first.txt".into(), Compression::Stored);
+    assert!(matches!(writer.write_entry_whole(duplicate, b"data").await, Err(ZipError::DuplicateEntryName(_))));
+    assert!(writer.inner_mut().is_empty());
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert!(reader.file().zip64);
+    let names: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect();
+    assert_eq!(names, ["first.txt", "second.txt"]);
+    assert!(reader.file().entries().iter().all(|entry| entry.last_modification_date() == &date));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn default_compression_inherited() {
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).default_compression(Compression::Deflate).build();
+    writer.write_entry_whole(ZipEntryBuilder::new_named("inherited.txt".into()), b"data").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("explicit.txt".into(), Compression::Stored), b"data").await.unwrap();
+
+    let builder = ZipEntryBuilder::new_named("stream.txt".into());
+    writer.write_entry_stream(builder).await.unwrap().close().await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let methods: Vec<_> = reader.file().entries().iter().map(|entry| entry.compression()).collect();
+    assert_eq!(methods, [Compression::Deflate, Compression::Stored, Compression::Deflate]);
+
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    assert_eq!(entry.read_to_vec().await.unwrap(), b"data");
+}
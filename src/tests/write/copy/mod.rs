@@ -0,0 +1,165 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::read::seek;
+use crate::base::write::ZipFileWriter;
+use crate::entry::StoredZipEntry;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, AsyncWriteExt, BufReader, Cursor};
+
+/// Returns the raw compressed bytes of an entry within an archive.
+async fn raw_data(archive: &[u8], entry: &StoredZipEntry) -> Vec<u8> {
+    let mut cursor = Cursor::new(archive);
+    entry.seek_to_data_offset(&mut cursor).await.unwrap();
+
+    let mut data = vec![0; entry.compressed_size() as usize];
+    cursor.read_exact(&mut data).await.unwrap();
+    data
+}
+
+#[tokio::test]
+async fn copy_raw_deflate_entry() {
+    let data = b"some highly compressible data, ".repeat(64);
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("source.txt".into(), Compression::Deflate), &data).await.unwrap();
+    let source = writer.close().await.unwrap();
+
+    let source_reader = ZipFileReader::new(source.clone()).await.unwrap();
+    let source_entry = &source_reader.file().entries()[0];
+    let compressed = raw_data(&source, source_entry).await;
+    assert!(compressed.len() < data.len());
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("copied.txt".into(), Compression::Deflate);
+    let written = writer
+        .copy_entry_raw(entry, source_entry.crc32(), source_entry.uncompressed_size(), &compressed)
+        .await
+        .unwrap();
+    assert_eq!(written.compressed_size, compressed.len() as u64);
+
+    let destination = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(destination.clone()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.crc32(), source_entry.crc32());
+    assert_eq!(raw_data(&destination, entry).await, compressed);
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data);
+}
+
+#[tokio::test]
+async fn merge_archives() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Deflate), b"alpha alpha").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("b.txt".into(), Compression::Stored), b"bravo").await.unwrap();
+    let first = writer.close().await.unwrap();
+
+    // Written with a data descriptor, so its local header holds no CRC or sizes.
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("c.txt".into(), Compression::Deflate)).await.unwrap();
+    entry_writer.write_all(b"charlie charlie charlie").await.unwrap();
+    entry_writer.close().await.unwrap();
+    let second = writer.close().await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for source in [first, second] {
+        let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(source))).await.unwrap();
+        for index in 0..reader.file().len() {
+            writer.copy_entry_from(&mut reader, index).await.unwrap();
+        }
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let expected = [("a.txt", &b"alpha alpha"[..]), ("b.txt", b"bravo"), ("c.txt", b"charlie charlie charlie")];
+    assert_eq!(reader.file().len(), expected.len());
+
+    for (index, (name, data)) in expected.into_iter().enumerate() {
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        assert_eq!(entry_reader.entry().filename().as_str().unwrap(), name);
+
+        let mut buffer = Vec::new();
+        entry_reader.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, data);
+    }
+}
+
+#[tokio::test]
+async fn write_external_deflate_stream() {
+    use crate::error::ZipError;
+    use std::io::Write;
+
+    let data = b"compressed elsewhere, ".repeat(32);
+    let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&data).unwrap();
+    let compressed = encoder.finish().unwrap();
+    let crc = crc32fast::hash(&data);
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("external.txt".into(), Compression::Deflate);
+    writer.write_entry_precompressed(entry, crc, data.len() as u64, &compressed).await.unwrap();
+
+    // Stored data must be exactly as long as the uncompressed size.
+    let entry = ZipEntryBuilder::new("mislabelled.txt".into(), Compression::Stored);
+    let result = writer.write_entry_precompressed(entry, crc, data.len() as u64, &compressed).await;
+    assert!(matches!(result, Err(ZipError::PrecompressedMethodMismatch)));
+
+    // Deflate data can't be empty, start with a block of the reserved type, or a stored block with a mismatched length.
+    for invalid in [&[][..], &[0b111, 0, 0], &[0b001, 4, 0, 4, 0, 1, 2, 3, 4]] {
+        let entry = ZipEntryBuilder::new("invalid.txt".into(), Compression::Deflate);
+        let result = writer.write_entry_precompressed(entry, crc, data.len() as u64, invalid).await;
+        assert!(matches!(result, Err(ZipError::PrecompressedMethodMismatch)));
+    }
+
+    // A stored block's data is accepted as Deflate.
+    let entry = ZipEntryBuilder::new("stored_block.txt".into(), Compression::Deflate);
+    let block = [0b001, 4, 0, !4, !0, 1, 2, 3, 4];
+    writer.write_entry_precompressed(entry, crc32fast::hash(&[1, 2, 3, 4]), 4, &block).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().len(), 2);
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), data);
+    assert_eq!(reader.reader_with_entry(1).await.unwrap().read_to_vec().await.unwrap(), [1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn copy_encrypted_entries_rejected() {
+    use crate::error::ZipError;
+    use crate::spec::consts::CDH_SIGNATURE;
+
+    let aes = [2, 0, b'A', b'E', 3, 8, 0];
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new("zipcrypto.txt".into(), Compression::Stored);
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let builder = ZipEntryBuilder::new("aes.txt".into(), Compression::Stored).add_extra_field(0x9901, &aes);
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let mut source = writer.close().await.unwrap();
+
+    // Mark both entries as encrypted within their central directory records, and the second as AES.
+    let records: Vec<usize> = source
+        .windows(4)
+        .enumerate()
+        .filter(|(_, bytes)| *bytes == CDH_SIGNATURE.to_le_bytes())
+        .map(|(index, _)| index)
+        .collect();
+    for record in &records {
+        source[record + 8] |= 1;
+    }
+    source[records[1] + 10..records[1] + 12].copy_from_slice(&99u16.to_le_bytes());
+
+    let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(source))).await.unwrap();
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for index in 0..2 {
+        let result = writer.copy_entry_from(&mut reader, index).await;
+        assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().len(), 0);
+}
@@ -0,0 +1,101 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::{Compression, Crc32, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Returns the CRC32 fields of the first local file header and central directory record.
+fn crcs(data: &[u8]) -> (u32, u32) {
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let record = data.windows(4).position(|window| window == signature).unwrap();
+    let read = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    (read(14), read(record + 16))
+}
+
+#[tokio::test]
+async fn trusted_crc_written_verbatim() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).trusted_crc32(Some(0xDEADBEEF));
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    assert_eq!(crcs(&data), (0xDEADBEEF, 0xDEADBEEF));
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].crc32(), 0xDEADBEEF);
+}
+
+#[tokio::test]
+async fn untrusted_crc_computed() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).trusted_crc32(None);
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let expected = crc32fast::hash(b"data");
+    assert_eq!(crcs(&data), (expected, expected));
+}
+
+/// A hasher which counts its updates, and optionally flips the bits of the checksum produced by `crc32fast`.
+struct CountingHasher {
+    inner: crc32fast::Hasher,
+    updates: Arc<AtomicUsize>,
+    invert: bool,
+}
+
+impl Crc32 for CountingHasher {
+    fn update(&mut self, data: &[u8]) {
+        self.updates.fetch_add(1, Ordering::SeqCst);
+        self.inner.update(data);
+    }
+
+    fn sum(&self) -> u32 {
+        match self.invert {
+            true => !self.inner.sum(),
+            false => self.inner.sum(),
+        }
+    }
+
+    fn reset(&mut self) {
+        Crc32::reset(&mut self.inner);
+    }
+}
+
+fn counting_hasher(updates: &Arc<AtomicUsize>, invert: bool) -> Box<dyn Crc32> {
+    Box::new(CountingHasher { inner: crc32fast::Hasher::new(), updates: updates.clone(), invert })
+}
+
+#[tokio::test]
+async fn custom_hasher_invoked() {
+    let write_updates = Arc::new(AtomicUsize::new(0));
+    let hasher_updates = write_updates.clone();
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new())
+        .crc32_hasher(move || counting_hasher(&hasher_updates, false))
+        .build();
+
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored);
+    let mut entry = writer.write_entry_stream(builder).await.unwrap();
+    entry.write_all(b"streamed data").await.unwrap();
+    entry.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    assert!(write_updates.load(Ordering::SeqCst) > 0);
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].crc32(), crc32fast::hash(b"streamed data"));
+
+    let read_updates = Arc::new(AtomicUsize::new(0));
+    let mut entry = reader.reader_with_entry(0).await.unwrap().with_crc32_hasher(counting_hasher(&read_updates, false));
+    assert_eq!(entry.read_to_vec().await.unwrap(), b"streamed data");
+    assert!(read_updates.load(Ordering::SeqCst) > 0);
+
+    // A hasher which disagrees with the stored checksum fails the read-side check.
+    let mut entry = reader.reader_with_entry(0).await.unwrap().with_crc32_hasher(counting_hasher(&read_updates, true));
+    assert!(matches!(entry.read_to_vec().await, Err(ZipError::CRC32CheckError)));
+}
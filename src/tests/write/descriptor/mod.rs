@@ -0,0 +1,39 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{DATA_DESCRIPTOR_SIGNATURE, LFH_LENGTH, SIGNATURE_LENGTH};
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn forced_descriptor_whole_write() {
+    let builder = ZipEntryBuilder::new("a.txt".into(), Compression::Stored).force_data_descriptor(true);
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, b"hello").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    // The local file header holds a zero CRC32 and sizes, with the descriptor flag (general purpose bit 3) set.
+    assert_eq!(data[6] & 0x8, 0x8);
+    assert_eq!(&data[14..26], &[0; 12]);
+
+    let descriptor = SIGNATURE_LENGTH + LFH_LENGTH + "a.txt".len() + "hello".len();
+    assert_eq!(&data[descriptor..descriptor + 4], &DATA_DESCRIPTOR_SIGNATURE.to_le_bytes());
+    assert_eq!(&data[descriptor + 4..descriptor + 8], &crc32fast::hash(b"hello").to_le_bytes());
+    assert_eq!(&data[descriptor + 8..descriptor + 16], &[5, 0, 0, 0, 5, 0, 0, 0]);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].crc32(), crc32fast::hash(b"hello"));
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), b"hello");
+}
+
+#[tokio::test]
+async fn descriptor_not_forced_by_default() {
+    let builder = ZipEntryBuilder::new("a.txt".into(), Compression::Stored);
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, b"hello").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    assert_eq!(data[6] & 0x8, 0);
+    assert!(!data.windows(4).any(|bytes| bytes == DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()));
+}
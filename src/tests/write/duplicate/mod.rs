@@ -0,0 +1,68 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+fn entry(name: &str) -> ZipEntryBuilder {
+    ZipEntryBuilder::new(name.into(), Compression::Stored)
+}
+
+#[tokio::test]
+async fn duplicates_permitted_by_default() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(entry("a.txt"), b"first").await.unwrap();
+    writer.write_entry_whole(entry("a.txt"), b"second").await.unwrap();
+    writer.close().await.unwrap();
+
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).reject_duplicates(false).build();
+    writer.write_entry_whole(entry("a.txt"), b"first").await.unwrap();
+    writer.write_entry_whole(entry("a.txt"), b"second").await.unwrap();
+    writer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn duplicates_rejected_whole() {
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).reject_duplicates(true).build();
+    writer.write_entry_whole(entry("a.txt"), b"first").await.unwrap();
+    writer.write_entry_whole(entry("b.txt"), b"other").await.unwrap();
+
+    let offset = writer.writer.offset();
+    let result = writer.write_entry_whole(entry("a.txt"), b"second").await;
+
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(name)) if name == "a.txt"));
+    assert_eq!(writer.writer.offset(), offset);
+}
+
+#[tokio::test]
+async fn duplicates_rejected_stream() {
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).reject_duplicates(true).build();
+    writer.write_entry_whole(entry("a.txt"), b"first").await.unwrap();
+
+    let result = writer.write_entry_stream(entry("a.txt")).await;
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(name)) if name == "a.txt"));
+}
+
+#[tokio::test]
+async fn duplicates_failed_entry_retried() {
+    use futures_lite::io::AsyncRead;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    struct FailingReader;
+
+    impl AsyncRead for FailingReader {
+        fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, _: &mut [u8]) -> Poll<std::io::Result<usize>> {
+            Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "broken pipe")))
+        }
+    }
+
+    // The aborted entry was never written, so its filename remains available.
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).reject_duplicates(true).build();
+    assert!(writer.write_entry_from_reader(entry("a.txt"), FailingReader).await.is_err());
+    writer.write_entry_whole(entry("a.txt"), b"first").await.unwrap();
+
+    let result = writer.write_entry_whole(entry("a.txt"), b"second").await;
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(name)) if name == "a.txt"));
+}
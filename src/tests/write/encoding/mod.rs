@@ -0,0 +1,104 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::{Compression, NameEncoding, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+const UTF8_FLAG: u16 = 1 << 11;
+
+/// Returns the general purpose flags of the first local file header and central directory record within the data.
+fn flags(data: &[u8]) -> (u16, u16) {
+    let find = |signature: u32| data.windows(4).position(|window| window == signature.to_le_bytes()).unwrap();
+    let read = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+
+    (read(find(LFH_SIGNATURE) + 6), read(find(CDH_SIGNATURE) + 8))
+}
+
+#[tokio::test]
+async fn utf8_flag_non_ascii_filename_whole() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("\u{444}\u{430}\u{439}\u{43b}.txt".into(), Compression::Stored);
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let (local, central) = flags(&data);
+    assert_eq!(local & UTF8_FLAG, UTF8_FLAG);
+    assert_eq!(local, central);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.filename().as_str().unwrap(), "\u{444}\u{430}\u{439}\u{43b}.txt");
+    assert_eq!(entry.name_encoding(), NameEncoding::Utf8);
+}
+
+#[tokio::test]
+async fn utf8_flag_non_ascii_filename_stream() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("\u{444}\u{430}\u{439}\u{43b}.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.unwrap();
+    entry_writer.write_all(b"data").await.unwrap();
+    entry_writer.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let (local, central) = flags(&data);
+    assert_eq!(local & UTF8_FLAG, UTF8_FLAG);
+    assert_eq!(local & UTF8_FLAG, central & UTF8_FLAG);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].filename().as_str().unwrap(), "\u{444}\u{430}\u{439}\u{43b}.txt");
+}
+
+#[tokio::test]
+async fn utf8_flag_non_ascii_comment() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).comment("\u{43a}\u{43e}\u{43c}".into());
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let (local, central) = flags(&data);
+    assert_eq!(local & UTF8_FLAG, UTF8_FLAG);
+    assert_eq!(local, central);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].comment().as_str().unwrap(), "\u{43a}\u{43e}\u{43c}");
+}
+
+#[tokio::test]
+async fn raw_name_round_trip() {
+    let name = b"invalid-\xff\xfe-\xc3.bin".to_vec();
+    assert!(std::str::from_utf8(&name).is_err());
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(ZipEntryBuilder::from_raw_name(name.clone(), Compression::Stored), b"data").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let flags = u16::from_le_bytes([data[6], data[7]]);
+    assert_eq!(flags & UTF8_FLAG, 0);
+    assert_eq!(&data[30..30 + name.len()], name.as_slice());
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.raw_name_bytes(), name.as_slice());
+    assert_eq!(entry.name_encoding(), NameEncoding::Cp437);
+}
+
+#[tokio::test]
+async fn normalized_path_separators() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("\\a\\b.txt".into(), Compression::Stored).normalize_path();
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let builder = ZipEntryBuilder::from_raw_name(b"raw\\c.txt".to_vec(), Compression::Stored);
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    // The local file header's filename directly follows its 30 fixed bytes.
+    assert_eq!(&data[30..30 + "a/b.txt".len()], b"a/b.txt");
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let names: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_bytes().to_vec()).collect();
+    assert_eq!(names, [b"a/b.txt".to_vec(), b"raw\\c.txt".to_vec()]);
+}
@@ -0,0 +1,131 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::spec::header::{ExtraField, InfoZipUnixExtraField};
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn add_extra_field_framing() {
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored)
+        .unix_uid_gid(1000, 1000)
+        .add_extra_field(0xCAFE, &[1, 2, 3])
+        .add_extra_field(0xBEEF, &[]);
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, &[]).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    // The local file header's extra fields directly follow its 30 fixed bytes and the filename.
+    let extra_length = u16::from_le_bytes([data[28], data[29]]) as usize;
+    let extra = &data[30 + "file.txt".len()..][..extra_length];
+
+    assert_eq!(extra_length, 15 + 7 + 4);
+    assert_eq!(&extra[15..], &[0xFE, 0xCA, 3, 0, 1, 2, 3, 0xEF, 0xBE, 0, 0]);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let fields = reader.file().entries()[0].extra_fields();
+
+    assert_eq!(fields.len(), 3);
+    assert!(matches!(fields[0], ExtraField::InfoZipUnix(InfoZipUnixExtraField::V1 { uid: 1000, gid: 1000 })));
+    assert!(
+        matches!(&fields[1], ExtraField::Unknown(field) if field.header_id.0 == 0xCAFE && field.content == [1, 2, 3])
+    );
+    assert!(
+        matches!(&fields[2], ExtraField::Unknown(field) if field.header_id.0 == 0xBEEF && field.content.is_empty())
+    );
+}
+
+#[tokio::test]
+async fn add_extra_field_too_large() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+
+    let builder = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored).add_extra_field(0xCAFE, &[0; 70_000]);
+    assert!(matches!(writer.write_entry_whole(builder, &[]).await, Err(ZipError::ExtraFieldTooLarge)));
+
+    let builder = ZipEntryBuilder::new("stream.txt".into(), Compression::Stored).add_extra_field(0xCAFE, &[0; 70_000]);
+    assert!(matches!(writer.write_entry_stream(builder).await, Err(ZipError::ExtraFieldTooLarge)));
+
+    assert!(ZipFileReader::new(writer.close().await.unwrap()).await.unwrap().file().entries().is_empty());
+}
+
+/// Returns the extra field lengths of the first local file header and central directory record within the data.
+fn extra_field_lengths(data: &[u8]) -> (u16, u16) {
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let cdh = data.windows(4).position(|window| window == signature).unwrap();
+    (u16::from_le_bytes([data[28], data[29]]), u16::from_le_bytes([data[cdh + 30], data[cdh + 31]]))
+}
+
+#[tokio::test]
+async fn separate_local_and_central_extra_fields() {
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored)
+        .add_extra_field(0x5455, &[3, 0, 0, 0, 0, 0, 0, 0, 0])
+        .local_extra_fields(vec![]);
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    assert_eq!(extra_field_lengths(&data), (0, 4 + 9));
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].extra_fields().len(), 1);
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), b"data");
+}
+
+#[tokio::test]
+async fn separate_local_and_central_extra_fields_stream() {
+    let local = ExtraField::InfoZipUnix(InfoZipUnixExtraField::V1 { uid: 1000, gid: 1000 });
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).local_extra_fields(vec![local]);
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_from_reader(builder, &b"data"[..]).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    // Both headers hold the writer's ZIP64 extended information field, which differs in size between the two.
+    assert_eq!(extra_field_lengths(&data), (15 + 20, 28));
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), b"data");
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn ntfs_times_round_trip() {
+    use chrono::{TimeZone, Utc};
+
+    let mtime = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let atime = Utc.timestamp_opt(1_704_067_200, 123_456_700).unwrap();
+    let ctime = Utc.with_ymd_and_hms(1601, 1, 1, 0, 0, 0).unwrap();
+
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).ntfs_times(mtime, atime, ctime);
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    writer.write_entry_whole(builder, &[]).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let extra = &data[30 + "file.txt".len()..][..36];
+    assert_eq!(&extra[..12], &[0x0A, 0, 32, 0, 0, 0, 0, 0, 1, 0, 24, 0]);
+    assert_eq!(extra[12..20], 133_485_408_000_000_000u64.to_le_bytes());
+    assert_eq!(extra[20..28], 133_485_408_001_234_567u64.to_le_bytes());
+    assert_eq!(extra[28..36], 0u64.to_le_bytes());
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].ntfs_times(), Some((mtime, atime, ctime)));
+}
+
+#[test]
+fn ntfs_without_times_attribute() {
+    use crate::spec::extra_field::extra_field_from_bytes;
+    use crate::spec::header::HeaderId;
+
+    // Reserved bytes followed by a single attribute with an unrecognised tag.
+    let data = [0, 0, 0, 0, 2, 0, 2, 0, 0xAB, 0xCD];
+    let field = extra_field_from_bytes(HeaderId::NTFS_EXTRA_FIELD, data.len() as u16, &data, 0, 0).unwrap();
+    assert!(matches!(field, ExtraField::Unknown(_)));
+
+    let data = [0, 0, 0, 0, 1, 0, 24, 0, 0];
+    assert!(extra_field_from_bytes(HeaderId::NTFS_EXTRA_FIELD, data.len() as u16, &data, 0, 0).is_err());
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+#[cfg(feature = "deflate")]
+use crate::base::write::ZipFileWriter;
+use crate::base::write::ZipFileWriterBuilder;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWrite;
+use std::io::Error;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// An AsyncWrite which appends to a shared buffer, allowing its contents to be inspected whilst it's being written to.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+}
+
+impl AsyncWrite for SharedBuffer {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Streams two chunks of data via an entry built from the provided builder, flushing after each.
+#[cfg(feature = "deflate")]
+async fn assert_stream_flush_reaches_writer(builder: ZipEntryBuilder) {
+    use futures_lite::io::AsyncWriteExt;
+
+    let buffer = SharedBuffer::default();
+    let mut writer = ZipFileWriter::new(buffer.clone());
+
+    let mut stream = writer.write_entry_stream(builder).await.unwrap();
+    let header = buffer.len();
+
+    // The encoder holds on to small amounts of input until more arrives or it's flushed.
+    stream.write_all(b"first chunk of data").await.unwrap();
+    assert_eq!(buffer.len(), header);
+
+    stream.flush().await.unwrap();
+    let flushed = buffer.len();
+    assert!(flushed > header);
+
+    // Flushing again without any new input shouldn't emit further sync blocks.
+    stream.flush().await.unwrap();
+    assert_eq!(buffer.len(), flushed);
+
+    stream.write_all(b", and the second").await.unwrap();
+    stream.flush().await.unwrap();
+    assert!(buffer.len() > flushed);
+
+    stream.close().await.unwrap();
+    writer.close().await.unwrap();
+
+    let data = buffer.0.lock().unwrap().clone();
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let read = reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap();
+    assert_eq!(read, b"first chunk of data, and the second");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_flush_reaches_writer() {
+    assert_stream_flush_reaches_writer(ZipEntryBuilder::new("stream.txt".into(), Compression::Deflate)).await;
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_flush_reaches_writer_with_strategy() {
+    let builder = ZipEntryBuilder::new("stream.txt".into(), Compression::Deflate);
+    assert_stream_flush_reaches_writer(builder.deflate_strategy(crate::DeflateStrategy::Rle)).await;
+}
+
+#[tokio::test]
+async fn writer_flush_reaches_writer() {
+    let buffer = SharedBuffer::default();
+    let mut writer = ZipFileWriterBuilder::new(buffer.clone()).buffered(64 * 1024).build();
+
+    let builder = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored);
+    writer.write_entry_whole(builder, b"whole entry data").await.unwrap();
+    assert_eq!(buffer.len(), 0);
+
+    writer.flush().await.unwrap();
+    assert_eq!(buffer.len() as u64, writer.writer.offset());
+
+    writer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn flush_after_each_entry() {
+    let buffer = SharedBuffer::default();
+    let mut writer = ZipFileWriterBuilder::new(buffer.clone()).buffered(64 * 1024).flush_after_each_entry(true).build();
+
+    for name in ["first.txt", "second.txt"] {
+        let builder = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(builder, name.as_bytes()).await.unwrap();
+        assert_eq!(buffer.len() as u64, writer.writer.offset());
+    }
+
+    let builder = ZipEntryBuilder::new("raw.txt".into(), Compression::Stored);
+    writer.copy_entry_raw(builder, crc32fast::hash(b"raw"), 3, b"raw").await.unwrap();
+    assert_eq!(buffer.len() as u64, writer.writer.offset());
+
+    writer.close().await.unwrap();
+
+    let data = buffer.0.lock().unwrap().clone();
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 3);
+}
+
+#[cfg(feature = "tokio-parallel")]
+#[tokio::test]
+async fn flush_after_parallel_entries() {
+    let buffer = SharedBuffer::default();
+    let mut writer = ZipFileWriterBuilder::new(buffer.clone())
+        .buffered(64 * 1024)
+        .flush_after_each_entry(true)
+        .scratch_buffer(Vec::new())
+        .build();
+
+    let entries: Vec<_> = ["first.txt", "second.txt"]
+        .into_iter()
+        .map(|name| (ZipEntryBuilder::new(name.into(), Compression::Stored), name.as_bytes().to_vec()))
+        .collect();
+    writer.write_entries_parallel(entries).await.unwrap();
+    assert_eq!(buffer.len() as u64, writer.writer.offset());
+    assert!(writer.scratch.is_some());
+
+    writer.close().await.unwrap();
+}
@@ -0,0 +1,88 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriterBuilder;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncRead, Cursor};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+const ENTRIES: [(&str, &[u8]); 3] = [("a.txt", b"first"), ("dir/b.txt", b"second"), ("c.txt", b"")];
+
+struct FailingReader;
+
+impl AsyncRead for FailingReader {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, _: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "broken pipe")))
+    }
+}
+
+#[tokio::test]
+async fn entries_from_stream() {
+    let entries = futures_lite::stream::iter(
+        ENTRIES.map(|(name, data)| (ZipEntryBuilder::new(name.into(), Compression::Stored), Cursor::new(data))),
+    );
+
+    let writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).panic_if_unclosed().build();
+    let data = writer.write_entries_from_stream(entries).await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), ENTRIES.len());
+
+    for (index, (name, data)) in ENTRIES.iter().enumerate() {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        assert_eq!(entry.entry().filename().as_str().unwrap(), *name);
+        assert_eq!(entry.read_to_vec().await.unwrap(), *data);
+    }
+}
+
+#[tokio::test]
+async fn entries_from_stream_abort() {
+    let readers: [Box<dyn AsyncRead + Unpin>; 3] =
+        [Box::new(Cursor::new(b"first")), Box::new(FailingReader), Box::new(Cursor::new(b"third"))];
+    let entries = futures_lite::stream::iter(
+        ENTRIES
+            .into_iter()
+            .zip(readers)
+            .map(|((name, _), reader)| (ZipEntryBuilder::new(name.into(), Compression::Stored), reader)),
+    );
+
+    // The abandoned writers don't panic on drop, despite never being closed.
+    let writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).panic_if_unclosed().build();
+    let result = writer.write_entries_from_stream(entries).await;
+    assert!(matches!(result, Err(ZipError::UpstreamReadError(e)) if e.kind() == std::io::ErrorKind::BrokenPipe));
+}
+
+#[tokio::test]
+async fn entry_from_bytes_stream() {
+    let expected: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+    let chunks = expected.chunks(1000).map(|chunk| Ok::<_, std::io::Error>(chunk.to_vec())).collect::<Vec<_>>();
+
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).panic_if_unclosed().build();
+    let builder = ZipEntryBuilder::new("chunked.bin".into(), Compression::Stored);
+    let written = writer.write_entry_from_bytes_stream(builder, futures_lite::stream::iter(chunks)).await.unwrap();
+    assert_eq!(written.compressed_size, expected.len() as u64);
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn entry_from_bytes_stream_abort() {
+    let chunks: [std::io::Result<&[u8]>; 2] =
+        [Ok(b"partial"), Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "broken pipe"))];
+
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).panic_if_unclosed().build();
+    let builder = ZipEntryBuilder::new("aborted.txt".into(), Compression::Stored);
+    let result = writer.write_entry_from_bytes_stream(builder, futures_lite::stream::iter(chunks)).await;
+    assert!(matches!(result, Err(ZipError::UpstreamReadError(e)) if e.kind() == std::io::ErrorKind::BrokenPipe));
+
+    // The aborted entry isn't recorded, but the writer remains usable.
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"first").await.unwrap();
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 1);
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), b"first");
+}
@@ -6,7 +6,38 @@ use std::io::Error;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+pub(crate) mod align;
+pub(crate) mod attribute;
+pub(crate) mod buffered;
+#[cfg(feature = "rayon")]
+pub(crate) mod central_directory;
+pub(crate) mod clock;
+pub(crate) mod comment;
+pub(crate) mod config;
+#[cfg(feature = "deflate")]
+pub(crate) mod copy;
+pub(crate) mod crc;
+pub(crate) mod descriptor;
+pub(crate) mod duplicate;
+pub(crate) mod encoding;
+pub(crate) mod extra;
+pub(crate) mod flush;
+pub(crate) mod from_stream;
 pub(crate) mod offset;
+#[cfg(feature = "tokio-parallel")]
+pub(crate) mod parallel;
+pub(crate) mod patch;
+#[cfg(feature = "unstable")]
+pub(crate) mod raw;
+#[cfg(feature = "deflate")]
+pub(crate) mod scratch;
+pub(crate) mod sort;
+#[cfg(feature = "deflate")]
+pub(crate) mod strategy;
+pub(crate) mod summary;
+pub(crate) mod unclosed;
+pub(crate) mod version;
+pub(crate) mod written;
 #[cfg(target_pointer_width = "64")]
 mod zip64;
 
@@ -0,0 +1,47 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+fn batch(compression: Compression) -> Vec<(ZipEntryBuilder, Vec<u8>)> {
+    (0..64)
+        .map(|index| {
+            let data = format!("entry {index} ").repeat(index * 64).into_bytes();
+            (ZipEntryBuilder::new(format!("{index}.txt").into(), compression), data)
+        })
+        .collect()
+}
+
+async fn assert_parallel_matches_sequential(compression: Compression) {
+    let mut sequential = ZipFileWriter::new(Vec::new());
+    for (entry, data) in batch(compression) {
+        sequential.write_entry_whole(entry, &data).await.unwrap();
+    }
+    let sequential = sequential.close().await.unwrap();
+
+    let mut parallel = ZipFileWriter::new(Vec::new());
+    parallel.write_entries_parallel(batch(compression)).await.unwrap();
+    let parallel = parallel.close().await.unwrap();
+
+    assert_eq!(parallel, sequential);
+
+    let reader = crate::base::read::mem::ZipFileReader::new(parallel).await.unwrap();
+    for (index, (_, data)) in batch(compression).into_iter().enumerate() {
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        let mut read = Vec::new();
+        entry_reader.read_to_end_checked(&mut read).await.unwrap();
+        assert_eq!(read, data);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn parallel_stored_matches_sequential() {
+    assert_parallel_matches_sequential(Compression::Stored).await;
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test(flavor = "multi_thread")]
+async fn parallel_deflate_matches_sequential() {
+    assert_parallel_matches_sequential(Compression::Deflate).await;
+}
@@ -0,0 +1,57 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::{ZipArchivePatcher, ZipFileWriter};
+use crate::error::ZipError;
+use crate::{Compression, ZipDateTimeBuilder, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for name in ["first.txt", "second.txt"] {
+        let builder = ZipEntryBuilder::new(name.into(), Compression::Stored).comment("old".into());
+        writer.write_entry_whole(builder, b"data").await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn patch_timestamp_in_place() {
+    let original = archive().await;
+    let date = ZipDateTimeBuilder::new().year(2021).month(3).day(14).hour(15).minute(9).second(26).build();
+
+    let mut patcher = ZipArchivePatcher::new(Cursor::new(original.clone())).await.unwrap();
+    patcher.set_last_modification_date(1, date).await.unwrap();
+    patcher.set_external_file_attribute(1, 0x20).await.unwrap();
+    patcher.set_comment(1, b"new").await.unwrap();
+    let patched = patcher.close().await.unwrap().into_inner();
+
+    assert_eq!(patched.len(), original.len());
+
+    let reader = ZipFileReader::new(patched).await.unwrap();
+    let (first, second) = (&reader.file().entries()[0], &reader.file().entries()[1]);
+    assert_eq!(first.last_modification_date(), &Default::default());
+    assert_eq!(second.last_modification_date(), &date);
+    assert_eq!(second.external_file_attribute(), 0x20);
+    assert_eq!(second.comment().as_str().unwrap(), "new");
+    assert_eq!(first.comment().as_str().unwrap(), "old");
+
+    // The local file header is patched alongside the central directory record.
+    let local = second.header_offset() as usize;
+    let bytes = reader.data();
+    assert_eq!(u16::from_le_bytes([bytes[local + 10], bytes[local + 11]]), date.time);
+    assert_eq!(u16::from_le_bytes([bytes[local + 12], bytes[local + 13]]), date.date);
+
+    let mut entry = reader.reader_with_entry(1).await.unwrap();
+    assert_eq!(entry.read_to_vec().await.unwrap(), b"data");
+}
+
+#[tokio::test]
+async fn patch_comment_length_rejected() {
+    let mut patcher = ZipArchivePatcher::new(Cursor::new(archive().await)).await.unwrap();
+
+    assert!(matches!(patcher.set_comment(0, b"longer").await, Err(ZipError::FeatureNotSupported(_))));
+    assert!(matches!(patcher.set_external_file_attribute(2, 0).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
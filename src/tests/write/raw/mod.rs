@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::header::{CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, HeaderId, LocalFileHeader};
+use crate::Compression;
+
+#[tokio::test]
+async fn hand_built_entry_round_trip() {
+    let name = b"raw.txt";
+    let extra = [0xCA, 0xFE, 0x02, 0x00, 0x01, 0x02];
+    let data = b"hand built";
+    let crc = crc32fast::hash(data);
+    let flags = GeneralPurposeFlag { encrypted: false, data_descriptor: false, filename_unicode: false };
+
+    let lfh = LocalFileHeader {
+        version: 10,
+        flags,
+        compression: 0,
+        mod_time: 0,
+        mod_date: 0x21,
+        crc,
+        compressed_size: data.len() as u32,
+        uncompressed_size: data.len() as u32,
+        file_name_length: name.len() as u16,
+        extra_field_length: extra.len() as u16,
+    };
+    let central_header = CentralDirectoryRecord {
+        v_made_by: 10,
+        v_needed: 10,
+        flags,
+        compression: 0,
+        mod_time: 0,
+        mod_date: 0x21,
+        crc,
+        compressed_size: data.len() as u32,
+        uncompressed_size: data.len() as u32,
+        file_name_length: name.len() as u16,
+        extra_field_length: extra.len() as u16,
+        file_comment_length: 0,
+        disk_start: 0,
+        inter_attr: 0,
+        exter_attr: 0,
+        lh_offset: 0,
+    };
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let written = writer.write_raw_entry(lfh, central_header, name, &extra, data).await.unwrap();
+    assert_eq!(written.bytes_written, (30 + name.len() + extra.len() + data.len()) as u64);
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.filename().as_str().unwrap(), "raw.txt");
+    assert_eq!(entry.compression(), Compression::Stored);
+    assert!(matches!(entry.extra_fields(), [ExtraField::Unknown(field)] if field.header_id == HeaderId(0xFECA)));
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data);
+}
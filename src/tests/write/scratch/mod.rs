@@ -0,0 +1,33 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::tests::allocations;
+use crate::{Compression, ZipEntryBuilder};
+
+const ENTRIES: usize = 10_000;
+
+/// Writes many tiny entries, returning the ZIP file's data and the number of allocations made whilst writing entries.
+async fn write_tiny_entries(mut writer: ZipFileWriter<Vec<u8>>) -> (Vec<u8>, usize) {
+    let builders: Vec<_> =
+        (0..ENTRIES).map(|index| ZipEntryBuilder::new(format!("{index}.txt").into(), Compression::Deflate)).collect();
+
+    let before = allocations();
+    for builder in builders {
+        writer.write_entry_whole(builder, b"tiny").await.unwrap();
+    }
+    let allocations = allocations() - before;
+
+    (writer.close().await.unwrap(), allocations)
+}
+
+#[tokio::test]
+async fn scratch_buffer_reuse() {
+    let (default, default_allocations) = write_tiny_entries(ZipFileWriter::new(Vec::new())).await;
+    let writer = ZipFileWriterBuilder::new(Vec::new()).scratch_buffer(Vec::with_capacity(1024)).build();
+    let (scratch, scratch_allocations) = write_tiny_entries(writer).await;
+
+    assert_eq!(default, scratch);
+    // The default path allocates at least one output buffer per entry, which the scratch buffer avoids entirely.
+    assert!(scratch_allocations + ENTRIES <= default_allocations);
+}
@@ -0,0 +1,47 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::{SortKey, ZipFileWriter, ZipFileWriterBuilder};
+use crate::{Compression, ZipEntryBuilder};
+
+async fn write_out_of_order(writer: ZipFileWriter<Vec<u8>>) -> ZipFileReader {
+    let mut writer = writer;
+
+    for name in ["c.txt", "B.txt", "a.txt"] {
+        let builder = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(builder, name.as_bytes()).await.unwrap();
+    }
+
+    ZipFileReader::new(writer.close().await.unwrap()).await.unwrap()
+}
+
+fn names(reader: &ZipFileReader) -> Vec<&str> {
+    reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn sort_by_name() {
+    let reader =
+        write_out_of_order(ZipFileWriterBuilder::new(Vec::new()).sort_central_directory(SortKey::Name).build()).await;
+    assert_eq!(names(&reader), ["B.txt", "a.txt", "c.txt"]);
+
+    // The data remains in the order it was written, with each record pointing to its own entry.
+    let offsets: Vec<_> = reader.file().entries().iter().map(|entry| entry.header_offset()).collect();
+    assert!(offsets[2] < offsets[0] && offsets[0] < offsets[1]);
+
+    for (index, name) in names(&reader).into_iter().enumerate() {
+        assert_eq!(reader.reader_with_entry(index).await.unwrap().read_to_vec().await.unwrap(), name.as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn sort_by_name_ignoring_case() {
+    let writer = ZipFileWriterBuilder::new(Vec::new()).sort_central_directory(SortKey::NameIgnoringAsciiCase).build();
+    assert_eq!(names(&write_out_of_order(writer).await), ["a.txt", "B.txt", "c.txt"]);
+}
+
+#[tokio::test]
+async fn insertion_order_by_default() {
+    assert_eq!(names(&write_out_of_order(ZipFileWriter::new(Vec::new())).await), ["c.txt", "B.txt", "a.txt"]);
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, DeflateStrategy, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+const STRATEGIES: [DeflateStrategy; 5] = [
+    DeflateStrategy::Default,
+    DeflateStrategy::Filtered,
+    DeflateStrategy::HuffmanOnly,
+    DeflateStrategy::Rle,
+    DeflateStrategy::Fixed,
+];
+
+/// Sorted integers with many repeated values, which the non-default strategies handle differently.
+fn sorted_integers() -> Vec<u8> {
+    (0..20_000u32).flat_map(|index| (index / 7).to_le_bytes()).collect()
+}
+
+#[tokio::test]
+async fn strategies_decode_identically() {
+    let data = sorted_integers();
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for strategy in STRATEGIES {
+        let builder =
+            ZipEntryBuilder::new(format!("{strategy:?}.whole").into(), Compression::Deflate).deflate_strategy(strategy);
+        writer.write_entry_whole(builder, &data).await.unwrap();
+
+        let builder = ZipEntryBuilder::new(format!("{strategy:?}.stream").into(), Compression::Deflate)
+            .deflate_strategy(strategy);
+        let mut entry_writer = writer.write_entry_stream(builder).await.unwrap();
+        entry_writer.write_all(&data).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let mut sizes = Vec::new();
+
+    for (index, entry) in reader.file().entries().iter().enumerate() {
+        sizes.push(entry.compressed_size());
+        assert_eq!(reader.reader_with_entry(index).await.unwrap().read_to_vec().await.unwrap(), data);
+    }
+
+    // Both the whole and stream writers produce the same output for a non-default strategy.
+    for pair in sizes[2..].chunks(2) {
+        assert_eq!(pair[0], pair[1]);
+    }
+
+    sizes.sort_unstable();
+    sizes.dedup();
+    assert!(sizes.len() > 2, "strategies should produce differing sizes: {sizes:?}");
+}
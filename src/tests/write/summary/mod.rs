@@ -0,0 +1,27 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn close_summary_matches_parsed() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        let builder = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(builder, name.as_bytes()).await.unwrap();
+    }
+    writer.comment(String::from("trailing"));
+    let (data, summary) = writer.close_with_summary().await.unwrap();
+
+    let (file, cd_offset) =
+        crate::base::read::file_with_directory_offset(Cursor::new(&data), &Default::default()).await.unwrap();
+    assert_eq!(summary.cd_offset, cd_offset);
+    assert_eq!(summary.entry_count, file.entries().len() as u64);
+    assert_eq!(summary.total_size, data.len() as u64);
+
+    // The end of central directory record (22 bytes plus the comment) immediately follows the central directory.
+    assert_eq!(summary.cd_offset + summary.cd_size + 22 + "trailing".len() as u64, summary.total_size);
+}
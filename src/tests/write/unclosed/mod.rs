@@ -0,0 +1,43 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::{Compression, ZipEntryBuilder};
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn writer_dropped_without_close() {
+    let writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).panic_if_unclosed().build();
+    assert!(catch_unwind(AssertUnwindSafe(move || drop(writer))).is_err());
+
+    // Without opting in, the writer is dropped quietly.
+    drop(ZipFileWriter::new(Vec::<u8>::new()));
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn writer_dropped_without_close_warns() {
+    crate::tests::init_logger();
+    crate::tests::take_warnings();
+
+    drop(ZipFileWriter::new(Vec::<u8>::new()));
+
+    let warnings = crate::tests::take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("ZipFileWriter was dropped without close()"), "{warnings:?}");
+}
+
+#[tokio::test]
+async fn entry_writer_dropped_without_close() {
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).panic_if_unclosed().build();
+
+    let entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("a.txt".into(), Compression::Stored)).await.unwrap();
+    assert!(catch_unwind(AssertUnwindSafe(move || drop(entry_writer))).is_err());
+
+    let entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("b.txt".into(), Compression::Stored)).await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap();
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Returns the "version needed to extract" fields of the first local file header and central directory record.
+fn versions_needed(data: &[u8]) -> (u16, u16) {
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let record = data.windows(4).position(|window| window == signature).unwrap();
+    (u16::from_le_bytes([data[4], data[5]]), u16::from_le_bytes([data[record + 6], data[record + 7]]))
+}
+
+#[tokio::test]
+async fn min_version_pinned() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).min_version(45);
+    writer.write_entry_whole(builder, b"data").await.unwrap();
+    assert_eq!(versions_needed(&writer.close().await.unwrap()), (45, 45));
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("file.txt".into(), Compression::Stored).min_version(45);
+    writer.write_entry_stream(builder).await.unwrap().close().await.unwrap();
+    assert_eq!(versions_needed(&writer.close().await.unwrap()), (45, 45));
+}
+
+#[tokio::test]
+async fn min_version_below_required() {
+    // Directories require version 2.0.
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("dir/".into(), Compression::Stored).min_version(10);
+
+    assert!(matches!(writer.write_entry_whole(builder, &[]).await, Err(ZipError::InvalidVersion(10, 20))));
+}
+
+#[tokio::test]
+async fn spec_version_made_by_pinned() {
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).spec_version_made_by(20).build();
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"data").await.unwrap();
+
+    let mut entry = writer.write_entry_stream(ZipEntryBuilder::new("b.txt".into(), Compression::Stored)).await.unwrap();
+    futures_lite::io::AsyncWriteExt::write_all(&mut entry, b"data").await.unwrap();
+    entry.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let records: Vec<usize> = (0..data.len() - 4).filter(|&offset| data[offset..offset + 4] == signature).collect();
+    assert_eq!(records.len(), 2);
+
+    for record in records {
+        assert_eq!(data[record + 4], 20);
+    }
+}
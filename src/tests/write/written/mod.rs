@@ -0,0 +1,89 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{ZipFileWriter, ZipFileWriterBuilder};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn written_entry_sizes() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+
+    let whole = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored);
+    let whole = writer.write_entry_whole(whole, b"whole entry data").await.unwrap();
+    assert_eq!(whole.compressed_size, 16);
+    assert_eq!(whole.bytes_written, writer.writer.offset());
+
+    let before = writer.writer.offset();
+    let mut stream =
+        writer.write_entry_stream(ZipEntryBuilder::new("stream.txt".into(), Compression::Stored)).await.unwrap();
+    stream.write_all(b"streamed entry data").await.unwrap();
+    let stream = stream.close().await.unwrap();
+    assert_eq!(stream.compressed_size, 19);
+    assert_eq!(stream.bytes_written, writer.writer.offset() - before);
+
+    let data = writer.close().await.unwrap();
+    assert!(data.len() as u64 > whole.bytes_written + stream.bytes_written);
+}
+
+#[tokio::test]
+async fn stream_bytes_written_progress() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let mut stream =
+        writer.write_entry_stream(ZipEntryBuilder::new("stream.txt".into(), Compression::Stored)).await.unwrap();
+    assert_eq!(stream.bytes_written(), 0);
+
+    let mut progress = Vec::new();
+    for chunk in [&b"first"[..], b"second", b"third"] {
+        stream.write_all(chunk).await.unwrap();
+        progress.push(stream.bytes_written());
+    }
+
+    assert_eq!(progress, [5, 11, 16]);
+    stream.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn write_entry_from_reader_round_trip() {
+    use crate::base::read::mem::ZipFileReader;
+    use futures_lite::io::Cursor;
+
+    let data: Vec<u8> = (0..1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let builder = ZipEntryBuilder::new("file.bin".into(), Compression::Stored);
+    let written = writer.write_entry_from_reader(builder, Cursor::new(&data)).await.unwrap();
+    assert_eq!(written.compressed_size, data.len() as u64);
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.reader_with_entry(0).await.unwrap().read_to_vec().await.unwrap(), data);
+}
+
+#[tokio::test]
+async fn write_entry_from_reader_error() {
+    use crate::base::read::mem::ZipFileReader;
+    use futures_lite::io::AsyncRead;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    struct FailingReader;
+
+    impl AsyncRead for FailingReader {
+        fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, _: &mut [u8]) -> Poll<std::io::Result<usize>> {
+            Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "broken pipe")))
+        }
+    }
+
+    // The abandoned entry writer doesn't panic on drop, despite never being closed.
+    let mut writer = ZipFileWriterBuilder::new(Vec::<u8>::new()).panic_if_unclosed().build();
+    let builder = ZipEntryBuilder::new("file.bin".into(), Compression::Stored);
+    let result = writer.write_entry_from_reader(builder, FailingReader).await;
+    assert!(
+        matches!(result, Err(crate::error::ZipError::UpstreamReadError(e)) if e.kind() == std::io::ErrorKind::BrokenPipe)
+    );
+
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"first").await.unwrap();
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 1);
+}
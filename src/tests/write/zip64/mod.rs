@@ -106,8 +106,8 @@ async fn test_write_large_zip64_file_self_read() {
     init_logger();
 
     // Allocate space with some extra for metadata records
-    let mut buffer = Vec::with_capacity(BATCHED_FILE_SIZE + 100_000);
-    let mut writer = ZipFileWriter::new(&mut buffer);
+    let buffer = Vec::with_capacity(BATCHED_FILE_SIZE + 100_000);
+    let mut writer = ZipFileWriter::new(futures_lite::io::Cursor::new(buffer));
 
     let entry = ZipEntryBuilder::new("file".into(), Compression::Stored);
     let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
@@ -115,7 +115,12 @@ async fn test_write_large_zip64_file_self_read() {
         entry_writer.write_all(&[0; BATCH_SIZE]).await.unwrap();
     }
     entry_writer.close().await.unwrap();
-    writer.close().await.unwrap();
+    let buffer = writer.close().await.unwrap().into_inner();
+
+    // The data descriptor follows the local file header (30 bytes), filename, zip64 extra field, and data.
+    let descriptor = 30 + "file".len() + 20 + BATCHED_FILE_SIZE;
+    let sizes = &buffer[descriptor + 8..descriptor + 24];
+    assert_eq!(sizes, [(BATCHED_FILE_SIZE as u64).to_le_bytes(), (BATCHED_FILE_SIZE as u64).to_le_bytes()].concat());
 
     let reader = crate::base::read::mem::ZipFileReader::new(buffer).await.unwrap();
     assert!(reader.file().zip64);
@@ -169,7 +174,7 @@ async fn test_zip64_when_many_files_whole() {
     let mut writer = ZipFileWriter::new(&mut sink);
     for i in 0..=u16::MAX as u32 + 1 {
         let entry = ZipEntryBuilder::new(format!("{i}").into(), Compression::Stored);
-        writer.write_entry_whole(entry, &[]).await.unwrap()
+        writer.write_entry_whole(entry, &[]).await.unwrap();
     }
     assert!(writer.is_zip64);
     writer.close().await.unwrap();
@@ -198,7 +203,7 @@ async fn test_force_no_zip64_errors_with_too_many_files_whole() {
     let mut writer = ZipFileWriter::new(&mut sink).force_no_zip64();
     for i in 0..u16::MAX {
         let entry = ZipEntryBuilder::new(format!("{i}").into(), Compression::Stored);
-        writer.write_entry_whole(entry, &[]).await.unwrap()
+        writer.write_entry_whole(entry, &[]).await.unwrap();
     }
     let entry = ZipEntryBuilder::new("65537".to_string().into(), Compression::Stored);
     let result = writer.write_entry_whole(entry, &[]).await;
@@ -10,6 +10,7 @@
 //! - [`base::read::seek::ZipFileReader::with_tokio()`]
 //! - [`base::read::stream::ZipFileReader::with_tokio()`]
 //! - [`base::write::ZipFileWriter::with_tokio()`]
+//! - [`base::write::ZipFileWriterBuilder::with_tokio()`]
 //!
 //! As a result of Rust's type inference, we are able to reuse the [`base`] implementation's types with considerable
 //! ease. There only exists one caveat with their use; the types returned by these constructors contain a wrapping
@@ -36,6 +37,9 @@ pub mod write {
     /// A [`tokio`]-specific type alias for [`base::write::ZipFileWriter`];
     pub type ZipFileWriter<W> = crate::base::write::ZipFileWriter<Compat<W>>;
 
+    /// A [`tokio`]-specific type alias for [`base::write::ZipFileWriterBuilder`];
+    pub type ZipFileWriterBuilder<W> = crate::base::write::ZipFileWriterBuilder<Compat<W>>;
+
     /// A [`tokio`]-specific type alias for [`base::write::EntryStreamWriter`];
     pub type EntryStreamWriter<'a, W> = crate::base::write::EntryStreamWriter<'a, Compat<W>>;
 }
@@ -71,15 +71,49 @@
 use crate::base::read::seek;
 
 use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::entry::PathPrefixPolicy;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncRead, AsyncWriteExt};
 
 use tokio::fs::File;
 use tokio::io::BufReader;
-use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+/// The behaviour used when an extracted file's target path already exists.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Return an error and halt extraction.
+    #[default]
+    Error,
+    /// Leave the existing file untouched and move on to the next entry.
+    Skip,
+    /// Replace the existing file with the entry's data.
+    Overwrite,
+}
+
+/// Options which control how entries are extracted via [`ZipFileReader::extract()`].
+#[derive(Debug, Default, Clone)]
+pub struct ExtractOptions {
+    /// The behaviour used when an extracted file's target path already exists.
+    pub overwrite: OverwritePolicy,
+    /// The handling of entries whose filenames begin with a Windows drive or UNC prefix.
+    pub path_prefixes: PathPrefixPolicy,
+}
+
+/// The default capacity of the buffer between an entry's file and its decompressor.
+///
+/// Larger buffers reduce the number of file reads needed for large entries, whilst smaller buffers suit
+/// memory-constrained environments. See [`ZipFileReader::reader_with_entry_and_capacity()`].
+pub const DEFAULT_BUFFER_CAPACITY: usize = 64 * 1024;
 
 struct Inner {
     path: PathBuf,
@@ -117,6 +151,27 @@ impl ZipFileReader {
         &self.inner.file
     }
 
+    /// Returns this ZIP file's trailing comment as raw bytes, or None if no comment is present.
+    ///
+    /// See [`ZipFile::comment_bytes()`] for more information.
+    pub fn comment_bytes(&self) -> Option<&[u8]> {
+        self.file().comment_bytes()
+    }
+
+    /// Returns this ZIP file's trailing comment decoded as UTF-8, or None if no comment is present.
+    ///
+    /// Invalid UTF-8 sequences are replaced with [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    pub fn comment(&self) -> Option<Cow<'_, str>> {
+        self.comment_bytes().map(String::from_utf8_lossy)
+    }
+
+    /// Returns whether this ZIP file was read via a ZIP64 end of central directory record.
+    ///
+    /// See [`ZipFile::zip64()`] for more information.
+    pub fn is_zip64(&self) -> bool {
+        self.file().zip64()
+    }
+
     /// Returns the file system path provided to the reader during construction.
     pub fn path(&self) -> &Path {
         &self.inner.path
@@ -128,33 +183,250 @@ impl ZipFileReader {
         index: usize,
     ) -> Result<ZipEntryReader<'static, Compat<BufReader<File>>, WithoutEntry>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut fs_file = BufReader::new(File::open(&self.inner.path).await?).compat();
+        let file = File::open(&self.inner.path).await?;
+        let mut fs_file = BufReader::with_capacity(DEFAULT_BUFFER_CAPACITY, file).compat();
 
         stored_entry.seek_to_data_offset(&mut fs_file).await?;
 
-        Ok(ZipEntryReader::new_with_owned(
+        ZipEntryReader::new_with_owned(
             fs_file,
-            stored_entry.entry.compression(),
+            stored_entry.entry.readable_compression()?,
             stored_entry.entry.compressed_size(),
-        ))
+        )
     }
 
     /// Returns a new entry reader if the provided index is valid.
+    ///
+    /// The entry's file is buffered with a capacity of [`DEFAULT_BUFFER_CAPACITY`].
     pub async fn reader_with_entry(
         &self,
         index: usize,
+    ) -> Result<ZipEntryReader<'_, Compat<BufReader<File>>, WithEntry<'_>>> {
+        self.reader_with_entry_and_capacity(index, DEFAULT_BUFFER_CAPACITY).await
+    }
+
+    /// Returns a new entry reader if the provided index is valid, buffering the entry's file with the given capacity.
+    pub async fn reader_with_entry_and_capacity(
+        &self,
+        index: usize,
+        capacity: usize,
     ) -> Result<ZipEntryReader<'_, Compat<BufReader<File>>, WithEntry<'_>>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut fs_file = BufReader::new(File::open(&self.inner.path).await?).compat();
+        let file = File::open(&self.inner.path).await?;
+        let mut fs_file = BufReader::with_capacity(capacity, file).compat();
 
         stored_entry.seek_to_data_offset(&mut fs_file).await?;
 
         let reader = ZipEntryReader::new_with_owned(
             fs_file,
-            stored_entry.entry.compression(),
+            stored_entry.entry.readable_compression()?,
             stored_entry.entry.compressed_size(),
-        );
+        )?;
 
         Ok(reader.into_with_entry(stored_entry))
     }
+
+    /// Returns an owned entry reader if the provided index is valid.
+    ///
+    /// Unlike [`ZipFileReader::reader_with_entry()`], the returned reader holds its own handle to this ZIP reader and
+    /// so can be cloned into further independent readers over the same entry via [`EntryReader::try_clone()`].
+    pub async fn entry_reader(&self, index: usize) -> Result<EntryReader> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let reader = self.reader_without_entry(index).await?;
+
+        Ok(EntryReader { reader: self.clone(), index, inner: reader.into_with_entry_owned(stored_entry.entry.clone()) })
+    }
+
+    /// Reads every entry concurrently, returning each entry's filename and data in entry order.
+    ///
+    /// Each entry is read within its own [`tokio`] task (opening its own [`File`]), with at most `limit` tasks running
+    /// at once. The CRC32 value of each entry is verified against that stored within the archive, and the first error
+    /// encountered is returned. Any invalid UTF-8 sequences within filenames are replaced with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    ///
+    /// Note that this must be called from within a [`tokio`] runtime.
+    pub async fn read_all_concurrent(&self, limit: usize) -> Result<Vec<(String, Vec<u8>)>> {
+        let results = self.read_concurrent(limit, true).await?;
+        let entries = self.inner.file.entries.iter();
+
+        entries
+            .zip(results)
+            .map(|(entry, data)| Ok((String::from_utf8_lossy(entry.filename().as_bytes()).into(), data?)))
+            .collect()
+    }
+
+    /// Reads every entry concurrently, returning each entry's filename and the result of reading it in entry order.
+    ///
+    /// Unlike [`ZipFileReader::read_all_concurrent()`], an error reading one entry (eg. a CRC32 mismatch) doesn't stop
+    /// the others from being read. As the central directory drives iteration, each entry's filename is still returned,
+    /// with any invalid UTF-8 sequences replaced with [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    ///
+    /// Note that this must be called from within a [`tokio`] runtime.
+    pub async fn read_all_concurrent_lenient(&self, limit: usize) -> Vec<(String, Result<Vec<u8>>)> {
+        // Errors are only returned early when failing fast.
+        let results = self.read_concurrent(limit, false).await.unwrap_or_default();
+        let entries = self.inner.file.entries.iter();
+
+        entries
+            .zip(results)
+            .map(|(entry, data)| (String::from_utf8_lossy(entry.filename().as_bytes()).into(), data))
+            .collect()
+    }
+
+    /// Reads every entry concurrently, returning the result of reading each in entry order.
+    ///
+    /// If `fail_fast` is set, the first error encountered is returned and any remaining reads are cancelled. A panic
+    /// within any read is propagated, whilst a read cancelled by the runtime (eg. as it's shutting down) results in an
+    /// [`std::io::ErrorKind::Interrupted`] error for that entry.
+    async fn read_concurrent(&self, limit: usize, fail_fast: bool) -> Result<Vec<Result<Vec<u8>>>> {
+        let count = self.inner.file.entries.len();
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut results: Vec<Option<Result<Vec<u8>>>> = (0..count).map(|_| None).collect();
+        let mut indices = std::collections::HashMap::with_capacity(limit.max(1));
+        let mut next = 0;
+
+        loop {
+            while next < count && tasks.len() < limit.max(1) {
+                let (reader, index) = (self.clone(), next);
+                let handle = tasks.spawn(async move { reader.read_entry_checked(index).await });
+                indices.insert(handle.id(), index);
+                next += 1;
+            }
+
+            let Some(joined) = tasks.join_next_with_id().await else {
+                break;
+            };
+
+            let (index, result) = match joined {
+                Ok((id, result)) => (indices[&id], result),
+                Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+                Err(err) => (indices[&err.id()], Err(std::io::Error::new(std::io::ErrorKind::Interrupted, err).into())),
+            };
+            results[index] = match (fail_fast, result) {
+                (true, Err(error)) => return Err(error),
+                (_, result) => Some(result),
+            };
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Reads an entry's data in full if the provided index is valid, verifying its CRC32 value.
+    async fn read_entry_checked(&self, index: usize) -> Result<Vec<u8>> {
+        self.entry_reader(index).await?.read_to_vec().await
+    }
+
+    /// Extracts every entry into the provided directory.
+    ///
+    /// Entries whose filenames end with a `/` are treated as directories. Each entry is extracted to the path returned
+    /// by [`ZipEntry::safe_path_with()`], preventing [directory traversal
+    /// attacks](https://en.wikipedia.org/wiki/Directory_traversal_attack). Entries for which that path is empty (eg.
+    /// a filename of `..`) would resolve to the directory itself, and so are skipped.
+    ///
+    /// Each file is first written alongside its target path with a `.partial` suffix, and only moved into place once
+    /// its CRC32 value has been verified against that stored within the archive. If extraction fails, the partial file
+    /// is removed, so no corrupted data is left behind.
+    ///
+    /// [`ZipEntry::safe_path_with()`]: crate::ZipEntry::safe_path_with
+    pub async fn extract<P>(&self, directory: P, options: &ExtractOptions) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        for (index, entry) in self.inner.file.entries.iter().enumerate() {
+            let relative = entry.safe_path_with(options.path_prefixes)?;
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+
+            let path = directory.as_ref().join(relative);
+
+            if entry.dir()? {
+                tokio::fs::create_dir_all(&path).await?;
+                continue;
+            }
+
+            if tokio::fs::try_exists(&path).await? {
+                match options.overwrite {
+                    OverwritePolicy::Error => return Err(ZipError::ExtractTargetExists(path)),
+                    OverwritePolicy::Skip => continue,
+                    OverwritePolicy::Overwrite => (),
+                }
+            }
+
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+
+            let mut partial = path.clone().into_os_string();
+            partial.push(".partial");
+            let partial = PathBuf::from(partial);
+
+            if let Err(err) = self.extract_file(index, &partial).await {
+                let _ = tokio::fs::remove_file(&partial).await;
+                return Err(err);
+            }
+
+            tokio::fs::rename(&partial, &path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes an entry's data to a file at the provided path, verifying its CRC32 value.
+    async fn extract_file(&self, index: usize, path: &Path) -> Result<()> {
+        let mut reader = self.reader_with_entry(index).await?;
+        let mut writer = File::create(path).await?.compat_write();
+        futures_lite::io::copy(&mut reader, &mut writer).await?;
+        writer.close().await?;
+
+        if reader.compute_hash() != reader.entry().crc32() {
+            return Err(ZipError::CRC32CheckError);
+        }
+
+        Ok(())
+    }
+}
+
+/// An owned ZIP entry reader which can be cloned into independent readers over the same entry.
+///
+/// This dereferences to the underlying [`ZipEntryReader`], providing access to its checked reading helpers.
+pub struct EntryReader {
+    reader: ZipFileReader,
+    index: usize,
+    inner: ZipEntryReader<'static, Compat<BufReader<File>>, WithEntry<'static>>,
+}
+
+impl EntryReader {
+    /// Returns a new independent reader over the same entry.
+    ///
+    /// The new reader opens its own [`File`] and starts from the beginning of the entry's data, regardless of how much
+    /// of the entry has been read by this reader.
+    pub async fn try_clone(&self) -> Result<EntryReader> {
+        self.reader.entry_reader(self.index).await
+    }
+
+    /// Returns the index of the entry being read.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Deref for EntryReader {
+    type Target = ZipEntryReader<'static, Compat<BufReader<File>>, WithEntry<'static>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for EntryReader {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl AsyncRead for EntryReader {
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_read(c, b)
+    }
 }
@@ -3,14 +3,32 @@
 
 use crate::error::{Result, ZipError};
 use futures_lite::io::{AsyncRead, AsyncReadExt};
+use std::io::ErrorKind;
+
+// Log a warning via the `log` crate if the `log` feature is enabled, or do nothing otherwise.
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::warn!($($arg)+);
+    };
+}
+
+pub(crate) use log_warn;
+
+// Read a fixed-length header from a reader which impls AsyncRead, distinguishing truncation from other IO errors.
+pub(crate) async fn read_header<R: AsyncRead + Unpin, const N: usize>(reader: &mut R) -> Result<[u8; N]> {
+    let mut buffer = [0; N];
+
+    match reader.read_exact(&mut buffer).await {
+        Ok(()) => Ok(buffer),
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => Err(ZipError::UnexpectedEof),
+        Err(error) => Err(error.into()),
+    }
+}
 
 // Assert that the next four-byte signature read by a reader which impls AsyncRead matches the expected signature.
 pub(crate) async fn assert_signature<R: AsyncRead + Unpin>(reader: &mut R, expected: u32) -> Result<()> {
-    let signature = {
-        let mut buffer = [0; 4];
-        reader.read_exact(&mut buffer).await?;
-        u32::from_le_bytes(buffer)
-    };
+    let signature = u32::from_le_bytes(read_header(reader).await?);
     match signature {
         actual if actual == expected => Ok(()),
         actual => Err(ZipError::UnexpectedHeaderError(actual, expected)),
@@ -1,6 +1,9 @@
 // Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+// Not every integration test binary makes use of every shared helper.
+#![allow(dead_code)]
+
 use async_zip::base::read::mem;
 use async_zip::base::read::seek;
 use async_zip::base::write::ZipFileWriter;
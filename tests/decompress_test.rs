@@ -6,7 +6,9 @@ use tokio_util::compat::TokioAsyncReadCompatExt;
 
 mod common;
 
+#[cfg(feature = "zstd")]
 const ZSTD_ZIP_FILE: &str = "tests/test_inputs/sample_data.zstd.zip";
+#[cfg(feature = "deflate")]
 const DEFLATE_ZIP_FILE: &str = "tests/test_inputs/sample_data.deflate.zip";
 const STORE_ZIP_FILE: &str = "tests/test_inputs/sample_data.store.zip";
 const UTF8_EXTRA_ZIP_FILE: &str = "tests/test_inputs/sample_data_utf8_extra.zip";